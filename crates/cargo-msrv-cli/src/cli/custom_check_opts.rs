@@ -7,21 +7,21 @@ pub struct CustomCheckOpts {
     /// check command.
     ///
     /// If a custom compatibility check command is used, this option is ignored.
-    #[arg(long, value_delimiter = ' ')]
+    #[arg(long, value_delimiter = ' ', env = "CARGO_MSRV_FEATURES")]
     pub features: Option<Vec<String>>,
 
     /// Forwards the --all-features flag to cargo, when running cargo-msrv with the default compatibility
     /// check command.
     ///
     /// If a custom compatibility check command is used, this option is ignored.
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_ALL_FEATURES")]
     pub all_features: bool,
 
     /// Forwards the --no-default-features flag to cargo, when running cargo-msrv with the default compatibility
     /// check command.
     ///
     /// If a custom compatibility check command is used, this option is ignored.
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_NO_DEFAULT_FEATURES")]
     pub no_default_features: bool,
 
     /// Supply a custom command to be used by cargo msrv.
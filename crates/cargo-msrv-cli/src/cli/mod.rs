@@ -2,12 +2,16 @@ use crate::cli::custom_check_opts::CustomCheckOpts;
 use crate::cli::rust_releases_opts::RustReleasesOpts;
 use crate::cli::shared_opts::SharedOpts;
 use crate::cli::toolchain_opts::ToolchainOpts;
-use crate::values::list_msrv_variant;
-use cargo_msrv_context::types::ListMsrvVariant;
+use crate::values::{list_msrv_variant, release_source, search_method, target_dir_cleanup};
+use cargo_msrv_context::types::{ListMsrvVariant, ReleaseSource};
+use cargo_msrv_context::{SearchMethod, TargetDirCleanup};
 use cargo_msrv_types::BareVersion;
-use clap::{Args, Parser, Subcommand};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
 use clap_cargo::style::CLAP_STYLING;
 use std::ffi::{OsStr, OsString};
+use std::num::NonZeroUsize;
 
 pub mod custom_check_opts;
 pub mod rust_releases_opts;
@@ -23,8 +27,52 @@ pub struct CargoCli {
 
 impl CargoCli {
     pub fn parse_args<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Self {
-        let modified_args = modify_args(args);
-        CargoCli::parse_from(modified_args)
+        Self::try_parse_args(args, |name| std::env::var_os(name)).unwrap_or_else(|err| err.exit())
+    }
+
+    // Parse the arguments, with the environment variables given by `var`, for the options which
+    // are given by an environment variable as a layer below the command line.
+    fn try_parse_args<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(
+        args: I,
+        var: impl Fn(&OsStr) -> Option<OsString>,
+    ) -> Result<Self, clap::Error> {
+        let modified_args = modify_args(args).into_iter().collect::<Vec<_>>();
+
+        validate_env(CargoCli::command(), &modified_args, &var)?;
+
+        let mut command = without_layered_env(CargoCli::command());
+        let matches = match command.try_get_matches_from_mut(&modified_args) {
+            Ok(matches) => matches,
+            // The help is rendered by the command which still lists each environment variable
+            Err(err) if err.use_stderr() => return Err(err),
+            Err(err) => {
+                return Err(CargoCli::command()
+                    .try_get_matches_from(modified_args)
+                    .err()
+                    .unwrap_or(err));
+            }
+        };
+        let mut cli =
+            CargoCli::from_arg_matches(&matches).map_err(|err| err.format(&mut command))?;
+
+        let CargoMsrvCli::Msrv(opts) = &mut cli.subcommand;
+        let matches = innermost_matches(&matches);
+        opts.split_env_values(matches);
+
+        if let SubCommand::Find(find_opts) = &mut opts.subcommand {
+            let mut command = CargoCli::command();
+            command.build();
+            let find = command
+                .find_subcommand("msrv")
+                .and_then(|msrv| msrv.find_subcommand("find"))
+                .expect("cargo msrv find is a subcommand");
+
+            find_opts
+                .apply_layered_env(find, matches, &var)
+                .map_err(|err| err.with_cmd(find))?;
+        }
+
+        Ok(cli)
     }
 
     pub fn to_cargo_msrv_cli(self) -> CargoMsrvCli {
@@ -63,6 +111,184 @@ fn modify_args<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(
     args
 }
 
+// Options which can be given more than once, and of which the values are separated by commas
+// when given by an environment variable, like `CARGO_MSRV_PACKAGE=a,b`. On the command line, each
+// value is given by its own option instead, like Cargo does.
+const ENV_VALUE_DELIMITED: &[&str] = &["package", "exclude", "target", "component"];
+const ENV_VALUE_DELIMITER: char = ',';
+
+// Options of `cargo msrv find` which are given by an environment variable as a layer below the
+// command line, rather than by clap. Clap counts values of environment variables when it checks for
+// conflicting options, so `CARGO_MSRV_LINEAR=true cargo msrv find --bisect` would be rejected,
+// instead of the option given on the command line taking precedence over the variable.
+const LAYERED_ENV: &[&str] = &["bisect", "linear", "multi_bisect", "search_method", "jobs"];
+
+// The command, of which the options in `LAYERED_ENV` don't read their environment variable.
+fn without_layered_env(command: clap::Command) -> clap::Command {
+    command.mut_subcommand("msrv", |msrv| {
+        msrv.mut_subcommand("find", |find| {
+            LAYERED_ENV
+                .iter()
+                .fold(find, |find, id| find.mut_arg(*id, |arg| arg.env(None)))
+        })
+    })
+}
+
+// The value of the environment variable of the option with the given id, if it's set.
+fn env_value<'cmd, T: Clone + Send + Sync + 'static>(
+    command: &'cmd clap::Command,
+    id: &str,
+    var: &impl Fn(&OsStr) -> Option<OsString>,
+) -> Result<Option<(&'cmd OsStr, T)>, clap::Error> {
+    let Some(arg) = command.get_arguments().find(|arg| arg.get_id() == id) else {
+        return Ok(None);
+    };
+    let Some((name, value)) = arg
+        .get_env()
+        .and_then(|name| var(name).map(|value| (name, value)))
+    else {
+        return Ok(None);
+    };
+
+    let matches = parse_env_value(arg, name, value)?;
+
+    Ok(matches
+        .get_one::<T>("value")
+        .map(|value| (name, value.clone())))
+}
+
+// The matches of the invoked subcommand, to which global options are propagated.
+fn innermost_matches(matches: &ArgMatches) -> &ArgMatches {
+    match matches.subcommand() {
+        Some((_, subcommand)) => innermost_matches(subcommand),
+        None => matches,
+    }
+}
+
+// Split the values of the option with the given id on commas, if they were given by an
+// environment variable.
+fn split_env_value(matches: &ArgMatches, id: &str, values: &mut Vec<String>) {
+    let from_env = matches.ids().any(|present| present == id)
+        && matches.value_source(id) == Some(ValueSource::EnvVariable);
+
+    if from_env {
+        *values = values
+            .iter()
+            .flat_map(|value| value.split(ENV_VALUE_DELIMITER))
+            .map(String::from)
+            .collect();
+    }
+}
+
+// Each option can also be given by a `CARGO_MSRV_*` environment variable, which is used when the
+// option is absent from the command line. Clap parses these values like any other, but its errors
+// don't name the variable, so we validate them up front. Only the options of the invoked
+// subcommand are validated, since the variables of other subcommands are not used.
+fn validate_env(
+    mut command: clap::Command,
+    args: &[OsString],
+    var: impl Fn(&OsStr) -> Option<OsString>,
+) -> Result<(), clap::Error> {
+    command.build();
+
+    // Errors, including those of the environment variables, are reported by the actual parse
+    let matches = command
+        .clone()
+        .ignore_errors(true)
+        .try_get_matches_from(args)
+        .unwrap_or_default();
+
+    let mut command = &command;
+    let mut matches = &matches;
+
+    while let Some((name, subcommand_matches)) = matches.subcommand() {
+        let Some(subcommand) = command.find_subcommand(name) else {
+            break;
+        };
+
+        command = subcommand;
+        matches = subcommand_matches;
+    }
+
+    validate_command_env(command, &var)
+}
+
+// Validate the environment variables of the options of the command, including the global options
+// propagated to it.
+fn validate_command_env(
+    command: &clap::Command,
+    var: &impl Fn(&OsStr) -> Option<OsString>,
+) -> Result<(), clap::Error> {
+    for arg in command.get_arguments() {
+        let Some((name, value)) = arg
+            .get_env()
+            .and_then(|name| var(name).map(|value| (name, value)))
+        else {
+            continue;
+        };
+
+        let delimiter = arg.get_value_delimiter().or_else(|| {
+            ENV_VALUE_DELIMITED
+                .contains(&arg.get_id().as_str())
+                .then_some(ENV_VALUE_DELIMITER)
+        });
+
+        let values = match delimiter {
+            Some(delimiter) => value
+                .to_string_lossy()
+                .split(delimiter)
+                .map(OsString::from)
+                .collect(),
+            None => vec![value],
+        };
+
+        for value in values {
+            parse_env_value(arg, name, value).map_err(|err| err.with_cmd(command))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Parse the value of the environment variable of an option on its own, with the value parser of
+// the option. The parsed value has the id `value`.
+fn parse_env_value(
+    arg: &clap::Arg,
+    name: &OsStr,
+    value: OsString,
+) -> Result<ArgMatches, clap::Error> {
+    let parse = clap::Command::new("env").arg(
+        clap::Arg::new("value")
+            .value_parser(arg.get_value_parser().clone())
+            .allow_hyphen_values(true),
+    );
+
+    parse
+        .try_get_matches_from([OsString::new(), value.clone()])
+        .map_err(|err| {
+            let mut message = format!(
+                "invalid value '{}' of environment variable '{}'",
+                value.to_string_lossy(),
+                name.to_string_lossy(),
+            );
+
+            // Flags don't take values on the command line, so ask the value parser, rather
+            // than the option, which values are possible
+            let possible_values = arg.get_value_parser().possible_values();
+            if let Some(source) = std::error::Error::source(&err) {
+                message.push_str(&format!(": {}", source));
+            } else if let Some(possible_values) = possible_values {
+                let names = possible_values
+                    .filter(|value| !value.is_hide_set())
+                    .map(|value| value.get_name().to_string())
+                    .collect::<Vec<_>>();
+                message.push_str(&format!(" [possible values: {}]", names.join(", ")));
+            }
+
+            clap::Error::raw(ErrorKind::InvalidValue, format!("{}\n", message))
+        })
+}
+
 #[derive(Debug, Subcommand)]
 pub enum CargoMsrvCli {
     /// Find your Minimum Supported Rust Version!
@@ -103,9 +329,32 @@ pub struct CargoMsrvOpts {
     pub subcommand: SubCommand,
 }
 
+impl CargoMsrvOpts {
+    // Split the values of options given by environment variables, like `CARGO_MSRV_TARGET`, on
+    // commas, given the matches of the invoked subcommand.
+    fn split_env_values(&mut self, matches: &ArgMatches) {
+        let workspace = &mut self.shared_opts.workspace;
+        split_env_value(matches, "package", &mut workspace.package);
+        split_env_value(matches, "exclude", &mut workspace.exclude);
+
+        let toolchain_opts = match &mut self.subcommand {
+            SubCommand::Find(opts) => &mut opts.toolchain_opts,
+            SubCommand::Verify(opts) => &mut opts.toolchain_opts,
+            _ => return,
+        };
+
+        split_env_value(matches, "target", &mut toolchain_opts.target);
+        split_env_value(matches, "component", &mut toolchain_opts.component);
+    }
+}
+
 #[derive(Debug, Subcommand)]
 #[command(propagate_version = true)]
 pub enum SubCommand {
+    /// Manage the cache of compatibility check outcomes
+    Cache(CacheOpts),
+    /// Diagnose the environment in which cargo-msrv runs
+    Doctor(DoctorOpts),
     /// Find the MSRV
     Find(FindOpts),
     /// Display the MSRV's of dependencies
@@ -114,6 +363,8 @@ pub enum SubCommand {
     Set(SetOpts),
     /// Show the MSRV of your crate, as specified in the Cargo manifest
     Show,
+    /// Manage the toolchains installed by cargo-msrv
+    Toolchains(ToolchainsOpts),
     /// Verify whether the MSRV is satisfiable.
     ///
     ///  The MSRV must be specified via the `--rust-version` option, or via the 'package.rust-version' or 'package.metadata.msrv' keys in the Cargo.toml manifest.
@@ -129,33 +380,161 @@ pub struct FindOpts {
     /// When the search space is sufficiently large, which is common, this is much
     /// faster than a linear search. A binary search will approximately halve the search
     /// space for each Rust version checked for compatibility.
-    #[arg(long, conflicts_with = "linear")]
+    #[arg(
+        long,
+        conflicts_with_all = ["linear", "multi_bisect", "search_method"],
+        env = "CARGO_MSRV_BISECT"
+    )]
     pub bisect: bool,
 
     /// Use a linear search to find the MSRV
     ///
     /// This method checks toolchain from the most recent release to the earliest.
-    #[arg(long, conflicts_with = "bisect")]
+    #[arg(
+        long,
+        conflicts_with_all = ["bisect", "multi_bisect", "search_method"],
+        env = "CARGO_MSRV_LINEAR"
+    )]
     pub linear: bool,
 
+    /// Use a k-ary search, which checks multiple Rust versions concurrently, to find the MSRV
+    ///
+    /// Each step checks up to `--jobs` Rust versions concurrently, which divide the search space
+    /// into equally sized parts. With more jobs, fewer steps are needed than with a binary search.
+    #[arg(
+        long,
+        conflicts_with_all = ["bisect", "linear", "search_method"],
+        env = "CARGO_MSRV_MULTI_BISECT"
+    )]
+    pub multi_bisect: bool,
+
+    /// The search method used to find the MSRV
+    ///
+    /// The `anchored` search method starts from the MSRV declared in the Cargo manifest, and
+    /// checks it and its predecessor first. When the MSRV has changed, it gallops away from the
+    /// declared MSRV, in steps of 1, 2, 4, 8, ... releases, and then bisects the remainder. If no
+    /// MSRV is declared, a binary search is used instead.
+    #[arg(
+        long,
+        value_name = "METHOD",
+        value_parser = search_method::VALUES.parser(),
+        conflicts_with_all = ["bisect", "linear", "multi_bisect"],
+        env = "CARGO_MSRV_SEARCH_METHOD"
+    )]
+    pub search_method: Option<SearchMethod>,
+
+    /// The maximum amount of compatibility checks to run concurrently
+    ///
+    /// Requires the linear or multi-bisect search method, which are the only search methods which
+    /// run checks concurrently. Each concurrent check installs its own toolchain. More than one job
+    /// implies `--isolated`, so each check builds its own copy of the crate, in a temporary
+    /// directory, and concurrent checks share neither the lockfile, nor the build directory.
+    /// The MSRV found is the same as with a single job.
+    #[arg(
+        long,
+        short = 'j',
+        value_name = "N",
+        default_value = "1",
+        env = "CARGO_MSRV_JOBS"
+    )]
+    pub jobs: NonZeroUsize,
+
+    /// Check a sample of the Rust releases more recent than the MSRV, after the search
+    ///
+    /// The search methods assume that once a Rust version is compatible, each more recent Rust
+    /// version is compatible as well. This doesn't hold when, for example, a toolchain has a
+    /// regression. Incompatible Rust releases more recent than the MSRV are reported as a warning.
+    #[arg(long, env = "CARGO_MSRV_VERIFY_MONOTONICITY")]
+    pub verify_monotonicity: bool,
+
+    /// The amount of Rust releases checked by `--verify-monotonicity`
+    #[arg(
+        long,
+        value_name = "N",
+        default_value = "4",
+        requires = "verify_monotonicity",
+        env = "CARGO_MSRV_MONOTONICITY_SAMPLES"
+    )]
+    pub monotonicity_samples: NonZeroUsize,
+
     /// Pin the MSRV by writing the version to a rust-toolchain file
     ///
     /// The [toolchain](https://rust-lang.github.io/rustup/overrides.html#the-toolchain-file) file will pin the Rust version for this crate.
-    #[arg(long, alias = "toolchain-file")]
+    #[arg(
+        long,
+        alias = "toolchain-file",
+        env = "CARGO_MSRV_WRITE_TOOLCHAIN_FILE"
+    )]
     pub write_toolchain_file: bool,
 
     /// Temporarily remove the lockfile, so it will not interfere with the building process
     ///
     /// This is important when testing against older Rust versions such as Cargo versions prior to
     /// Rust 1.38.0, for which Cargo does not recognize the newer lockfile formats.
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_IGNORE_LOCKFILE")]
     pub ignore_lockfile: bool,
 
+    /// Check each toolchain against a lockfile which was resolved for that toolchain
+    ///
+    /// For each checked toolchain, a lockfile is generated in a scratch copy of the workspace,
+    /// preferring dependency versions whose `rust-version` is compatible with the toolchain.
+    /// The lockfile of the crate itself is never touched.
+    #[arg(
+        long,
+        conflicts_with = "ignore_lockfile",
+        env = "CARGO_MSRV_RESOLVE_MSRV_LOCKFILE"
+    )]
+    pub resolve_msrv_lockfile: bool,
+
+    /// Run each check in a copy of the crate outside of the working tree
+    ///
+    /// A snapshot of the workspace is taken once per run, and copied to a temporary directory for
+    /// each check, which is built with a target directory for each toolchain. Neither the
+    /// lockfile, nor the target directory of the crate are touched, so an interrupted run can't
+    /// leave the working tree in a broken state, and the crate can be edited while the checks run.
+    #[arg(long, env = "CARGO_MSRV_ISOLATED")]
+    pub isolated: bool,
+
+    /// What happens to the target directory of each checked toolchain, once the search is done
+    ///
+    /// Each toolchain builds in its own target directory, at `<target-dir>/cargo-msrv/<toolchain>`,
+    /// so alternating between toolchains doesn't invalidate the build artifacts of another.
+    #[arg(
+        long,
+        value_parser = target_dir_cleanup::VALUES.parser(),
+        default_value = target_dir_cleanup::VALUES.default_value(),
+        value_name = "POLICY",
+        env = "CARGO_MSRV_TARGET_DIR_CLEANUP"
+    )]
+    pub target_dir_cleanup: TargetDirCleanup,
+
+    /// Uninstall the toolchains which were installed during the search, once it is done
+    ///
+    /// Toolchains which were already installed before the search are kept. Toolchains installed
+    /// by earlier runs can be uninstalled with `cargo msrv toolchains prune`.
+    #[arg(long, env = "CARGO_MSRV_CLEANUP_TOOLCHAINS")]
+    pub cleanup_toolchains: bool,
+
+    /// Use, and store, cached outcomes of compatibility checks
+    ///
+    /// The outcome of each compatibility check is cached, keyed by the toolchain, the check
+    /// command and a fingerprint of the sources, the Cargo configuration and the environment.
+    /// Inputs which aren't part of the fingerprint, like the files read by a build script, can
+    /// make a cached outcome stale, so the cache is only used when opted in to.
+    #[arg(long, env = "CARGO_MSRV_CACHE")]
+    pub cache: bool,
+
+    /// Don't use, nor store, cached outcomes of compatibility checks
+    ///
+    /// Takes precedence over `--cache`, e.g. when the cache is enabled by the environment.
+    #[arg(long, env = "CARGO_MSRV_NO_CACHE")]
+    pub no_cache: bool,
+
     /// Treats a Rust version as incompatible when a toolchain failed to install or was otherwise unavailable
     ///
     /// Can be useful for reducing the search space on platforms with limited toolchain availability.
     /// Be warned that network errors on either end can also mark versions incorrectly as incompatible.
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_SKIP_UNAVAILABLE_TOOLCHAINS")]
     pub skip_unavailable_toolchains: bool,
 
     /// Don't print the result of compatibility checks
@@ -164,7 +543,7 @@ pub struct FindOpts {
     /// version is not compatible. Rust usually prints very detailed error messages.
     /// While most often very useful, in some cases they may be too noisy or lengthy.
     /// If this flag is given, the result messages will not be printed.
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_NO_CHECK_FEEDBACK")]
     pub no_check_feedback: bool,
 
     /// Write the MSRV to the Cargo manifest
@@ -172,9 +551,63 @@ pub struct FindOpts {
     /// For toolchains which include a Cargo version which supports the rust-version field,
     /// the `package.rust-version` field will be written. For older Rust toolchains,
     /// the `package.metadata.msrv` field will be written instead.
-    #[arg(long, visible_alias = "set")]
+    #[arg(long, visible_alias = "set", env = "CARGO_MSRV_WRITE_MSRV")]
     pub write_msrv: bool,
 
+    /// Find the MSRV of each combination of the features of the package
+    ///
+    /// Each feature set is checked with `--no-default-features --features <set>`. The MSRV of
+    /// each feature set is reported, together with the smallest feature set which raises the MSRV
+    /// above the MSRV without any features.
+    #[arg(
+        long,
+        group = "feature_sets",
+        conflicts_with_all = ["features", "all_features", "no_default_features", "custom_check_opts", "workspace", "all", "exclude"],
+        env = "CARGO_MSRV_FEATURE_POWERSET"
+    )]
+    pub feature_powerset: bool,
+
+    /// Find the MSRV of each feature of the package separately
+    ///
+    /// Like `--feature-powerset --depth 1`: each feature set consists of a single feature, in
+    /// addition to the feature set without any features.
+    #[arg(
+        long,
+        group = "feature_sets",
+        conflicts_with_all = ["features", "all_features", "no_default_features", "custom_check_opts", "workspace", "all", "exclude"],
+        env = "CARGO_MSRV_EACH_FEATURE"
+    )]
+    pub each_feature: bool,
+
+    /// The maximum amount of features combined into a feature set by `--feature-powerset`
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "feature_powerset",
+        conflicts_with = "each_feature",
+        env = "CARGO_MSRV_DEPTH"
+    )]
+    pub depth: Option<NonZeroUsize>,
+
+    /// Features which are left out of each feature set of `--feature-powerset` or `--each-feature`
+    #[arg(
+        long,
+        value_name = "FEATURES",
+        value_delimiter = ',',
+        requires = "feature_sets",
+        env = "CARGO_MSRV_EXCLUDE_FEATURES"
+    )]
+    pub exclude_features: Vec<String>,
+
+    /// Print the configuration, merged from the configuration files and the command line, instead of finding the MSRV
+    ///
+    /// Configuration is read from the `[workspace.metadata.cargo-msrv]` and
+    /// `[package.metadata.cargo-msrv]` tables of the Cargo manifests, and from a `cargo-msrv.toml`
+    /// file in the crate root, in order of increasing precedence. Options given on the command
+    /// line take precedence over each of them.
+    #[arg(long, env = "CARGO_MSRV_PRINT_CONFIG")]
+    pub print_config: bool,
+
     #[command(flatten)]
     pub rust_releases_opts: RustReleasesOpts,
 
@@ -185,6 +618,109 @@ pub struct FindOpts {
     pub custom_check_opts: CustomCheckOpts,
 }
 
+impl FindOpts {
+    // Apply the search method and the amount of jobs given by environment variables, unless they
+    // were given on the command line, given the matches and the command of `cargo msrv find`.
+    fn apply_layered_env(
+        &mut self,
+        command: &clap::Command,
+        matches: &ArgMatches,
+        var: &impl Fn(&OsStr) -> Option<OsString>,
+    ) -> Result<(), clap::Error> {
+        let on_command_line = |id: &str| {
+            matches.ids().any(|present| present == id)
+                && matches.value_source(id) == Some(ValueSource::CommandLine)
+        };
+
+        let search_method_given = ["bisect", "linear", "multi_bisect", "search_method"]
+            .into_iter()
+            .any(on_command_line);
+
+        if !search_method_given {
+            let mut search_methods = Vec::new();
+
+            if let Some(search_method) = env_value::<SearchMethod>(command, "search_method", var)? {
+                search_methods.push(search_method);
+            }
+
+            for (id, search_method) in [
+                ("bisect", SearchMethod::Bisect),
+                ("linear", SearchMethod::Linear),
+                ("multi_bisect", SearchMethod::MultiBisect),
+            ] {
+                if let Some((name, true)) = env_value::<bool>(command, id, var)? {
+                    search_methods.push((name, search_method));
+                }
+            }
+
+            match search_methods.as_slice() {
+                [] => {}
+                [(_, search_method)] => self.search_method = Some(*search_method),
+                [(first, _), (second, _), ..] => {
+                    return Err(clap::Error::raw(
+                        ErrorKind::ArgumentConflict,
+                        format!(
+                            "the environment variables '{}' and '{}' can't be used together\n",
+                            first.to_string_lossy(),
+                            second.to_string_lossy(),
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if !on_command_line("jobs")
+            && let Some((_, jobs)) = env_value::<NonZeroUsize>(command, "jobs", var)?
+        {
+            self.jobs = jobs;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct CacheOpts {
+    #[command(subcommand)]
+    pub action: CacheCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// Remove all cached outcomes of compatibility checks
+    Clear,
+}
+
+#[derive(Debug, Args)]
+pub struct ToolchainsOpts {
+    #[command(subcommand)]
+    pub action: ToolchainsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ToolchainsCommand {
+    /// Uninstall the toolchains which were installed by cargo-msrv, during any earlier run
+    Prune,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Doctor options")]
+pub struct DoctorOpts {
+    /// Source of the index of Rust releases, of which the availability is checked
+    #[arg(
+        long,
+        value_parser = release_source::parser(),
+        default_value = release_source::VALUES.default_value(),
+        value_name = "SOURCE",
+        env = "CARGO_MSRV_RELEASE_SOURCE"
+    )]
+    pub release_source: ReleaseSource,
+
+    /// Only check the locally cached copy of the index of Rust releases, instead of fetching it
+    #[arg(long, env = "CARGO_MSRV_OFFLINE")]
+    pub offline: bool,
+}
+
 #[derive(Debug, Args)]
 #[command(next_help_heading = "List options")]
 pub struct ListOpts {
@@ -195,6 +731,18 @@ pub struct ListOpts {
         default_value = list_msrv_variant::VALUES.default_value()
     )]
     pub variant: ListMsrvVariant,
+
+    /// Suggest dependency downgrades which would lower the MSRV of the dependency tree
+    ///
+    /// For each dependency with a `rust-version` greater than the `--target-msrv`, the newest
+    /// version with a `rust-version` of at most the target MSRV is looked up in the local cargo
+    /// registry index cache; no network access is needed.
+    #[arg(long, requires = "target_msrv")]
+    pub suggest_downgrades: bool,
+
+    /// The MSRV which the suggested dependency downgrades should bring the dependency tree within
+    #[arg(long, value_name = "MSRV", requires = "suggest_downgrades")]
+    pub target_msrv: Option<BareVersion>,
 }
 
 #[derive(Debug, Args)]
@@ -217,25 +765,63 @@ pub struct SetOpts {
 #[command(next_help_heading = "Verify options")]
 pub struct VerifyOpts {
     /// Ignore the lockfile for the MSRV search
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_IGNORE_LOCKFILE")]
     pub ignore_lockfile: bool,
 
+    /// Check against a lockfile which was resolved for the toolchain, instead of the lockfile of the crate
+    #[arg(
+        long,
+        conflicts_with = "ignore_lockfile",
+        env = "CARGO_MSRV_RESOLVE_MSRV_LOCKFILE"
+    )]
+    pub resolve_msrv_lockfile: bool,
+
+    /// Run the check in a copy of the crate outside of the working tree
+    #[arg(long, env = "CARGO_MSRV_ISOLATED")]
+    pub isolated: bool,
+
+    /// What happens to the target directory of the checked toolchain, once the check is done
+    #[arg(
+        long,
+        value_parser = target_dir_cleanup::VALUES.parser(),
+        default_value = target_dir_cleanup::VALUES.default_value(),
+        value_name = "POLICY",
+        env = "CARGO_MSRV_TARGET_DIR_CLEANUP"
+    )]
+    pub target_dir_cleanup: TargetDirCleanup,
+
+    /// Uninstall the toolchain if it was installed for the check, once the check is done
+    #[arg(long, env = "CARGO_MSRV_CLEANUP_TOOLCHAINS")]
+    pub cleanup_toolchains: bool,
+
+    /// Use, and store, cached outcomes of compatibility checks
+    #[arg(long, env = "CARGO_MSRV_CACHE")]
+    pub cache: bool,
+
+    /// Don't use, nor store, cached outcomes of compatibility checks; takes precedence over `--cache`
+    #[arg(long, env = "CARGO_MSRV_NO_CACHE")]
+    pub no_cache: bool,
+
     /// Don't print the result of compatibility checks
     ///
     /// The feedback of a compatibility check can be useful to determine why a certain Rust
     /// version is not compatible. Rust usually prints very detailed error messages.
     /// While most often very useful, in some cases they may be too noisy or lengthy.
     /// If this flag is given, the result messages will not be printed.
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_NO_CHECK_FEEDBACK")]
     pub no_check_feedback: bool,
 
+    /// Print the configuration, merged from the configuration files and the command line, instead of verifying the MSRV
+    #[arg(long, env = "CARGO_MSRV_PRINT_CONFIG")]
+    pub print_config: bool,
+
     #[command(flatten)]
     pub rust_releases_opts: RustReleasesOpts,
 
     /// The Rust version, to check against for toolchain compatibility
     ///
     /// If not set, the MSRV will be parsed from the Cargo manifest instead.
-    #[arg(long, value_name = "rust-version")]
+    #[arg(long, value_name = "rust-version", env = "CARGO_MSRV_RUST_VERSION")]
     pub rust_version: Option<BareVersion>,
 
     #[command(flatten)]
@@ -316,6 +902,193 @@ mod tests {
                 });
             }
 
+            #[test]
+            fn has_jobs() {
+                let cargo =
+                    CargoCli::parse_args(["cargo", "msrv", "find", "--linear", "--jobs", "4"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.jobs.get(), 4);
+                });
+            }
+
+            #[test]
+            fn has_multi_bisect() {
+                let cargo = CargoCli::parse_args([
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--multi-bisect",
+                    "--jobs",
+                    "4",
+                ]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.multi_bisect);
+                    assert_eq!(find_opts.jobs.get(), 4);
+                });
+            }
+
+            #[yare::parameterized(
+                linear = { "linear", SearchMethod::Linear },
+                bisect = { "bisect", SearchMethod::Bisect },
+                multi_bisect = { "multi-bisect", SearchMethod::MultiBisect },
+                anchored = { "anchored", SearchMethod::Anchored },
+            )]
+            fn has_search_method(value: &str, expected: SearchMethod) {
+                let cargo =
+                    CargoCli::parse_args(["cargo", "msrv", "find", "--search-method", value]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.search_method, Some(expected));
+                });
+            }
+
+            #[test]
+            fn has_search_method_with_jobs() {
+                let cargo = CargoCli::parse_args([
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--search-method",
+                    "multi-bisect",
+                    "--jobs",
+                    "4",
+                ]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.search_method, Some(SearchMethod::MultiBisect));
+                    assert_eq!(find_opts.jobs.get(), 4);
+                });
+            }
+
+            #[yare::parameterized(
+                unknown = { &["cargo", "msrv", "find", "--search-method", "galloping"] },
+                with_bisect = { &["cargo", "msrv", "find", "--search-method", "anchored", "--bisect"] },
+                with_linear = { &["cargo", "msrv", "find", "--search-method", "anchored", "--linear"] },
+            )]
+            fn rejects_search_method(args: &[&str]) {
+                assert!(CargoCli::try_parse_from(args).is_err());
+            }
+
+            #[test]
+            fn has_verify_monotonicity() {
+                let cargo = CargoCli::parse_args([
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--verify-monotonicity",
+                    "--monotonicity-samples",
+                    "8",
+                ]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.verify_monotonicity);
+                    assert_eq!(find_opts.monotonicity_samples.get(), 8);
+                });
+            }
+
+            #[test]
+            fn has_not_verify_monotonicity() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(!find_opts.verify_monotonicity);
+                    assert_eq!(find_opts.monotonicity_samples.get(), 4);
+                });
+            }
+
+            #[yare::parameterized(
+                samples_without_verify = { &["cargo", "msrv", "find", "--monotonicity-samples", "2"] },
+                zero_samples = { &["cargo", "msrv", "find", "--verify-monotonicity", "--monotonicity-samples", "0"] },
+            )]
+            fn rejects_monotonicity_samples(args: &[&str]) {
+                assert!(CargoCli::try_parse_from(args).is_err());
+            }
+
+            #[test]
+            fn has_feature_powerset() {
+                let cargo = CargoCli::parse_args([
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--feature-powerset",
+                    "--depth",
+                    "2",
+                    "--exclude-features",
+                    "nightly,unstable",
+                ]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.feature_powerset);
+                    assert!(!find_opts.each_feature);
+                    assert_eq!(find_opts.depth, NonZeroUsize::new(2));
+                    assert_eq!(find_opts.exclude_features, vec!["nightly", "unstable"]);
+                });
+            }
+
+            #[test]
+            fn has_each_feature() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--each-feature"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.each_feature);
+                    assert!(!find_opts.feature_powerset);
+                    assert!(find_opts.depth.is_none());
+                    assert!(find_opts.exclude_features.is_empty());
+                });
+            }
+
+            #[yare::parameterized(
+                powerset_and_each_feature = { &["cargo", "msrv", "find", "--feature-powerset", "--each-feature"] },
+                depth_without_powerset = { &["cargo", "msrv", "find", "--depth", "2"] },
+                depth_with_each_feature = { &["cargo", "msrv", "find", "--each-feature", "--depth", "2"] },
+                exclude_without_feature_sets = { &["cargo", "msrv", "find", "--exclude-features", "a"] },
+                with_features = { &["cargo", "msrv", "find", "--feature-powerset", "--features", "a"] },
+                with_all_features = { &["cargo", "msrv", "find", "--each-feature", "--all-features"] },
+                with_custom_check = { &["cargo", "msrv", "find", "--feature-powerset", "--", "cargo", "test"] },
+                with_workspace = { &["cargo", "msrv", "find", "--feature-powerset", "--workspace"] },
+                with_exclude = { &["cargo", "msrv", "find", "--each-feature", "--exclude", "a"] },
+            )]
+            fn rejects_feature_sets(args: &[&str]) {
+                assert!(CargoCli::try_parse_from(args).is_err());
+            }
+
+            #[test]
+            fn has_single_job_by_default() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.jobs.get(), 1);
+                });
+            }
+
+            #[yare::parameterized(
+                multi_bisect_and_linear = { &["cargo", "msrv", "find", "--multi-bisect", "--linear"] },
+                zero = { &["cargo", "msrv", "find", "--linear", "--jobs", "0"] },
+            )]
+            fn rejects_jobs(args: &[&str]) {
+                assert!(CargoCli::try_parse_from(args).is_err());
+            }
+
             #[test]
             fn has_write_toolchain_file() {
                 let cargo =
@@ -361,6 +1134,154 @@ mod tests {
                 });
             }
 
+            #[test]
+            fn has_resolve_msrv_lockfile() {
+                let cargo =
+                    CargoCli::parse_args(["cargo", "msrv", "find", "--resolve-msrv-lockfile"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.resolve_msrv_lockfile);
+                });
+            }
+
+            #[test]
+            fn has_isolated() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--isolated"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.isolated);
+                });
+            }
+
+            #[yare::parameterized(
+                keep = { &["cargo", "msrv", "find", "--target-dir-cleanup", "keep"], TargetDirCleanup::Keep },
+                delete = { &["cargo", "msrv", "find", "--target-dir-cleanup", "delete"], TargetDirCleanup::Delete },
+                default = { &["cargo", "msrv", "find"], TargetDirCleanup::Keep },
+            )]
+            fn has_target_dir_cleanup(args: &[&str], expected: TargetDirCleanup) {
+                let cargo = CargoCli::parse_args(args);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.target_dir_cleanup, expected);
+                });
+            }
+
+            #[yare::parameterized(
+                cleanup = { &["cargo", "msrv", "find", "--cleanup-toolchains"], true },
+                default = { &["cargo", "msrv", "find"], false },
+            )]
+            fn has_cleanup_toolchains(args: &[&str], expected: bool) {
+                let cargo = CargoCli::parse_args(args);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.cleanup_toolchains, expected);
+                });
+            }
+
+            #[yare::parameterized(
+                dir = { &["cargo", "msrv", "find", "--toolchain-dir", "toolchains"], Some("toolchains") },
+                default = { &["cargo", "msrv", "find"], None },
+            )]
+            fn has_toolchain_dir(args: &[&str], expected: Option<&str>) {
+                let cargo = CargoCli::parse_args(args);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(
+                        find_opts.toolchain_opts.toolchain_dir,
+                        expected.map(std::path::PathBuf::from)
+                    );
+                });
+            }
+
+            #[test]
+            fn rejects_resolve_msrv_lockfile_with_ignore_lockfile() {
+                let args = [
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--resolve-msrv-lockfile",
+                    "--ignore-lockfile",
+                ];
+
+                assert!(CargoCli::try_parse_from(args).is_err());
+            }
+
+            #[test]
+            fn has_no_cache() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--no-cache"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.no_cache);
+                });
+            }
+
+            #[test]
+            fn has_cache() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--cache"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.cache);
+                });
+            }
+
+            #[test]
+            fn has_not_cache() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(!find_opts.cache);
+                });
+            }
+
+            #[test]
+            fn has_not_no_cache() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(!find_opts.no_cache);
+                });
+            }
+
+            #[test]
+            fn has_offline() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--offline"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.rust_releases_opts.offline);
+                });
+            }
+
+            #[test]
+            fn has_not_offline() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(!find_opts.rust_releases_opts.offline);
+                });
+            }
+
             #[test]
             fn has_no_check_feedback() {
                 let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--no-check-feedback"]);
@@ -405,11 +1326,290 @@ mod tests {
                 });
             }
 
+            #[test]
+            fn has_print_config() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--print-config"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert!(find_opts.print_config);
+                });
+            }
+
             // todo: rust-releases opts
 
             // todo: toolchain opts
 
             // todo: custom check opts
         }
+
+        mod cache_opts {
+            use super::*;
+
+            #[test]
+            fn clear() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "cache", "clear"]);
+                let opts = cargo.to_cargo_msrv_cli().to_opts();
+
+                assert!(matches!(
+                    opts.subcommand,
+                    SubCommand::Cache(CacheOpts {
+                        action: CacheCommand::Clear
+                    })
+                ));
+            }
+        }
+
+        mod toolchains_opts {
+            use super::*;
+
+            #[test]
+            fn prune() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "toolchains", "prune"]);
+                let opts = cargo.to_cargo_msrv_cli().to_opts();
+
+                assert!(matches!(
+                    opts.subcommand,
+                    SubCommand::Toolchains(ToolchainsOpts {
+                        action: ToolchainsCommand::Prune
+                    })
+                ));
+            }
+        }
+
+        mod doctor_opts {
+            use super::*;
+
+            #[test]
+            fn defaults() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "doctor"]);
+                let opts = cargo.to_cargo_msrv_cli().to_opts();
+
+                assert!(matches!(
+                    opts.subcommand,
+                    SubCommand::Doctor(DoctorOpts {
+                        release_source: ReleaseSource::RustChangelog,
+                        offline: false,
+                    })
+                ));
+            }
+
+            #[test]
+            fn offline() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "doctor", "--offline"]);
+                let opts = cargo.to_cargo_msrv_cli().to_opts();
+
+                assert!(matches!(
+                    opts.subcommand,
+                    SubCommand::Doctor(DoctorOpts { offline: true, .. })
+                ));
+            }
+        }
+
+        mod workspace_opts {
+            use super::*;
+            use cargo_msrv_context::PackageSelection;
+
+            #[test]
+            fn workspace_after_subcommand() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--workspace"]);
+                let opts = cargo.to_cargo_msrv_cli().to_opts();
+
+                assert!(opts.shared_opts.workspace.workspace);
+                assert_eq!(
+                    opts.shared_opts.workspace.selection(),
+                    PackageSelection::Workspace
+                );
+            }
+
+            #[test]
+            fn package_after_subcommand() {
+                let cargo =
+                    CargoCli::parse_args(["cargo", "msrv", "find", "-p", "a", "--package", "b"]);
+                let opts = cargo.to_cargo_msrv_cli().to_opts();
+
+                assert_eq!(opts.shared_opts.workspace.package, vec!["a", "b"]);
+                assert_eq!(
+                    opts.shared_opts.workspace.selection(),
+                    PackageSelection::Packages
+                );
+            }
+
+            #[test]
+            fn no_selection() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find"]);
+                let opts = cargo.to_cargo_msrv_cli().to_opts();
+
+                assert_eq!(
+                    opts.shared_opts.workspace.selection(),
+                    PackageSelection::Default
+                );
+            }
+        }
+    }
+
+    mod env {
+        use super::*;
+
+        fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&OsStr) -> Option<OsString> + 'a {
+            |name| {
+                vars.iter()
+                    .find(|(var, _)| OsStr::new(var) == name)
+                    .map(|(_, value)| OsString::from(value))
+            }
+        }
+
+        fn args(args: &[&str]) -> Vec<OsString> {
+            args.iter().map(OsString::from).collect()
+        }
+
+        #[yare::parameterized(
+            jobs = { "CARGO_MSRV_JOBS", "4" },
+            flag = { "CARGO_MSRV_LINEAR", "true" },
+            search_method = { "CARGO_MSRV_SEARCH_METHOD", "anchored" },
+            delimited = { "CARGO_MSRV_TARGET", "x86_64-unknown-linux-gnu,wasm32-unknown-unknown" },
+        )]
+        fn accepts_valid_value(var: &str, value: &str) {
+            let args = args(&["cargo", "msrv", "find"]);
+            assert!(validate_env(CargoCli::command(), &args, env(&[(var, value)])).is_ok());
+        }
+
+        #[yare::parameterized(
+            jobs = { "CARGO_MSRV_JOBS", "0" },
+            flag = { "CARGO_MSRV_LINEAR", "yes" },
+            search_method = { "CARGO_MSRV_SEARCH_METHOD", "fastest" },
+            min = { "CARGO_MSRV_MIN", "one" },
+        )]
+        fn invalid_value_names_variable(var: &str, value: &str) {
+            let args = args(&["cargo", "msrv", "find"]);
+            let err = validate_env(CargoCli::command(), &args, env(&[(var, value)])).unwrap_err();
+
+            assert_eq!(err.kind(), ErrorKind::InvalidValue);
+            assert!(err.to_string().contains(var));
+        }
+
+        #[yare::parameterized(
+            find = { &["cargo", "msrv", "find"], true },
+            verify = { &["cargo", "msrv", "verify"], false },
+            verify_with_global_option = { &["cargo", "msrv", "--workspace", "verify"], false },
+            show = { &["cargo", "msrv", "show"], true },
+        )]
+        fn validates_variables_of_invoked_subcommand(invoked: &[&str], ok: bool) {
+            // `--rust-version` is an option of `cargo msrv verify` only
+            let vars = env(&[("CARGO_MSRV_RUST_VERSION", "one")]);
+
+            let result = validate_env(CargoCli::command(), &args(invoked), vars);
+
+            assert_eq!(result.is_ok(), ok);
+        }
+
+        #[yare::parameterized(
+            package = { &["cargo", "msrv", "find", "--package", "a,b"] },
+            target = { &["cargo", "msrv", "find", "--target", "a,b"] },
+        )]
+        fn command_line_values_are_not_split(args: &[&str]) {
+            let opts = CargoCli::parse_args(args).to_cargo_msrv_cli().to_opts();
+            let SubCommand::Find(find_opts) = &opts.subcommand else {
+                panic!("expected find");
+            };
+
+            let values = [
+                opts.shared_opts.workspace.package.as_slice(),
+                find_opts.toolchain_opts.target.as_slice(),
+            ];
+            assert!(values.contains(&["a,b".to_string()].as_slice()));
+        }
+
+        fn find_opts(args: &[&str], vars: &[(&str, &str)]) -> Result<FindOpts, clap::Error> {
+            let cli = CargoCli::try_parse_args(args.iter().copied(), env(vars))?;
+
+            match cli.to_cargo_msrv_cli().to_opts().subcommand {
+                SubCommand::Find(opts) => Ok(opts),
+                _ => panic!("expected find"),
+            }
+        }
+
+        #[yare::parameterized(
+            search_method = { "CARGO_MSRV_SEARCH_METHOD", "linear", SearchMethod::Linear },
+            bisect = { "CARGO_MSRV_BISECT", "true", SearchMethod::Bisect },
+            linear = { "CARGO_MSRV_LINEAR", "true", SearchMethod::Linear },
+            multi_bisect = { "CARGO_MSRV_MULTI_BISECT", "true", SearchMethod::MultiBisect },
+        )]
+        fn search_method_of_variable(var: &str, value: &str, expected: SearchMethod) {
+            let opts = find_opts(&["cargo", "msrv", "find"], &[(var, value)]).unwrap();
+
+            assert_eq!(opts.search_method, Some(expected));
+        }
+
+        // The variable is ignored, so only the option determines the search method
+        #[yare::parameterized(
+            search_method_and_bisect = { ("CARGO_MSRV_SEARCH_METHOD", "linear"), "--bisect", None },
+            linear_and_bisect = { ("CARGO_MSRV_LINEAR", "true"), "--bisect", None },
+            bisect_and_search_method = { ("CARGO_MSRV_BISECT", "true"), "--search-method=anchored", Some(SearchMethod::Anchored) },
+            multi_bisect_and_linear = { ("CARGO_MSRV_MULTI_BISECT", "true"), "--linear", None },
+        )]
+        fn search_method_option_takes_precedence_over_variable(
+            var: (&str, &str),
+            option: &str,
+            search_method: Option<SearchMethod>,
+        ) {
+            let opts = find_opts(&["cargo", "msrv", "find", option], &[var]).unwrap();
+
+            assert_eq!(opts.search_method, search_method);
+            assert!(!opts.multi_bisect);
+        }
+
+        #[test]
+        fn rejects_conflicting_search_method_variables() {
+            let vars = [
+                ("CARGO_MSRV_SEARCH_METHOD", "anchored"),
+                ("CARGO_MSRV_LINEAR", "true"),
+            ];
+            let err = find_opts(&["cargo", "msrv", "find"], &vars).unwrap_err();
+
+            assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        }
+
+        #[yare::parameterized(
+            without_option = { &["cargo", "msrv", "find"], 4 },
+            with_search_method_option = { &["cargo", "msrv", "find", "--linear"], 4 },
+            with_jobs_option = { &["cargo", "msrv", "find", "--jobs", "2"], 2 },
+        )]
+        fn jobs_of_variable(args: &[&str], expected: usize) {
+            let opts = find_opts(args, &[("CARGO_MSRV_JOBS", "4")]).unwrap();
+
+            assert_eq!(opts.jobs.get(), expected);
+        }
+
+        #[yare::parameterized(
+            find = { "find" },
+            verify = { "verify" },
+        )]
+        fn each_option_has_variable(subcommand: &str) {
+            let mut command = CargoCli::command();
+            command.build();
+
+            let subcommand = command
+                .find_subcommand("msrv")
+                .and_then(|msrv| msrv.find_subcommand(subcommand))
+                .unwrap();
+
+            for arg in subcommand
+                .get_arguments()
+                .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
+            {
+                let Some(long) = arg.get_long() else {
+                    continue;
+                };
+
+                if long == "help" || long == "version" {
+                    continue;
+                }
+
+                let expected = format!("CARGO_MSRV_{}", long.to_uppercase().replace('-', "_"));
+                assert_eq!(arg.get_env(), Some(OsStr::new(&expected)), "--{}", long);
+            }
+        }
     }
 }
@@ -15,27 +15,49 @@ pub struct RustReleasesOpts {
     ///
     /// For example, the edition alias "2018" would match Rust version `1.31.0`, since that's the
     /// first version which added support for the Rust 2018 edition.
-    #[arg(long, value_name = "VERSION_SPEC or EDITION", alias = "minimum")]
+    #[arg(
+        long,
+        value_name = "VERSION_SPEC or EDITION",
+        alias = "minimum",
+        env = "CARGO_MSRV_MIN"
+    )]
     pub min: Option<EditionOrVersion>,
 
     /// Most recent version to take into account
     ///
     /// Given version must match a valid Rust toolchain, and be semver compatible, or
     /// be a two component `major.minor` version.
-    #[arg(long, value_name = "VERSION_SPEC", alias = "maximum")]
+    #[arg(
+        long,
+        value_name = "VERSION_SPEC",
+        alias = "maximum",
+        env = "CARGO_MSRV_MAX"
+    )]
     pub max: Option<BareVersion>,
 
     /// Include all patch releases, instead of only the last
-    #[arg(long)]
+    #[arg(long, env = "CARGO_MSRV_INCLUDE_ALL_PATCH_RELEASES")]
     pub include_all_patch_releases: bool,
 
+    /// Source of the index of Rust releases
+    ///
+    /// One of `rust-changelog`, `rust-dist`, `rustup-installed`, or `file:<path>`. A file lists
+    /// one Rust version per line, or is a channel manifest, if its extension is `.toml`.
     #[arg(
         long,
-        value_parser = release_source::VALUES.parser(),
+        value_parser = release_source::parser(),
         default_value = release_source::VALUES.default_value(),
-        value_name = "SOURCE"
+        value_name = "SOURCE",
+        env = "CARGO_MSRV_RELEASE_SOURCE"
     )]
     pub release_source: ReleaseSource,
+
+    /// Don't fetch the index of Rust releases, and only use the locally cached copy instead
+    ///
+    /// A fetched index is cached, and re-used for up to a day. Fails if no cached copy of the
+    /// index exists for the selected release source.
+    #[arg(long, env = "CARGO_MSRV_OFFLINE")]
+    pub offline: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -1,4 +1,5 @@
 use crate::values::{log_level, output_format, tracing_target_option};
+use cargo_msrv_context::PackageSelection;
 use cargo_msrv_context::types::{LogLevel, OutputFormat, TracingTargetOption};
 use clap::{ArgGroup, Args, ValueHint};
 use std::path::PathBuf;
@@ -11,15 +12,27 @@ pub struct SharedOpts {
     ///
     /// This should be used over `--manifest-path` if not in a Cargo project.
     /// If you have a Cargo project, prefer `--manifest-path`.
-    #[arg(long, value_name = "Crate Directory", global = true, value_hint = ValueHint::DirPath)]
+    #[arg(
+        long,
+        value_name = "Crate Directory",
+        global = true,
+        value_hint = ValueHint::DirPath,
+        env = "CARGO_MSRV_PATH"
+    )]
     pub path: Option<PathBuf>,
 
     /// Path to cargo manifest file
-    #[arg(long, value_name = "Cargo Manifest", global = true, value_hint = ValueHint::FilePath)]
+    #[arg(
+        long,
+        value_name = "Cargo Manifest",
+        global = true,
+        value_hint = ValueHint::FilePath,
+        env = "CARGO_MSRV_MANIFEST_PATH"
+    )]
     pub manifest_path: Option<PathBuf>,
 
     #[command(flatten)]
-    pub workspace: clap_cargo::Workspace,
+    pub workspace: WorkspaceOpts,
 
     #[command(flatten)]
     pub user_output_opts: UserOutputOpts,
@@ -28,6 +41,56 @@ pub struct SharedOpts {
     pub debug_output_opts: DebugOutputOpts,
 }
 
+// Mirrors `clap_cargo::Workspace`, but its flags are global, so they can also be given after the subcommand,
+// like `cargo msrv find --workspace`.
+#[derive(Debug, Default, Args)]
+#[command(next_help_heading = "Package selection")]
+pub struct WorkspaceOpts {
+    /// Package to process (see `cargo help pkgid`)
+    #[arg(
+        short,
+        long,
+        value_name = "SPEC",
+        global = true,
+        env = "CARGO_MSRV_PACKAGE"
+    )]
+    pub package: Vec<String>,
+
+    /// Process all packages in the workspace
+    #[arg(long, global = true, env = "CARGO_MSRV_WORKSPACE")]
+    pub workspace: bool,
+
+    /// Process all packages in the workspace
+    #[arg(long, hide = true, global = true)]
+    pub all: bool,
+
+    /// Exclude packages from being processed
+    #[arg(long, value_name = "SPEC", global = true, env = "CARGO_MSRV_EXCLUDE")]
+    pub exclude: Vec<String>,
+}
+
+impl WorkspaceOpts {
+    pub fn to_workspace(&self) -> clap_cargo::Workspace {
+        let mut workspace = clap_cargo::Workspace::default();
+        workspace.package = self.package.clone();
+        workspace.workspace = self.workspace;
+        workspace.all = self.all;
+        workspace.exclude = self.exclude.clone();
+        workspace
+    }
+
+    /// How the packages were selected by the user.
+    pub fn selection(&self) -> PackageSelection {
+        if !self.package.is_empty() {
+            PackageSelection::Packages
+        } else if self.workspace || self.all || !self.exclude.is_empty() {
+            PackageSelection::Workspace
+        } else {
+            PackageSelection::Default
+        }
+    }
+}
+
 #[derive(Debug, Args)]
 #[command(next_help_heading = "User output options")]
 pub struct UserOutputOpts {
@@ -37,12 +100,18 @@ pub struct UserOutputOpts {
         value_parser = output_format::VALUES.parser(),
         default_value = output_format::VALUES.default_value(),
         value_name = "FORMAT",
-        global = true
+        global = true,
+        env = "CARGO_MSRV_OUTPUT_FORMAT"
     )]
     output_format: OutputFormat,
 
     /// Disable user output
-    #[arg(long, global = true, conflicts_with = "output_format")]
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "output_format",
+        env = "CARGO_MSRV_NO_USER_OUTPUT"
+    )]
     no_user_output: bool,
 }
 
@@ -60,7 +129,7 @@ impl UserOutputOpts {
 #[command(next_help_heading = "Debug output options")]
 pub struct DebugOutputOpts {
     /// Disable logging
-    #[arg(long, global = true)]
+    #[arg(long, global = true, env = "CARGO_MSRV_NO_LOG")]
     pub no_log: bool,
 
     /// Specify where the program should output its logs
@@ -69,7 +138,8 @@ pub struct DebugOutputOpts {
         value_parser = tracing_target_option::VALUES.parser(),
         default_value = tracing_target_option::VALUES.default_value(),
         value_name = "LOG TARGET",
-        global = true
+        global = true,
+        env = "CARGO_MSRV_LOG_TARGET"
     )]
     pub log_target: TracingTargetOption,
 
@@ -79,7 +149,8 @@ pub struct DebugOutputOpts {
         value_parser = log_level::VALUES.parser(),
         default_value = log_level::VALUES.default_value(),
         value_name = "LEVEL",
-        global = true
+        global = true,
+        env = "CARGO_MSRV_LOG_LEVEL"
     )]
     pub log_level: LogLevel,
 }
@@ -1,4 +1,5 @@
-use clap::Args;
+use clap::{Args, ValueHint};
+use std::path::PathBuf;
 
 // Cli Options for commands which invoke Rust toolchains, such as the top level cargo msrv command
 // (find) or cargo msrv verify
@@ -6,15 +7,36 @@ use clap::Args;
 #[command(next_help_heading = "Toolchain options")]
 pub struct ToolchainOpts {
     /// Check against a custom target (instead of the rustup default)
+    ///
+    /// Can be supplied multiple times, to find the MSRV of each target. Multiple targets are only
+    /// supported by `cargo msrv find`.
     // Unfortunately, Clap will not reject the
-    #[arg(long, value_name = "TARGET", global = true)]
-    pub target: Option<String>,
+    #[arg(long, value_name = "TARGET", global = true, env = "CARGO_MSRV_TARGET")]
+    pub target: Vec<String>,
 
     /// Components be added to the toolchain
     ///
     /// Can be supplied multiple times to add multiple components.
     ///
     /// For example: --component rustc --component cargo
-    #[arg(long, value_name = "COMPONENT", global = true)]
+    #[arg(
+        long,
+        value_name = "COMPONENT",
+        global = true,
+        env = "CARGO_MSRV_COMPONENT"
+    )]
     pub component: Vec<String>,
+
+    /// Use the toolchains unpacked in the given directory, instead of installing them with rustup
+    ///
+    /// Each toolchain is located at `<DIR>/<version>`, e.g. `<DIR>/1.70.0/bin/cargo`, and
+    /// `<DIR>/1.70.0/bin/rustc`. Toolchains are never installed into this directory.
+    #[arg(
+        long,
+        value_name = "DIR",
+        global = true,
+        value_hint = ValueHint::DirPath,
+        env = "CARGO_MSRV_TOOLCHAIN_DIR"
+    )]
+    pub toolchain_dir: Option<PathBuf>,
 }
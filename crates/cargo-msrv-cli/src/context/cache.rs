@@ -0,0 +1,30 @@
+use crate::cli::{CacheCommand, CargoMsrvOpts, SubCommand};
+use cargo_msrv_context::CacheContext;
+use cargo_msrv_context::context::cache::CacheAction;
+use cargo_msrv_context::context::error::{Error, TResult};
+use std::convert::{TryFrom, TryInto};
+
+impl TryFrom<CargoMsrvOpts> for CacheContext {
+    type Error = Error;
+
+    fn try_from(opts: CargoMsrvOpts) -> TResult<Self> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let cache_opts = match subcommand {
+            SubCommand::Cache(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `cache`!"),
+        };
+
+        let action = match cache_opts.action {
+            CacheCommand::Clear => CacheAction::Clear,
+        };
+
+        Ok(Self {
+            action,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
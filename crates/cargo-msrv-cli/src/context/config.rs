@@ -0,0 +1,321 @@
+//! Merge the configuration of the project with the options given on the command line.
+
+use crate::cli::custom_check_opts::CustomCheckOpts;
+use crate::cli::rust_releases_opts::{EditionOrVersion, RustReleasesOpts};
+use crate::cli::toolchain_opts::ToolchainOpts;
+use crate::cli::{FindOpts, VerifyOpts};
+use cargo_msrv_context::EnvironmentContext;
+use cargo_msrv_context::SearchMethod;
+use cargo_msrv_context::config::{Config, ConfigLayer, ConfigSource};
+use cargo_msrv_context::context::error::{ConfigError, Error, TResult};
+
+/// Options of a subcommand, which can also be configured by the project.
+pub(crate) trait ConfigurableOpts {
+    /// The options which were given on the command line, as a layer of configuration.
+    fn command_line_config(&self) -> Config;
+
+    /// Replace the options by the configured options, which include those given on the
+    /// command line.
+    fn apply_config(&mut self, config: Config) -> TResult<()>;
+}
+
+/// The configuration of the project, merged with the options given on the command line, and
+/// each layer it was merged from, from lowest to highest precedence.
+pub(crate) fn resolve_config(
+    environment: &EnvironmentContext,
+    opts: &impl ConfigurableOpts,
+) -> TResult<(Config, Vec<ConfigLayer>)> {
+    let mut layers = ConfigLayer::load(environment.root())?;
+
+    let command_line = opts.command_line_config();
+    if command_line != Config::default() {
+        layers.push(ConfigLayer::new(ConfigSource::CommandLine, command_line));
+    }
+
+    Ok((Config::merge(&layers), layers))
+}
+
+/// Apply the configuration of the project to the options given on the command line.
+pub(crate) fn configure(
+    environment: &EnvironmentContext,
+    opts: &mut impl ConfigurableOpts,
+) -> TResult<()> {
+    let (config, _) = resolve_config(environment, opts)?;
+
+    opts.apply_config(config)
+}
+
+impl ConfigurableOpts for FindOpts {
+    fn command_line_config(&self) -> Config {
+        let search_method = self.search_method.or(if self.linear {
+            Some(SearchMethod::Linear)
+        } else if self.multi_bisect {
+            Some(SearchMethod::MultiBisect)
+        } else if self.bisect {
+            Some(SearchMethod::Bisect)
+        } else {
+            None
+        });
+
+        Config {
+            search_method,
+            ignore_lockfile: self.ignore_lockfile.then_some(true),
+            ..shared_command_line_config(
+                &self.rust_releases_opts,
+                &self.toolchain_opts,
+                &self.custom_check_opts,
+            )
+        }
+    }
+
+    fn apply_config(&mut self, config: Config) -> TResult<()> {
+        let ignore_lockfile = config.ignore_lockfile.unwrap_or_default();
+
+        if ignore_lockfile && self.resolve_msrv_lockfile {
+            return Err(conflict("ignore-lockfile", "--resolve-msrv-lockfile"));
+        }
+
+        if self.feature_powerset || self.each_feature {
+            let conflicting_key = [
+                ("features", config.features.is_some()),
+                ("all-features", config.all_features == Some(true)),
+                (
+                    "no-default-features",
+                    config.no_default_features == Some(true),
+                ),
+                ("check-command", config.check_command.is_some()),
+            ]
+            .into_iter()
+            .find_map(|(key, is_set)| is_set.then_some(key));
+
+            if let Some(key) = conflicting_key {
+                return Err(conflict(key, "--feature-powerset` or `--each-feature"));
+            }
+        }
+
+        if config.search_method.is_some() {
+            self.search_method = config.search_method;
+        }
+        self.ignore_lockfile = ignore_lockfile;
+
+        apply_shared_config(
+            config,
+            &mut self.rust_releases_opts,
+            &mut self.toolchain_opts,
+            &mut self.custom_check_opts,
+        );
+
+        Ok(())
+    }
+}
+
+impl ConfigurableOpts for VerifyOpts {
+    fn command_line_config(&self) -> Config {
+        Config {
+            ignore_lockfile: self.ignore_lockfile.then_some(true),
+            ..shared_command_line_config(
+                &self.rust_releases_opts,
+                &self.toolchain_opts,
+                &self.custom_check_opts,
+            )
+        }
+    }
+
+    fn apply_config(&mut self, config: Config) -> TResult<()> {
+        let ignore_lockfile = config.ignore_lockfile.unwrap_or_default();
+
+        if ignore_lockfile && self.resolve_msrv_lockfile {
+            return Err(conflict("ignore-lockfile", "--resolve-msrv-lockfile"));
+        }
+
+        self.ignore_lockfile = ignore_lockfile;
+
+        apply_shared_config(
+            config,
+            &mut self.rust_releases_opts,
+            &mut self.toolchain_opts,
+            &mut self.custom_check_opts,
+        );
+
+        Ok(())
+    }
+}
+
+fn shared_command_line_config(
+    rust_releases_opts: &RustReleasesOpts,
+    toolchain_opts: &ToolchainOpts,
+    custom_check_opts: &CustomCheckOpts,
+) -> Config {
+    Config {
+        min: rust_releases_opts
+            .min
+            .as_ref()
+            .map(EditionOrVersion::as_bare_version),
+        max: rust_releases_opts.max.clone(),
+        features: custom_check_opts.features.clone(),
+        all_features: custom_check_opts.all_features.then_some(true),
+        no_default_features: custom_check_opts.no_default_features.then_some(true),
+        components: (!toolchain_opts.component.is_empty())
+            .then(|| toolchain_opts.component.clone()),
+        target: (!toolchain_opts.target.is_empty()).then(|| toolchain_opts.target.clone()),
+        check_command: custom_check_opts.custom_check_opts.clone(),
+        ..Config::default()
+    }
+}
+
+fn apply_shared_config(
+    config: Config,
+    rust_releases_opts: &mut RustReleasesOpts,
+    toolchain_opts: &mut ToolchainOpts,
+    custom_check_opts: &mut CustomCheckOpts,
+) {
+    // Keep an edition given on the command line as is
+    if rust_releases_opts.min.is_none() {
+        rust_releases_opts.min = config.min.map(EditionOrVersion::Version);
+    }
+    rust_releases_opts.max = config.max;
+
+    toolchain_opts.target = config.target.unwrap_or_default();
+    toolchain_opts.component = config.components.unwrap_or_default();
+
+    custom_check_opts.features = config.features;
+    custom_check_opts.all_features = config.all_features.unwrap_or_default();
+    custom_check_opts.no_default_features = config.no_default_features.unwrap_or_default();
+    custom_check_opts.custom_check_opts = config.check_command;
+}
+
+fn conflict(key: &'static str, flag: &'static str) -> Error {
+    Error::Config(ConfigError::ConflictsWith { key, flag })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{CargoCli, SubCommand};
+    use cargo_msrv_types::BareVersion;
+
+    fn find_opts(args: &[&str]) -> FindOpts {
+        match CargoCli::parse_args(args)
+            .to_cargo_msrv_cli()
+            .to_opts()
+            .subcommand
+        {
+            SubCommand::Find(opts) => opts,
+            _ => unreachable!(),
+        }
+    }
+
+    fn configured(args: &[&str], config: Config) -> TResult<FindOpts> {
+        let mut opts = find_opts(args);
+        let layers = [
+            ConfigLayer::new(
+                ConfigSource::ConfigFile {
+                    path: "cargo-msrv.toml".into(),
+                },
+                config,
+            ),
+            ConfigLayer::new(ConfigSource::CommandLine, opts.command_line_config()),
+        ];
+
+        opts.apply_config(Config::merge(&layers))?;
+        Ok(opts)
+    }
+
+    #[test]
+    fn command_line_takes_precedence() {
+        let config = Config {
+            search_method: Some(SearchMethod::Anchored),
+            max: Some(BareVersion::TwoComponents(1, 80)),
+            target: Some(vec!["x".to_string()]),
+            ..Config::default()
+        };
+
+        let opts = configured(
+            &["cargo", "msrv", "find", "--linear", "--target", "y"],
+            config,
+        )
+        .unwrap();
+
+        assert_eq!(opts.search_method, Some(SearchMethod::Linear));
+        assert_eq!(
+            opts.rust_releases_opts.max,
+            Some(BareVersion::TwoComponents(1, 80))
+        );
+        assert_eq!(opts.toolchain_opts.target, vec!["y"]);
+    }
+
+    #[test]
+    fn configured_options_are_applied() {
+        let config = Config {
+            search_method: Some(SearchMethod::Anchored),
+            min: Some(BareVersion::TwoComponents(1, 60)),
+            features: Some(vec!["a".to_string()]),
+            no_default_features: Some(true),
+            components: Some(vec!["clippy".to_string()]),
+            check_command: Some(vec!["cargo".to_string(), "test".to_string()]),
+            ignore_lockfile: Some(true),
+            ..Config::default()
+        };
+
+        let opts = configured(&["cargo", "msrv", "find"], config).unwrap();
+
+        assert_eq!(opts.search_method, Some(SearchMethod::Anchored));
+        assert_eq!(
+            opts.rust_releases_opts.min.map(|min| min.as_bare_version()),
+            Some(BareVersion::TwoComponents(1, 60))
+        );
+        assert_eq!(opts.custom_check_opts.features, Some(vec!["a".to_string()]));
+        assert!(opts.custom_check_opts.no_default_features);
+        assert_eq!(opts.toolchain_opts.component, vec!["clippy"]);
+        assert_eq!(
+            opts.custom_check_opts.custom_check_opts,
+            Some(vec!["cargo".to_string(), "test".to_string()])
+        );
+        assert!(opts.ignore_lockfile);
+    }
+
+    #[test]
+    fn rejects_configured_ignore_lockfile() {
+        let config = Config {
+            ignore_lockfile: Some(true),
+            ..Config::default()
+        };
+
+        assert!(matches!(
+            configured(
+                &["cargo", "msrv", "find", "--resolve-msrv-lockfile"],
+                config
+            ),
+            Err(Error::Config(ConfigError::ConflictsWith {
+                key: "ignore-lockfile",
+                ..
+            }))
+        ));
+    }
+
+    // Concurrent checks are isolated, so the lockfile of each scratch copy is removed instead
+    #[test]
+    fn configured_ignore_lockfile_with_jobs() {
+        let config = Config {
+            ignore_lockfile: Some(true),
+            ..Config::default()
+        };
+
+        let opts = configured(
+            &["cargo", "msrv", "find", "--linear", "--jobs", "2"],
+            config,
+        );
+
+        assert!(opts.unwrap().ignore_lockfile);
+    }
+
+    #[test]
+    fn rejects_configured_features_with_feature_powerset() {
+        let config = Config {
+            features: Some(vec!["a".to_string()]),
+            ..Config::default()
+        };
+
+        assert!(configured(&["cargo", "msrv", "find", "--feature-powerset"], config).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use cargo_msrv_context::context::error::{Error, TResult};
+use cargo_msrv_context::{DoctorContext, RustReleasesContext};
+use std::convert::{TryFrom, TryInto};
+
+impl TryFrom<CargoMsrvOpts> for DoctorContext {
+    type Error = Error;
+
+    fn try_from(opts: CargoMsrvOpts) -> TResult<Self> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let doctor_opts = match subcommand {
+            SubCommand::Doctor(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `doctor`!"),
+        };
+
+        Ok(Self {
+            rust_releases: RustReleasesContext {
+                release_source: doctor_opts.release_source,
+                offline: doctor_opts.offline,
+                ..RustReleasesContext::default()
+            },
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::config::configure;
 use cargo_msrv_context::context::error::{Error, TResult};
-use cargo_msrv_context::{FindContext, SearchMethod};
+use cargo_msrv_context::{EnvironmentContext, FeatureSetsContext, FindContext, SearchMethod};
 use std::convert::{TryFrom, TryInto};
+use std::num::NonZeroUsize;
 
 impl TryFrom<CargoMsrvOpts> for FindContext {
     type Error = Error;
@@ -12,29 +14,185 @@ impl TryFrom<CargoMsrvOpts> for FindContext {
             subcommand,
         } = opts;
 
-        let find_opts = match subcommand {
+        let mut find_opts = match subcommand {
             SubCommand::Find(opts) => opts,
             _ => unreachable!("This should never happen. The subcommand is not `find`!"),
         };
 
-        let toolchain = find_opts.toolchain_opts.try_into()?;
-        let environment = (&shared_opts).try_into()?;
+        let environment: EnvironmentContext = (&shared_opts).try_into()?;
+        configure(&environment, &mut find_opts)?;
+
+        let mut toolchain_opts = find_opts.toolchain_opts;
+        let additional_targets = toolchain_opts
+            .target
+            .split_off(toolchain_opts.target.len().min(1))
+            .into_iter()
+            .map(|target| -> &'static str { String::leak(target) })
+            .collect::<Vec<_>>();
+
+        let toolchain = toolchain_opts.try_into()?;
+
+        // A custom check command can't select a package, so it would check the same crate for
+        // each of the selected packages
+        if find_opts.custom_check_opts.custom_check_opts.is_some()
+            && environment.workspace_packages.is_explicit_selection()
+        {
+            return Err(Error::CustomCheckCommandWithPackageSelection);
+        }
+
+        // Each of these searches runs for a single package, so it can't be combined with a
+        // selection of multiple packages, nor with another one of these searches
+        let selected_packages = environment.workspace_packages.selected_packages().len();
+        if (find_opts.feature_powerset || find_opts.each_feature) && selected_packages > 1 {
+            return Err(Error::FeatureSetsRequireSinglePackage {
+                selected: selected_packages,
+            });
+        }
+
+        if !additional_targets.is_empty() {
+            if find_opts.feature_powerset || find_opts.each_feature {
+                return Err(Error::MultipleTargetsCombinedWith(
+                    "`--feature-powerset` or `--each-feature`",
+                ));
+            }
+
+            if environment.workspace_packages.is_explicit_selection() {
+                return Err(Error::MultipleTargetsCombinedWith(
+                    "a selection of workspace packages",
+                ));
+            }
+        }
+
+        // The search method may be configured by the project, so it's only known once the
+        // configuration is applied
+        let search_method = if let Some(search_method) = find_opts.search_method {
+            search_method
+        } else if find_opts.linear {
+            SearchMethod::Linear
+        } else if find_opts.multi_bisect {
+            SearchMethod::MultiBisect
+        } else {
+            SearchMethod::Bisect
+        };
+
+        let concurrent_search = matches!(
+            search_method,
+            SearchMethod::Linear | SearchMethod::MultiBisect
+        );
+        if find_opts.jobs.get() > 1 && !concurrent_search {
+            return Err(Error::JobsRequireConcurrentSearch {
+                search_method: search_method.into(),
+            });
+        }
 
         Ok(Self {
-            search_method: if find_opts.linear {
-                SearchMethod::Linear
+            search_method,
+            jobs: find_opts.jobs,
+            verify_monotonicity: find_opts
+                .verify_monotonicity
+                .then_some(find_opts.monotonicity_samples),
+            feature_sets: if find_opts.feature_powerset {
+                Some(FeatureSetsContext {
+                    max_depth: find_opts.depth,
+                    exclude: find_opts.exclude_features,
+                })
+            } else if find_opts.each_feature {
+                Some(FeatureSetsContext {
+                    max_depth: Some(NonZeroUsize::MIN),
+                    exclude: find_opts.exclude_features,
+                })
             } else {
-                SearchMethod::Bisect
+                None
             },
             write_toolchain_file: find_opts.write_toolchain_file,
             ignore_lockfile: find_opts.ignore_lockfile,
+            resolve_msrv_lockfile: find_opts.resolve_msrv_lockfile,
+            // concurrent checks would otherwise share the lockfile of the crate
+            isolated: find_opts.isolated || find_opts.jobs.get() > 1,
+            target_dir_cleanup: find_opts.target_dir_cleanup,
+            cleanup_toolchains: find_opts.cleanup_toolchains,
+            cache: find_opts.cache && !find_opts.no_cache,
             skip_unavailable_toolchains: find_opts.skip_unavailable_toolchains,
             no_check_feedback: find_opts.no_check_feedback,
             write_msrv: find_opts.write_msrv,
             rust_releases: find_opts.rust_releases_opts.into(),
             toolchain,
+            additional_targets,
             check_cmd: find_opts.custom_check_opts.into(),
             environment,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod multiple_targets {
+        use crate::cli::CargoCli;
+        use cargo_msrv_context::FindContext;
+        use std::convert::TryFrom;
+
+        #[test]
+        fn additional_targets() {
+            let opts = CargoCli::parse_args([
+                "cargo", "msrv", "find", "--target", "x", "--target", "y", "--target", "z",
+            ]);
+            let context = FindContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+            assert_eq!(context.toolchain.target, "x");
+            assert_eq!(context.additional_targets, vec!["y", "z"]);
+        }
+
+        #[test]
+        fn single_target() {
+            let opts = CargoCli::parse_args(["cargo", "msrv", "find", "--target", "x"]);
+            let context = FindContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+            assert_eq!(context.toolchain.target, "x");
+            assert!(context.additional_targets.is_empty());
+        }
+
+        #[yare::parameterized(
+            feature_powerset = { &["cargo", "msrv", "find", "--target", "x", "--target", "y", "--feature-powerset"] },
+            each_feature = { &["cargo", "msrv", "find", "--target", "x", "--target", "y", "--each-feature"] },
+        )]
+        fn rejects_multiple_targets_with_feature_sets(args: &[&str]) {
+            let opts = CargoCli::parse_args(args);
+
+            assert!(FindContext::try_from(opts.to_cargo_msrv_cli().to_opts()).is_err());
+        }
+    }
+
+    mod jobs {
+        use crate::cli::CargoCli;
+        use cargo_msrv_context::FindContext;
+        use cargo_msrv_context::context::error::Error;
+        use std::convert::TryFrom;
+
+        #[yare::parameterized(
+            single_job = { &["cargo", "msrv", "find", "--linear"], false },
+            single_job_isolated = { &["cargo", "msrv", "find", "--linear", "--isolated"], true },
+            concurrent_jobs = { &["cargo", "msrv", "find", "--linear", "--jobs", "2"], true },
+        )]
+        fn concurrent_jobs_are_isolated(args: &[&str], isolated: bool) {
+            let opts = CargoCli::parse_args(args);
+            let context = FindContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+            assert_eq!(context.isolated, isolated);
+        }
+
+        #[yare::parameterized(
+            default = { &["cargo", "msrv", "find", "--jobs", "2"] },
+            bisect = { &["cargo", "msrv", "find", "--bisect", "--jobs", "2"] },
+            anchored = { &["cargo", "msrv", "find", "--search-method", "anchored", "--jobs", "2"] },
+        )]
+        fn rejects_concurrent_jobs_without_concurrent_search(args: &[&str]) {
+            let opts = CargoCli::parse_args(args);
+            let result = FindContext::try_from(opts.to_cargo_msrv_cli().to_opts());
+
+            assert!(matches!(
+                result,
+                Err(Error::JobsRequireConcurrentSearch { .. })
+            ));
+        }
+    }
+}
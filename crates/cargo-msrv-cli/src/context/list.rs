@@ -22,7 +22,45 @@ impl TryFrom<CargoMsrvOpts> for ListContext {
 
         Ok(Self {
             variant: list_opts.variant,
+            suggest_downgrades: list_opts
+                .suggest_downgrades
+                .then_some(list_opts.target_msrv)
+                .flatten(),
             environment,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::CargoCli;
+    use cargo_msrv_context::ListContext;
+    use cargo_msrv_types::BareVersion;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn suggest_downgrades() {
+        let opts = CargoCli::parse_args([
+            "cargo",
+            "msrv",
+            "list",
+            "--suggest-downgrades",
+            "--target-msrv",
+            "1.70",
+        ]);
+        let context = ListContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+        assert_eq!(
+            context.suggest_downgrades,
+            Some(BareVersion::TwoComponents(1, 70))
+        );
+    }
+
+    #[test]
+    fn no_suggestions_by_default() {
+        let opts = CargoCli::parse_args(["cargo", "msrv", "list"]);
+        let context = ListContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+        assert!(context.suggest_downgrades.is_none());
+    }
+}
@@ -9,20 +9,25 @@ use camino::Utf8PathBuf;
 use cargo_msrv_context::context::error::{
     Error, InvalidUtf8Error, IoError, IoErrorSource, PathError, TResult,
 };
-use cargo_msrv_context::default_target::default_target;
+use cargo_msrv_context::default_target::{default_target, toolchain_dir_host_triple};
 use cargo_msrv_context::{
-    CheckCommandContext, Context, EnvironmentContext, FindContext, ListContext,
-    RustReleasesContext, SetContext, ShowContext, ToolchainContext, VerifyContext,
-    WorkspacePackages,
+    CacheContext, CheckCommandContext, Context, DoctorContext, EnvironmentContext, FindContext,
+    ListContext, PrintConfigContext, RustReleasesContext, SetContext, ShowContext,
+    ToolchainContext, ToolchainSource, ToolchainsContext, VerifyContext, WorkspacePackages,
 };
 use std::convert::{TryFrom, TryInto};
 use std::env;
 use std::path::Path;
 
+mod cache;
+mod config;
+mod doctor;
 mod find;
 mod list;
+mod print_config;
 mod set;
 mod show;
+mod toolchains;
 mod verify;
 
 impl TryFrom<CargoMsrvOpts> for Context {
@@ -30,10 +35,19 @@ impl TryFrom<CargoMsrvOpts> for Context {
 
     fn try_from(opts: CargoMsrvOpts) -> TResult<Self> {
         let ctx = match opts.subcommand {
+            SubCommand::Find(ref find_opts) if find_opts.print_config => {
+                Self::PrintConfig(PrintConfigContext::try_from(opts)?)
+            }
+            SubCommand::Verify(ref verify_opts) if verify_opts.print_config => {
+                Self::PrintConfig(PrintConfigContext::try_from(opts)?)
+            }
+            SubCommand::Cache(_) => Self::Cache(CacheContext::try_from(opts)?),
+            SubCommand::Doctor(_) => Self::Doctor(DoctorContext::try_from(opts)?),
             SubCommand::Find(_) => Self::Find(FindContext::try_from(opts)?),
             SubCommand::List(_) => Self::List(ListContext::try_from(opts)?),
             SubCommand::Set(_) => Self::Set(SetContext::try_from(opts)?),
             SubCommand::Show => Self::Show(ShowContext::try_from(opts)?),
+            SubCommand::Toolchains(_) => Self::Toolchains(ToolchainsContext::try_from(opts)?),
             SubCommand::Verify(_) => Self::Verify(VerifyContext::try_from(opts)?),
         };
 
@@ -48,6 +62,7 @@ impl From<RustReleasesOpts> for RustReleasesContext {
             maximum_rust_version: opts.max,
             consider_patch_releases: opts.include_all_patch_releases,
             release_source: opts.release_source,
+            offline: opts.offline,
         }
     }
 }
@@ -56,14 +71,6 @@ impl TryFrom<ToolchainOpts> for ToolchainContext {
     type Error = Error;
 
     fn try_from(opts: ToolchainOpts) -> TResult<Self> {
-        let target = if let Some(target) = opts.target {
-            target
-        } else {
-            default_target()?
-        };
-
-        let target: &'static str = String::leak(target);
-
         let components: &'static [&'static str] = Vec::leak(
             opts.component
                 .into_iter()
@@ -74,7 +81,38 @@ impl TryFrom<ToolchainOpts> for ToolchainContext {
                 .collect(),
         );
 
-        Ok(Self { target, components })
+        let source = match opts.toolchain_dir {
+            Some(dir) => {
+                let dir = dunce::canonicalize(&dir)
+                    .map_err(|_| Error::Path(PathError::DoesNotExist(dir)))?;
+                let dir = Utf8PathBuf::try_from(dir).map_err(|err| {
+                    Error::Path(PathError::InvalidUtf8(InvalidUtf8Error::from(err)))
+                })?;
+
+                ToolchainSource::Directory(dir)
+            }
+            None => ToolchainSource::Rustup,
+        };
+
+        let mut targets = opts.target.into_iter();
+
+        let target = match (targets.next(), targets.next()) {
+            (Some(target), None) => target,
+            // toolchains in a directory aren't run with rustup, so rustup may not be present
+            (None, _) => match &source {
+                ToolchainSource::Rustup => default_target()?,
+                ToolchainSource::Directory(dir) => toolchain_dir_host_triple(dir)?,
+            },
+            (Some(_), Some(_)) => return Err(Error::MultipleTargets),
+        };
+
+        let target: &'static str = String::leak(target);
+
+        Ok(Self {
+            target,
+            components,
+            source,
+        })
     }
 }
 
@@ -130,7 +168,7 @@ impl<'shared_opts> TryFrom<&'shared_opts SharedOpts> for EnvironmentContext {
             .manifest_path(root_crate_path.join("Cargo.toml"))
             .exec()
         {
-            let partition = opts.workspace.partition_packages(&metadata);
+            let partition = opts.workspace.to_workspace().partition_packages(&metadata);
             let selected = partition.0.into_iter().cloned().collect();
             let excluded = partition.1;
 
@@ -142,7 +180,7 @@ impl<'shared_opts> TryFrom<&'shared_opts SharedOpts> for EnvironmentContext {
                 ?excluded
             );
 
-            WorkspacePackages::from_vec(selected)
+            WorkspacePackages::from_selection(selected, opts.workspace.selection())
         } else {
             tracing::info!(
                 action = "detect_cargo_workspace_packages",
@@ -0,0 +1,32 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::config::resolve_config;
+use cargo_msrv_context::context::error::{Error, TResult};
+use cargo_msrv_context::{EnvironmentContext, PrintConfigContext};
+use std::convert::{TryFrom, TryInto};
+
+impl TryFrom<CargoMsrvOpts> for PrintConfigContext {
+    type Error = Error;
+
+    fn try_from(opts: CargoMsrvOpts) -> TResult<Self> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let environment: EnvironmentContext = (&shared_opts).try_into()?;
+
+        let (config, layers) = match &subcommand {
+            SubCommand::Find(find_opts) => resolve_config(&environment, find_opts)?,
+            SubCommand::Verify(verify_opts) => resolve_config(&environment, verify_opts)?,
+            _ => {
+                unreachable!("This should never happen. The subcommand is not `find` or `verify`!")
+            }
+        };
+
+        Ok(Self {
+            config,
+            layers,
+            environment,
+        })
+    }
+}
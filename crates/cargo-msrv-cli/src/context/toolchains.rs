@@ -0,0 +1,30 @@
+use crate::cli::{CargoMsrvOpts, SubCommand, ToolchainsCommand};
+use cargo_msrv_context::ToolchainsContext;
+use cargo_msrv_context::context::error::{Error, TResult};
+use cargo_msrv_context::context::toolchains::ToolchainsAction;
+use std::convert::{TryFrom, TryInto};
+
+impl TryFrom<CargoMsrvOpts> for ToolchainsContext {
+    type Error = Error;
+
+    fn try_from(opts: CargoMsrvOpts) -> TResult<Self> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let toolchains_opts = match subcommand {
+            SubCommand::Toolchains(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `toolchains`!"),
+        };
+
+        let action = match toolchains_opts.action {
+            ToolchainsCommand::Prune => ToolchainsAction::Prune,
+        };
+
+        Ok(Self {
+            action,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
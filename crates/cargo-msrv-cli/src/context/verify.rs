@@ -1,7 +1,8 @@
 use crate::cli::{CargoMsrvOpts, SubCommand};
-use cargo_msrv_context::VerifyContext;
+use crate::context::config::configure;
 use cargo_msrv_context::context::error::{Error, TResult};
-use cargo_msrv_context::context::verify::RustVersion;
+use cargo_msrv_context::context::verify::{PackageRustVersion, RustVersion, VerifyRustVersion};
+use cargo_msrv_context::{EnvironmentContext, VerifyContext};
 use std::convert::{TryFrom, TryInto};
 
 impl TryFrom<CargoMsrvOpts> for VerifyContext {
@@ -13,22 +14,50 @@ impl TryFrom<CargoMsrvOpts> for VerifyContext {
             subcommand,
         } = opts;
 
-        let verify_opts = match subcommand {
+        let mut verify_opts = match subcommand {
             SubCommand::Verify(opts) => opts,
             _ => unreachable!("This should never happen. The subcommand is not `verify`!"),
         };
 
+        let environment: EnvironmentContext = (&shared_opts).try_into()?;
+        configure(&environment, &mut verify_opts)?;
+
         let toolchain = verify_opts.toolchain_opts.try_into()?;
-        let environment = (&shared_opts).try_into()?;
 
-        let rust_version = match verify_opts.rust_version {
-            Some(v) => RustVersion::from_arg(v),
-            None => RustVersion::try_from_environment(&environment)?,
+        // A custom check command can't select a package, so it would check the same crate for
+        // each of the selected packages
+        if verify_opts.custom_check_opts.custom_check_opts.is_some()
+            && environment.workspace_packages.is_explicit_selection()
+        {
+            return Err(Error::CustomCheckCommandWithPackageSelection);
+        }
+
+        let rust_version = if environment.workspace_packages.is_explicit_selection() {
+            let packages = environment
+                .workspace_packages
+                .selected_packages()
+                .iter()
+                .map(|package| {
+                    PackageRustVersion::resolve(package, verify_opts.rust_version.as_ref())
+                })
+                .collect::<TResult<Vec<_>>>()?;
+
+            VerifyRustVersion::Workspace(packages)
+        } else {
+            VerifyRustVersion::Crate(match verify_opts.rust_version {
+                Some(v) => RustVersion::from_arg(v),
+                None => RustVersion::try_from_environment(&environment)?,
+            })
         };
 
         Ok(Self {
             rust_version,
             ignore_lockfile: verify_opts.ignore_lockfile,
+            resolve_msrv_lockfile: verify_opts.resolve_msrv_lockfile,
+            isolated: verify_opts.isolated,
+            target_dir_cleanup: verify_opts.target_dir_cleanup,
+            cleanup_toolchains: verify_opts.cleanup_toolchains,
+            cache: verify_opts.cache && !verify_opts.no_cache,
             no_check_feedback: verify_opts.no_check_feedback,
             rust_releases: verify_opts.rust_releases_opts.into(),
             toolchain,
@@ -52,5 +81,13 @@ mod tests {
 
             assert_eq!(context.toolchain.target, "x");
         }
+
+        #[test]
+        fn rejects_multiple_targets() {
+            let opts =
+                CargoCli::parse_args(["cargo", "msrv", "verify", "--target", "x", "--target", "y"]);
+
+            assert!(VerifyContext::try_from(opts.to_cargo_msrv_cli().to_opts()).is_err());
+        }
     }
 }
@@ -11,6 +11,8 @@ pub mod list_msrv_variant;
 pub mod log_level;
 pub mod output_format;
 pub mod release_source;
+pub mod search_method;
+pub mod target_dir_cleanup;
 pub mod tracing_target_option;
 
 pub struct CliValue<T> {
@@ -97,6 +99,7 @@ pub struct UnknownValueError(String);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cargo_msrv_context::SearchMethod;
     use cargo_msrv_context::types::{
         ListMsrvVariant, LogLevel, OutputFormat, ReleaseSource, TracingTargetOption,
     };
@@ -150,6 +153,16 @@ mod tests {
         assert_eq!(parse(list_msrv_variant::VALUES, input).unwrap(), expected);
     }
 
+    #[yare::parameterized(
+        bisect = { "bisect", SearchMethod::Bisect },
+        linear = { "linear", SearchMethod::Linear },
+        multi_bisect = { "multi-bisect", SearchMethod::MultiBisect },
+        anchored = { "anchored", SearchMethod::Anchored },
+    )]
+    fn parses_search_method(input: &str, expected: SearchMethod) {
+        assert_eq!(parse(search_method::VALUES, input).unwrap(), expected);
+    }
+
     #[test]
     fn parses_release_source() {
         assert_eq!(
@@ -162,6 +175,36 @@ mod tests {
             parse(release_source::VALUES, "rust-dist").unwrap(),
             ReleaseSource::RustDist
         );
+
+        assert_eq!(
+            parse(release_source::VALUES, "rustup-installed").unwrap(),
+            ReleaseSource::RustupInstalled
+        );
+    }
+
+    #[yare::parameterized(
+        named = { "rustup-installed", ReleaseSource::RustupInstalled },
+        file = { "file:releases.txt", ReleaseSource::File("releases.txt".into()) },
+    )]
+    fn parses_release_source_with_file(input: &str, expected: ReleaseSource) {
+        let parsed = release_source::parser()
+            .parse_ref(&clap::Command::new("cargo-msrv"), None, OsStr::new(input))
+            .unwrap();
+
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn rejects_unknown_release_source() {
+        assert!(
+            release_source::parser()
+                .parse_ref(
+                    &clap::Command::new("cargo-msrv"),
+                    None,
+                    OsStr::new("unknown")
+                )
+                .is_err()
+        );
     }
 
     #[test]
@@ -171,6 +214,7 @@ mod tests {
         assert_eq!(tracing_target_option::VALUES.default_value(), "file");
         assert_eq!(list_msrv_variant::VALUES.default_value(), "ordered-by-msrv");
         assert_eq!(release_source::VALUES.default_value(), "rust-changelog");
+        assert_eq!(search_method::VALUES.default_value(), "bisect");
     }
 
     #[yare::parameterized(
@@ -1,8 +1,19 @@
 use crate::values::{CliValue, CliValues};
 use cargo_msrv_context::types::ReleaseSource;
+use clap::builder::{StringValueParser, TypedValueParser};
+use std::str::FromStr;
 
-pub const VALUES: CliValues<ReleaseSource> = CliValues::new(&[
+// The release sources which are selected by name; unlike a file source, these don't have a payload
+static NAMED: &[CliValue<ReleaseSource>] = &[
     CliValue::new("rust-changelog", ReleaseSource::RustChangelog),
     #[cfg(feature = "rust-releases-dist-source")]
     CliValue::new("rust-dist", ReleaseSource::RustDist),
-]);
+    CliValue::new("rustup-installed", ReleaseSource::RustupInstalled),
+];
+
+pub static VALUES: CliValues<ReleaseSource> = CliValues::new(NAMED);
+
+/// Parses one of the named [`VALUES`], or a user supplied file, given as `file:<path>`.
+pub fn parser() -> impl TypedValueParser<Value = ReleaseSource> {
+    StringValueParser::new().try_map(|value| ReleaseSource::from_str(&value))
+}
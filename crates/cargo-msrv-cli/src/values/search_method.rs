@@ -0,0 +1,12 @@
+use crate::values::{CliValue, CliValues};
+use cargo_msrv_context::SearchMethod;
+
+pub const VALUES: CliValues<SearchMethod> = CliValues::new(&[
+    CliValue::new("bisect", SearchMethod::Bisect).help("A binary search (default)"),
+    CliValue::new("linear", SearchMethod::Linear)
+        .help("A linear search, from the most recent release to the earliest"),
+    CliValue::new("multi-bisect", SearchMethod::MultiBisect)
+        .help("A k-ary search, which checks up to `--jobs` releases concurrently"),
+    CliValue::new("anchored", SearchMethod::Anchored)
+        .help("A search which starts from the declared MSRV, and gallops away from it"),
+]);
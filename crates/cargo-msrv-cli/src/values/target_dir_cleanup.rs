@@ -0,0 +1,9 @@
+use crate::values::{CliValue, CliValues};
+use cargo_msrv_context::TargetDirCleanup;
+
+pub const VALUES: CliValues<TargetDirCleanup> = CliValues::new(&[
+    CliValue::new("keep", TargetDirCleanup::Keep).help(
+        "Keep the target directories, so a next run can re-use their build artifacts (default)",
+    ),
+    CliValue::new("delete", TargetDirCleanup::Delete).help("Delete the target directories"),
+]);
@@ -0,0 +1,544 @@
+//! The configuration of a project, read from its Cargo manifest, or from a `cargo-msrv.toml` file.
+//!
+//! Configuration is read from the following sources, from lowest to highest precedence:
+//!
+//! 1. The `[workspace.metadata.cargo-msrv]` table of the workspace manifest
+//! 2. The `[package.metadata.cargo-msrv]` table of the package manifest
+//! 3. The `cargo-msrv.toml` file in the root of the crate
+//!
+//! Options given on the command line take precedence over each of them.
+
+use crate::context::SearchMethod;
+use crate::context::error::{ConfigError, IoError, IoErrorSource, TResult};
+use crate::types::Edition;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_msrv_types::BareVersion;
+use std::fmt;
+use std::str::FromStr;
+use toml_edit::{Array, DocumentMut, Item, TableLike, value};
+
+/// The name of the standalone configuration file, in the root of the crate.
+pub const CONFIG_FILE_NAME: &str = "cargo-msrv.toml";
+
+/// The name of the table in `package.metadata` and `workspace.metadata`.
+const METADATA_TABLE: &str = "cargo-msrv";
+
+const SEARCH_METHODS: [SearchMethod; 4] = [
+    SearchMethod::Linear,
+    SearchMethod::Bisect,
+    SearchMethod::MultiBisect,
+    SearchMethod::Anchored,
+];
+
+/// Options which can be configured for a project. Options which are not set are `None`.
+///
+/// The keys are the names of the equivalent command line options, e.g. `search-method`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Config {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_search_method"
+    )]
+    pub search_method: Option<SearchMethod>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<BareVersion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<BareVersion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_features: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_default_features: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_command: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_lockfile: Option<bool>,
+}
+
+impl Config {
+    /// Merge the given layers, where each layer overrides the options set by the layers
+    /// before it.
+    pub fn merge<'layer>(layers: impl IntoIterator<Item = &'layer ConfigLayer>) -> Self {
+        layers.into_iter().fold(Self::default(), |lower, layer| {
+            layer.config.clone().or(lower)
+        })
+    }
+
+    /// The options of `self`, where each option which is not set, is taken from `lower`.
+    pub fn or(self, lower: Self) -> Self {
+        Self {
+            search_method: self.search_method.or(lower.search_method),
+            min: self.min.or(lower.min),
+            max: self.max.or(lower.max),
+            features: self.features.or(lower.features),
+            all_features: self.all_features.or(lower.all_features),
+            no_default_features: self.no_default_features.or(lower.no_default_features),
+            components: self.components.or(lower.components),
+            target: self.target.or(lower.target),
+            check_command: self.check_command.or(lower.check_command),
+            ignore_lockfile: self.ignore_lockfile.or(lower.ignore_lockfile),
+        }
+    }
+
+    /// The configuration as a TOML document, in the format of a `cargo-msrv.toml` file.
+    pub fn to_toml(&self) -> String {
+        let mut document = DocumentMut::new();
+        let strings = |list: &[String]| value(list.iter().collect::<Array>());
+
+        if let Some(search_method) = self.search_method {
+            document["search-method"] = value(<&str>::from(search_method));
+        }
+        if let Some(min) = &self.min {
+            document["min"] = value(min.to_string());
+        }
+        if let Some(max) = &self.max {
+            document["max"] = value(max.to_string());
+        }
+        if let Some(features) = &self.features {
+            document["features"] = strings(features);
+        }
+        if let Some(all_features) = self.all_features {
+            document["all-features"] = value(all_features);
+        }
+        if let Some(no_default_features) = self.no_default_features {
+            document["no-default-features"] = value(no_default_features);
+        }
+        if let Some(components) = &self.components {
+            document["components"] = strings(components);
+        }
+        if let Some(target) = &self.target {
+            document["target"] = strings(target);
+        }
+        if let Some(check_command) = &self.check_command {
+            document["check-command"] = strings(check_command);
+        }
+        if let Some(ignore_lockfile) = self.ignore_lockfile {
+            document["ignore-lockfile"] = value(ignore_lockfile);
+        }
+
+        document.to_string()
+    }
+
+    /// Parse the configuration from a table, which was read from the given source.
+    fn from_table(table: &dyn TableLike, origin: &ConfigSource) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+
+        for (key, item) in table.iter() {
+            let invalid = |expected| ConfigError::InvalidValue {
+                origin: origin.clone(),
+                key: key.to_string(),
+                expected,
+            };
+
+            match key {
+                "search-method" => {
+                    config.search_method =
+                        Some(item.as_str().and_then(parse_search_method).ok_or_else(|| {
+                            invalid("one of 'linear', 'bisect', 'multi-bisect' or 'anchored'")
+                        })?);
+                }
+                "min" => {
+                    config.min = Some(
+                        parse_edition_or_version(item)
+                            .ok_or_else(|| invalid("a Rust version or edition"))?,
+                    );
+                }
+                "max" => {
+                    config.max = Some(
+                        item.as_str()
+                            .and_then(|version| BareVersion::from_str(version).ok())
+                            .ok_or_else(|| invalid("a Rust version"))?,
+                    );
+                }
+                "features" => {
+                    config.features = Some(
+                        string_list(item, split_whitespace)
+                            .ok_or_else(|| invalid("a list of features"))?,
+                    );
+                }
+                "all-features" => {
+                    config.all_features = Some(item.as_bool().ok_or_else(|| invalid("a boolean"))?);
+                }
+                "no-default-features" => {
+                    config.no_default_features =
+                        Some(item.as_bool().ok_or_else(|| invalid("a boolean"))?);
+                }
+                "components" => {
+                    config.components = Some(
+                        string_list(item, |component| vec![component.to_string()])
+                            .ok_or_else(|| invalid("a list of components"))?,
+                    );
+                }
+                "target" => {
+                    config.target = Some(
+                        string_list(item, |target| vec![target.to_string()])
+                            .ok_or_else(|| invalid("a target, or a list of targets"))?,
+                    );
+                }
+                "check-command" => {
+                    config.check_command = Some(
+                        string_list(item, split_whitespace)
+                            .ok_or_else(|| invalid("a command, or a list of arguments"))?,
+                    );
+                }
+                "ignore-lockfile" => {
+                    config.ignore_lockfile =
+                        Some(item.as_bool().ok_or_else(|| invalid("a boolean"))?);
+                }
+                unknown => {
+                    return Err(ConfigError::UnknownKey {
+                        origin: origin.clone(),
+                        key: unknown.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+fn serialize_search_method<S>(
+    method: &Option<SearchMethod>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match method {
+        Some(method) => serializer.serialize_str((*method).into()),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn parse_search_method(name: &str) -> Option<SearchMethod> {
+    SEARCH_METHODS
+        .into_iter()
+        .find(|&method| <&str>::from(method) == name)
+}
+
+/// Like `--min`, accepts a Rust version, or an edition, which may be given as a number.
+fn parse_edition_or_version(item: &Item) -> Option<BareVersion> {
+    let input = item
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| item.as_integer().map(|edition| edition.to_string()))?;
+
+    Edition::from_str(&input)
+        .map(|edition| edition.as_bare_version())
+        .ok()
+        .or_else(|| BareVersion::from_str(&input).ok())
+}
+
+/// A list of strings, or a single string, which is split into a list with `split`.
+fn string_list(item: &Item, split: impl Fn(&str) -> Vec<String>) -> Option<Vec<String>> {
+    if let Some(string) = item.as_str() {
+        return Some(split(string));
+    }
+
+    item.as_array()?
+        .iter()
+        .map(|value| value.as_str().map(str::to_string))
+        .collect()
+}
+
+fn split_whitespace(input: &str) -> Vec<String> {
+    input.split_whitespace().map(str::to_string).collect()
+}
+
+/// Where a layer of configuration was read from.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum ConfigSource {
+    /// The `[workspace.metadata.cargo-msrv]` table of a workspace manifest
+    WorkspaceManifest { path: Utf8PathBuf },
+    /// The `[package.metadata.cargo-msrv]` table of a package manifest
+    PackageManifest { path: Utf8PathBuf },
+    /// A `cargo-msrv.toml` file
+    ConfigFile { path: Utf8PathBuf },
+    /// The options given on the command line, or by `CARGO_MSRV_*` environment variables
+    CommandLine,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WorkspaceManifest { path } => {
+                write!(f, "'[workspace.metadata.{}]' of '{}'", METADATA_TABLE, path)
+            }
+            Self::PackageManifest { path } => {
+                write!(f, "'[package.metadata.{}]' of '{}'", METADATA_TABLE, path)
+            }
+            Self::ConfigFile { path } => write!(f, "'{}'", path),
+            Self::CommandLine => write!(f, "the command line, or environment variables"),
+        }
+    }
+}
+
+/// The configuration read from a single source.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct ConfigLayer {
+    pub source: ConfigSource,
+    pub config: Config,
+}
+
+impl ConfigLayer {
+    pub fn new(source: ConfigSource, config: Config) -> Self {
+        Self { source, config }
+    }
+
+    /// Read each source of configuration of the crate at `crate_root` which exists, from lowest
+    /// to highest precedence.
+    pub fn load(crate_root: &Utf8Path) -> TResult<Vec<Self>> {
+        let mut layers = Vec::new();
+
+        if let Some((path, manifest)) = workspace_manifest(crate_root)?
+            && let Some(table) = metadata_table(&manifest, "workspace")
+        {
+            let source = ConfigSource::WorkspaceManifest { path };
+            layers.push(Self::new(
+                source.clone(),
+                Config::from_table(table, &source)?,
+            ));
+        }
+
+        let path = crate_root.join("Cargo.toml");
+        let manifest = path.is_file().then(|| read_toml(&path)).transpose()?;
+        if let Some(table) = manifest
+            .as_ref()
+            .and_then(|manifest| metadata_table(manifest, "package"))
+        {
+            let source = ConfigSource::PackageManifest { path };
+            layers.push(Self::new(
+                source.clone(),
+                Config::from_table(table, &source)?,
+            ));
+        }
+
+        let path = crate_root.join(CONFIG_FILE_NAME);
+        if path.is_file() {
+            let document = read_toml(&path)?;
+            let source = ConfigSource::ConfigFile { path };
+            layers.push(Self::new(
+                source.clone(),
+                Config::from_table(document.as_table(), &source)?,
+            ));
+        }
+
+        Ok(layers)
+    }
+}
+
+/// The nearest manifest which defines a workspace, starting at the crate root, like Cargo does.
+fn workspace_manifest(crate_root: &Utf8Path) -> TResult<Option<(Utf8PathBuf, DocumentMut)>> {
+    for dir in crate_root.ancestors() {
+        let path = dir.join("Cargo.toml");
+
+        if path.is_file() {
+            let manifest = read_toml(&path)?;
+
+            if manifest.contains_key("workspace") {
+                return Ok(Some((path, manifest)));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// The `[<parent>.metadata.cargo-msrv]` table of a manifest.
+fn metadata_table<'doc>(manifest: &'doc DocumentMut, parent: &str) -> Option<&'doc dyn TableLike> {
+    manifest
+        .get(parent)
+        .and_then(Item::as_table_like)
+        .and_then(|table| table.get("metadata"))
+        .and_then(Item::as_table_like)
+        .and_then(|metadata| metadata.get(METADATA_TABLE))
+        .and_then(Item::as_table_like)
+}
+
+fn read_toml(path: &Utf8Path) -> TResult<DocumentMut> {
+    let contents = std::fs::read_to_string(path).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    let document = contents
+        .parse::<DocumentMut>()
+        .map_err(|error| ConfigError::ParseToml {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> Result<Config, ConfigError> {
+        let document = toml.parse::<DocumentMut>().unwrap();
+        let source = ConfigSource::ConfigFile {
+            path: Utf8PathBuf::from(CONFIG_FILE_NAME),
+        };
+
+        Config::from_table(document.as_table(), &source)
+    }
+
+    #[test]
+    fn parses_each_key() {
+        let config = parse(
+            r#"
+            search-method = "multi-bisect"
+            min = "2018"
+            max = "1.80"
+            features = "a b"
+            all-features = false
+            no-default-features = true
+            components = ["clippy"]
+            target = "x86_64-unknown-linux-gnu"
+            check-command = ["cargo", "test"]
+            ignore-lockfile = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                search_method: Some(SearchMethod::MultiBisect),
+                min: Some(BareVersion::ThreeComponents(1, 31, 0)),
+                max: Some(BareVersion::TwoComponents(1, 80)),
+                features: Some(vec!["a".to_string(), "b".to_string()]),
+                all_features: Some(false),
+                no_default_features: Some(true),
+                components: Some(vec!["clippy".to_string()]),
+                target: Some(vec!["x86_64-unknown-linux-gnu".to_string()]),
+                check_command: Some(vec!["cargo".to_string(), "test".to_string()]),
+                ignore_lockfile: Some(true),
+            }
+        );
+    }
+
+    #[yare::parameterized(
+        edition_number = { "min = 2021", BareVersion::ThreeComponents(1, 56, 0) },
+        version = { "min = \"1.60.1\"", BareVersion::ThreeComponents(1, 60, 1) },
+    )]
+    fn parses_min(toml: &str, expected: BareVersion) {
+        assert_eq!(parse(toml).unwrap().min, Some(expected));
+    }
+
+    #[yare::parameterized(
+        search_method = { "search-method = \"galloping\"", "search-method" },
+        min = { "min = \"x\"", "min" },
+        features = { "features = [1]", "features" },
+        ignore_lockfile = { "ignore-lockfile = \"yes\"", "ignore-lockfile" },
+    )]
+    fn invalid_value_names_key(toml: &str, expected_key: &str) {
+        let error = parse(toml).unwrap_err();
+
+        assert!(
+            matches!(&error, ConfigError::InvalidValue { key, .. } if key == expected_key),
+            "{error}"
+        );
+        assert!(error.to_string().contains(CONFIG_FILE_NAME));
+    }
+
+    #[test]
+    fn rejects_unknown_key() {
+        let error = parse("search = \"linear\"").unwrap_err();
+
+        assert!(matches!(error, ConfigError::UnknownKey { key, .. } if key == "search"));
+    }
+
+    #[test]
+    fn higher_layers_take_precedence() {
+        let layer = |path: &str, toml: &str| {
+            ConfigLayer::new(
+                ConfigSource::ConfigFile {
+                    path: Utf8PathBuf::from(path),
+                },
+                parse(toml).unwrap(),
+            )
+        };
+
+        let layers = [
+            layer("workspace", "min = \"1.60\"\nmax = \"1.70\""),
+            layer("package", "max = \"1.75\"\nignore-lockfile = true"),
+            layer("file", "ignore-lockfile = false"),
+        ];
+
+        assert_eq!(
+            Config::merge(&layers),
+            Config {
+                min: Some(BareVersion::TwoComponents(1, 60)),
+                max: Some(BareVersion::TwoComponents(1, 75)),
+                ignore_lockfile: Some(false),
+                ..Config::default()
+            }
+        );
+    }
+
+    #[test]
+    fn to_toml_round_trips() {
+        let toml = "search-method = \"anchored\"\nmin = \"1.56.0\"\ncheck-command = [\"cargo\", \"test\"]\nignore-lockfile = true\n";
+
+        assert_eq!(parse(toml).unwrap().to_toml(), toml);
+    }
+
+    #[test]
+    fn loads_layers_of_workspace_member() {
+        let root = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .unwrap()
+            .join(format!("cargo-msrv-config-{}", std::process::id()));
+        let member = root.join("member");
+        std::fs::create_dir_all(&member).unwrap();
+
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"member\"]\n\n[workspace.metadata.cargo-msrv]\nmin = \"1.60\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"member\"\n\n[package.metadata.cargo-msrv]\nmax = \"1.70\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            member.join(CONFIG_FILE_NAME),
+            "search-method = \"linear\"\n",
+        )
+        .unwrap();
+
+        let layers = ConfigLayer::load(&member);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let sources = layers
+            .unwrap()
+            .into_iter()
+            .map(|layer| layer.source)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sources,
+            vec![
+                ConfigSource::WorkspaceManifest {
+                    path: root.join("Cargo.toml")
+                },
+                ConfigSource::PackageManifest {
+                    path: member.join("Cargo.toml")
+                },
+                ConfigSource::ConfigFile {
+                    path: member.join(CONFIG_FILE_NAME)
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crate::context::EnvironmentContext;
+
+#[derive(Debug)]
+pub struct CacheContext {
+    /// The action to perform on the cache
+    pub action: CacheAction,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+/// An action to perform on the cache of compatibility check outcomes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CacheAction {
+    /// Remove all cached outcomes
+    Clear,
+}
@@ -0,0 +1,10 @@
+use crate::context::{EnvironmentContext, RustReleasesContext};
+
+#[derive(Debug)]
+pub struct DoctorContext {
+    /// The release source of which the index is checked
+    pub rust_releases: RustReleasesContext,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
@@ -1,3 +1,4 @@
+use crate::config::ConfigSource;
 use crate::types::ParseEditionError;
 use camino::Utf8PathBuf;
 use cargo_msrv_manifest::ManifestParseError;
@@ -15,12 +16,41 @@ pub enum Error {
     #[error("The default host triple (target) could not be found.")]
     DefaultHostTripleNotFound,
 
+    #[error(
+        "No toolchain, to determine the host triple (target) with, was found in '{0}'. Use `--target` to set the target."
+    )]
+    NoToolchainInDirectory(Utf8PathBuf),
+
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+
     #[error(transparent)]
     Io(#[from] IoError),
 
     #[error(transparent)]
     ManifestParseError(#[from] ManifestParseError),
 
+    #[error(
+        "A custom check command can't be combined with a selection of workspace packages, since it would be run as-is for each package"
+    )]
+    CustomCheckCommandWithPackageSelection,
+
+    #[error("Multiple targets are only supported by `cargo msrv find`")]
+    MultipleTargets,
+
+    #[error("Multiple targets can't be combined with {0}")]
+    MultipleTargetsCombinedWith(&'static str),
+
+    #[error(
+        "`--feature-powerset` and `--each-feature` require a single package, but {selected} packages were selected. Use `--package` to select one."
+    )]
+    FeatureSetsRequireSinglePackage { selected: usize },
+
+    #[error(
+        "More than one job requires the 'linear' or 'multi-bisect' search method, but the '{search_method}' search method was selected"
+    )]
+    JobsRequireConcurrentSearch { search_method: &'static str },
+
     #[error("Unable to find key 'package.rust-version' (or 'package.metadata.msrv') in '{0}'")]
     NoMSRVKeyInCargoToml(Utf8PathBuf),
 
@@ -61,6 +91,18 @@ pub enum IoErrorSource {
     #[error("Unable to rename file '{0}'")]
     RenameFile(Utf8PathBuf),
 
+    #[error("Unable to copy file '{0}'")]
+    CopyFile(Utf8PathBuf),
+
+    #[error("Unable to create folder '{0}'")]
+    CreateDir(Utf8PathBuf),
+
+    #[error("Unable to remove folder '{0}'")]
+    RemoveDir(Utf8PathBuf),
+
+    #[error("Unable to read folder '{0}'")]
+    ReadDir(Utf8PathBuf),
+
     #[error("Unable to spawn process '{0:?}'")]
     SpawnProcess(OsString),
 
@@ -68,6 +110,31 @@ pub enum IoErrorSource {
     WaitForProcessAndCollectOutput(OsString),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Unable to parse configuration file '{path}': {error}")]
+    ParseToml {
+        path: Utf8PathBuf,
+        error: toml_edit::TomlError,
+    },
+
+    #[error("Unknown key '{key}' in {origin}")]
+    UnknownKey { origin: ConfigSource, key: String },
+
+    #[error("Invalid value for key '{key}' in {origin}: expected {expected}")]
+    InvalidValue {
+        origin: ConfigSource,
+        key: String,
+        expected: &'static str,
+    },
+
+    #[error("The configured '{key}' can't be combined with `{flag}`")]
+    ConflictsWith {
+        key: &'static str,
+        flag: &'static str,
+    },
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum PathError {
     #[error("'{}' does not exist", .0.display())]
@@ -1,18 +1,45 @@
 use crate::context::{
-    CheckCommandContext, EnvironmentContext, RustReleasesContext, SearchMethod, ToolchainContext,
+    CheckCommandContext, EnvironmentContext, RustReleasesContext, SearchMethod, TargetDirCleanup,
+    ToolchainContext,
 };
+use std::num::NonZeroUsize;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct FindContext {
     /// Use a binary (bisect) or linear search to find the MSRV
     pub search_method: SearchMethod,
 
+    /// The maximum amount of compatibility checks which may run concurrently
+    pub jobs: NonZeroUsize,
+
+    /// Check up to this amount of Rust releases more recent than the MSRV, after the search,
+    /// to detect non-monotonic compatibility
+    pub verify_monotonicity: Option<NonZeroUsize>,
+
+    /// Find the MSRV of each combination of features, instead of for a single set of features
+    pub feature_sets: Option<FeatureSetsContext>,
+
     /// Write the toolchain file if the MSRV is found
     pub write_toolchain_file: bool,
 
     /// Ignore the lockfile for the MSRV search
     pub ignore_lockfile: bool,
 
+    /// Check each toolchain against a lockfile which was resolved for that toolchain
+    pub resolve_msrv_lockfile: bool,
+
+    /// Run each check in a copy of the crate outside of the working tree
+    pub isolated: bool,
+
+    /// What happens to the target directory of each checked toolchain, once the search is done
+    pub target_dir_cleanup: TargetDirCleanup,
+
+    /// Uninstall the toolchains which were installed during the search, once it is done
+    pub cleanup_toolchains: bool,
+
+    /// Use, and store, cached outcomes of compatibility checks
+    pub cache: bool,
+
     /// Don't print the result of compatibility checks
     pub no_check_feedback: bool,
 
@@ -28,9 +55,25 @@ pub struct FindContext {
     /// The context for Rust toolchains
     pub toolchain: ToolchainContext,
 
+    /// The targets, in addition to the target of the toolchain context, to find the MSRV for
+    pub additional_targets: Vec<&'static str>,
+
     /// The context for checks to be used with rustup
     pub check_cmd: CheckCommandContext,
 
     /// Resolved environment options
     pub environment: EnvironmentContext,
 }
+
+/// Which combinations of the features of a package are searched.
+///
+/// Each feature set is checked without the default features, so the empty feature set is the
+/// baseline to which the other feature sets are compared.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FeatureSetsContext {
+    /// The maximum amount of features in a feature set, or no limit if `None`
+    pub max_depth: Option<NonZeroUsize>,
+
+    /// Features which are not part of any feature set
+    pub exclude: Vec<String>,
+}
@@ -1,11 +1,15 @@
 use crate::context::EnvironmentContext;
 use crate::types::ListMsrvVariant;
+use cargo_msrv_types::BareVersion;
 
 #[derive(Debug)]
 pub struct ListContext {
     /// The type of output expected by the user
     pub variant: ListMsrvVariant,
 
+    /// Suggest dependency downgrades which bring the dependency tree within this MSRV, if set
+    pub suggest_downgrades: Option<BareVersion>,
+
     /// Resolved environment options
     pub environment: EnvironmentContext,
 }
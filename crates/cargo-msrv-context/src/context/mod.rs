@@ -11,17 +11,25 @@ use crate::types::{Edition, LogLevel, ReleaseSource, TracingTargetOption};
 use camino::{Utf8Path, Utf8PathBuf};
 use cargo_msrv_types::BareVersion;
 
+pub mod cache;
+pub mod doctor;
 pub mod error;
 pub mod find;
 pub mod list;
+pub mod print_config;
 pub mod set;
 pub mod show;
+pub mod toolchains;
 pub mod verify;
 
-pub use find::FindContext;
+pub use cache::CacheContext;
+pub use doctor::DoctorContext;
+pub use find::{FeatureSetsContext, FindContext};
 pub use list::ListContext;
+pub use print_config::PrintConfigContext;
 pub use set::SetContext;
 pub use show::ShowContext;
+pub use toolchains::ToolchainsContext;
 pub use verify::VerifyContext;
 
 /// A `context` in `cargo-msrv`, is a definitive and flattened set of options,
@@ -35,7 +43,7 @@ pub use verify::VerifyContext;
 /// using `clap` as an argument parser, but may be just one way to provide user
 /// input. Alternative user interfaces may be provided, such as one which parses
 /// environment variables and another which reads inputs from a configuration
-/// file. If multiple inputs are provided, they should be merged with a specified
+/// file (see [`crate::config`]). If multiple inputs are provided, they should be merged with a specified
 /// precedence. The final, flattened result shall be used as the program's internal
 /// interface, i.e. this `context`.
 ///
@@ -44,30 +52,42 @@ pub use verify::VerifyContext;
 /// data.
 #[derive(Debug)]
 pub enum Context {
+    Cache(CacheContext),
+    Doctor(DoctorContext),
     Find(FindContext),
     List(ListContext),
+    PrintConfig(PrintConfigContext),
     Set(SetContext),
     Show(ShowContext),
+    Toolchains(ToolchainsContext),
     Verify(VerifyContext),
 }
 
 impl Context {
     pub fn reporting_name(&self) -> &'static str {
         match self {
+            Context::Cache(_) => "cache",
+            Context::Doctor(_) => "doctor",
             Context::Find(_) => "find",
             Context::List(_) => "list",
+            Context::PrintConfig(_) => "config",
             Context::Set(_) => "set",
             Context::Show(_) => "show",
+            Context::Toolchains(_) => "toolchains",
             Context::Verify(_) => "verify",
         }
     }
 
     pub fn environment_context(&self) -> &EnvironmentContext {
         match self {
+            Context::Cache(ctx) => &ctx.environment,
+            Context::Doctor(ctx) => &ctx.environment,
             Context::Find(ctx) => &ctx.environment,
             Context::List(ctx) => &ctx.environment,
+            Context::PrintConfig(ctx) => &ctx.environment,
             Context::Set(ctx) => &ctx.environment,
             Context::Show(ctx) => &ctx.environment,
+            Context::Toolchains(ctx) => &ctx.environment,
             Context::Verify(ctx) => &ctx.environment,
         }
     }
@@ -104,6 +124,9 @@ pub struct RustReleasesContext {
 
     /// The release source to use.
     pub release_source: ReleaseSource,
+
+    /// Only use the cached copy of the release index, instead of fetching it.
+    pub offline: bool,
 }
 
 impl RustReleasesContext {
@@ -146,16 +169,29 @@ impl RustReleasesContext {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ToolchainContext {
     /// The target of the toolchain
     pub target: &'static str,
 
     /// Components to be installed for the toolchain
     pub components: &'static [&'static str],
+
+    /// Where the toolchains come from
+    pub source: ToolchainSource,
 }
 
-#[derive(Debug)]
+/// Where the toolchains which are checked come from.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum ToolchainSource {
+    /// Toolchains installed with rustup, on demand
+    #[default]
+    Rustup,
+    /// Toolchains which are unpacked in a directory, each at `<dir>/<version>`
+    Directory(Utf8PathBuf),
+}
+
+#[derive(Clone, Debug)]
 pub struct CheckCommandContext {
     pub cargo_features: Option<Vec<String>>,
 
@@ -201,12 +237,25 @@ impl EnvironmentContext {
 #[derive(Clone, Debug, Default)]
 pub struct WorkspacePackages {
     selected: Option<Vec<cargo_metadata::Package>>,
+    selection: PackageSelection,
 }
 
 impl WorkspacePackages {
     pub fn from_vec(selected: Vec<cargo_metadata::Package>) -> Self {
         Self {
             selected: Some(selected),
+            selection: PackageSelection::Default,
+        }
+    }
+
+    /// Like [`WorkspacePackages::from_vec`], but also records how the packages were selected.
+    pub fn from_selection(
+        selected: Vec<cargo_metadata::Package>,
+        selection: PackageSelection,
+    ) -> Self {
+        Self {
+            selected: Some(selected),
+            selection,
         }
     }
 
@@ -221,6 +270,17 @@ impl WorkspacePackages {
         })
     }
 
+    /// How the packages were selected.
+    pub fn selection(&self) -> PackageSelection {
+        self.selection
+    }
+
+    /// Whether the user explicitly selected packages, as opposed to the selection
+    /// of default workspace members which is made when no workspace flags were used.
+    pub fn is_explicit_selection(&self) -> bool {
+        self.selection != PackageSelection::Default && !self.selected_packages().is_empty()
+    }
+
     /// The default package is used when either:
     /// 1. No packages were selected (e.g. because we are not in a cargo workspace or do not use cargo)
     /// 2. No workspace flags like --workspace, --package, --all or --exclude are used
@@ -239,6 +299,19 @@ impl WorkspacePackages {
     }
 }
 
+/// How the workspace packages were selected by the user.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PackageSelection {
+    /// No workspace flags were used, the default workspace members are selected.
+    #[default]
+    Default,
+    /// All workspace members were selected, e.g. via `--workspace`, possibly minus those given
+    /// by `--exclude`.
+    Workspace,
+    /// Specific packages were selected via `--package`.
+    Packages,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct SelectedPackage {
@@ -252,6 +325,10 @@ pub enum SearchMethod {
     Linear,
     #[default]
     Bisect,
+    /// A k-ary search, which checks multiple split points concurrently
+    MultiBisect,
+    /// A search which starts from the declared MSRV, and gallops away from it
+    Anchored,
 }
 
 impl From<SearchMethod> for &'static str {
@@ -259,10 +336,23 @@ impl From<SearchMethod> for &'static str {
         match method {
             SearchMethod::Linear => "linear",
             SearchMethod::Bisect => "bisect",
+            SearchMethod::MultiBisect => "multi-bisect",
+            SearchMethod::Anchored => "anchored",
         }
     }
 }
 
+/// What happens to the Cargo target directory of each checked toolchain, once the run is done.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetDirCleanup {
+    /// Keep the target directories, so a next run can re-use their build artifacts
+    #[default]
+    Keep,
+    /// Delete the target directories
+    Delete,
+}
+
 #[derive(Debug, Clone)]
 pub struct TracingOptions {
     target: TracingTargetOption,
@@ -0,0 +1,14 @@
+use crate::config::{Config, ConfigLayer};
+use crate::context::EnvironmentContext;
+
+#[derive(Debug)]
+pub struct PrintConfigContext {
+    /// The configuration, merged from each of the layers
+    pub config: Config,
+
+    /// The layers of configuration, from lowest to highest precedence
+    pub layers: Vec<ConfigLayer>,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
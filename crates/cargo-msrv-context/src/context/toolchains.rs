@@ -0,0 +1,17 @@
+use crate::context::EnvironmentContext;
+
+#[derive(Debug)]
+pub struct ToolchainsContext {
+    /// The action to perform on the toolchains installed by cargo-msrv
+    pub action: ToolchainsAction,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+/// An action to perform on the toolchains which were installed by cargo-msrv.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ToolchainsAction {
+    /// Uninstall the recorded toolchains
+    Prune,
+}
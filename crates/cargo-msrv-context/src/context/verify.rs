@@ -1,6 +1,7 @@
 use crate::context::error::{Error, TResult};
 use crate::context::{
-    CheckCommandContext, EnvironmentContext, RustReleasesContext, ToolchainContext,
+    CheckCommandContext, EnvironmentContext, RustReleasesContext, TargetDirCleanup,
+    ToolchainContext,
 };
 use camino::Utf8PathBuf;
 use cargo_metadata::MetadataCommand;
@@ -10,12 +11,27 @@ use std::convert::TryFrom;
 
 #[derive(Debug)]
 pub struct VerifyContext {
-    /// The resolved Rust version, to check against for toolchain compatibility.
-    pub rust_version: RustVersion,
+    /// The resolved Rust version(s), to check against for toolchain compatibility.
+    pub rust_version: VerifyRustVersion,
 
     /// Ignore the lockfile for the MSRV verification
     pub ignore_lockfile: bool,
 
+    /// Check against a lockfile which was resolved for the toolchain
+    pub resolve_msrv_lockfile: bool,
+
+    /// Run the check in a copy of the crate outside of the working tree
+    pub isolated: bool,
+
+    /// What happens to the target directory of the checked toolchain, once the check is done
+    pub target_dir_cleanup: TargetDirCleanup,
+
+    /// Uninstall the toolchain if it was installed for the check, once the check is done
+    pub cleanup_toolchains: bool,
+
+    /// Use, and store, cached outcomes of compatibility checks
+    pub cache: bool,
+
     /// Don't print the result of compatibility check
     pub no_check_feedback: bool,
 
@@ -32,6 +48,44 @@ pub struct VerifyContext {
     pub environment: EnvironmentContext,
 }
 
+/// The Rust version(s) which will be verified.
+#[derive(Clone, Debug)]
+pub enum VerifyRustVersion {
+    /// The Rust version of the crate at the root of the environment.
+    Crate(RustVersion),
+    /// The Rust version of each selected workspace package, verified per package.
+    Workspace(Vec<PackageRustVersion>),
+}
+
+/// The Rust version of a single workspace package.
+#[derive(Clone, Debug)]
+pub struct PackageRustVersion {
+    pub name: String,
+    /// The Rust version of the package, or `None` if the package has no `rust-version`, in which
+    /// case it is skipped, rather than verified.
+    pub rust_version: Option<RustVersion>,
+}
+
+impl PackageRustVersion {
+    /// Resolve the Rust version of the given package, unless one was given as argument,
+    /// in which case that version is used for each package.
+    pub fn resolve(package: &cargo_metadata::Package, arg: Option<&BareVersion>) -> TResult<Self> {
+        let rust_version = match arg {
+            Some(v) => Some(RustVersion::from_arg(v.clone())),
+            None => match RustVersion::try_from_package(package) {
+                Ok(rust_version) => Some(rust_version),
+                Err(Error::NoMSRVKeyInCargoToml(_)) => None,
+                Err(err) => return Err(err),
+            },
+        };
+
+        Ok(Self {
+            name: package.name.to_string(),
+            rust_version,
+        })
+    }
+}
+
 /// A combination of a bare (two- or three component) Rust version and the source which was used to
 /// locate this version.
 #[derive(Clone, Debug)]
@@ -63,6 +117,19 @@ impl RustVersion {
             })
     }
 
+    /// Like [`RustVersion::try_from_environment`], but for a single workspace package.
+    pub fn try_from_package(package: &cargo_metadata::Package) -> TResult<Self> {
+        let manifest_path = &package.manifest_path;
+
+        CargoManifest::try_from(package)?
+            .minimum_rust_version()
+            .ok_or_else(|| Error::NoMSRVKeyInCargoToml(manifest_path.clone()))
+            .map(|v| RustVersion {
+                rust_version: v.clone(),
+                source: RustVersionSource::Manifest(manifest_path.clone()),
+            })
+    }
+
     /// Get the bare (two- or three component) version specifying the Rust version.
     pub fn version(&self) -> &BareVersion {
         &self.rust_version
@@ -1,4 +1,6 @@
 use crate::context::error::{Error, IoError, IoErrorSource, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::env::consts::EXE_SUFFIX;
 use std::ffi::OsStr;
 use std::process::{Command, Stdio};
 
@@ -39,3 +41,78 @@ fn rustup_show() -> TResult<String> {
 
     Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
+
+/// The host triple of the toolchains unpacked in the given directory, as reported by `rustc -vV`
+/// of the first toolchain in it, i.e. `<dir>/<version>/bin/rustc`. Used instead of the default
+/// host triple of rustup, since these toolchains aren't run with rustup.
+pub fn toolchain_dir_host_triple(dir: &Utf8Path) -> TResult<String> {
+    let rustc =
+        first_rustc_in(dir)?.ok_or_else(|| Error::NoToolchainInDirectory(dir.to_path_buf()))?;
+
+    let output = Command::new(&rustc)
+        .arg("-vV")
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|error| IoError {
+            error,
+            source: IoErrorSource::SpawnProcess(rustc.as_os_str().to_owned()),
+        })?;
+
+    parse_host_triple(&String::from_utf8_lossy(&output.stdout))
+        .ok_or(Error::DefaultHostTripleNotFound)
+}
+
+/// The `rustc` of the first toolchain in the directory, by name, if any.
+fn first_rustc_in(dir: &Utf8Path) -> TResult<Option<Utf8PathBuf>> {
+    let entries = dir.read_dir_utf8().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadDir(dir.to_path_buf()),
+    })?;
+
+    let mut rustcs = entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            entry
+                .path()
+                .join("bin")
+                .join(format!("rustc{}", EXE_SUFFIX))
+        })
+        .filter(|rustc| rustc.is_file())
+        .collect::<Vec<_>>();
+    rustcs.sort();
+
+    Ok(rustcs.into_iter().next())
+}
+
+/// The host triple in the output of `rustc -vV`, e.g. `host: x86_64-unknown-linux-gnu`.
+fn parse_host_triple(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("host:"))
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_host_triple() {
+        let stdout = "rustc 1.70.0 (90c541806 2023-05-31)\n\
+            binary: rustc\n\
+            commit-hash: 90c541806f23a127002de5b4038be731ba1458ca\n\
+            host: x86_64-unknown-linux-gnu\n\
+            release: 1.70.0\n";
+
+        assert_eq!(
+            parse_host_triple(stdout),
+            Some("x86_64-unknown-linux-gnu".to_string())
+        );
+    }
+
+    #[test]
+    fn no_host_triple() {
+        assert_eq!(parse_host_triple("rustc 1.70.0\n"), None);
+    }
+}
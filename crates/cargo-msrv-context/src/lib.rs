@@ -1,12 +1,15 @@
 #![deny(clippy::all)]
 #![allow(clippy::uninlined_format_args)]
 
+pub mod config;
 pub mod context;
 pub mod default_target;
 pub mod types;
 
 pub use context::{
-    CheckCommandContext, Context, EnvironmentContext, FindContext, ListContext,
-    RustReleasesContext, SearchMethod, SelectedPackage, SetContext, ShowContext, ToolchainContext,
-    TracingOptions, VerifyContext, WorkspacePackages,
+    CacheContext, CheckCommandContext, Context, DoctorContext, EnvironmentContext,
+    FeatureSetsContext, FindContext, ListContext, PackageSelection, PrintConfigContext,
+    RustReleasesContext, SearchMethod, SelectedPackage, SetContext, ShowContext, TargetDirCleanup,
+    ToolchainContext, ToolchainSource, ToolchainsContext, TracingOptions, VerifyContext,
+    WorkspacePackages,
 };
@@ -1,14 +1,33 @@
+use camino::Utf8PathBuf;
 use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize)]
+const FILE_PREFIX: &str = "file:";
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ReleaseSource {
     #[default]
     RustChangelog,
     #[cfg(feature = "rust-releases-dist-source")]
     RustDist,
+    /// The toolchains which are installed locally, as listed by `rustup toolchain list`
+    RustupInstalled,
+    /// A user supplied file, which lists the available releases
+    File(Utf8PathBuf),
+}
+
+impl ReleaseSource {
+    /// Whether the network must be accessed to obtain the release index from this source.
+    pub fn requires_network(&self) -> bool {
+        match self {
+            Self::RustChangelog => true,
+            #[cfg(feature = "rust-releases-dist-source")]
+            Self::RustDist => true,
+            Self::RustupInstalled | Self::File(_) => false,
+        }
+    }
 }
 
 impl FromStr for ReleaseSource {
@@ -19,16 +38,6 @@ impl FromStr for ReleaseSource {
     }
 }
 
-impl From<ReleaseSource> for &'static str {
-    fn from(value: ReleaseSource) -> Self {
-        match value {
-            ReleaseSource::RustChangelog => "rust-changelog",
-            #[cfg(feature = "rust-releases-dist-source")]
-            ReleaseSource::RustDist => "rust-dist",
-        }
-    }
-}
-
 impl TryFrom<&str> for ReleaseSource {
     type Error = ParseReleaseSourceError;
 
@@ -37,7 +46,11 @@ impl TryFrom<&str> for ReleaseSource {
             "rust-changelog" => Ok(Self::RustChangelog),
             #[cfg(feature = "rust-releases-dist-source")]
             "rust-dist" => Ok(Self::RustDist),
-            s => Err(ParseReleaseSourceError(s.to_string())),
+            "rustup-installed" => Ok(Self::RustupInstalled),
+            s => match s.strip_prefix(FILE_PREFIX) {
+                Some(path) if !path.is_empty() => Ok(Self::File(Utf8PathBuf::from(path))),
+                _ => Err(ParseReleaseSourceError(s.to_string())),
+            },
         }
     }
 }
@@ -48,6 +61,8 @@ impl fmt::Display for ReleaseSource {
             Self::RustChangelog => write!(f, "rust-changelog"),
             #[cfg(feature = "rust-releases-dist-source")]
             Self::RustDist => write!(f, "rust-dist"),
+            Self::RustupInstalled => write!(f, "rustup-installed"),
+            Self::File(path) => write!(f, "{}{}", FILE_PREFIX, path),
         }
     }
 }
@@ -55,3 +70,29 @@ impl fmt::Display for ReleaseSource {
 #[derive(Debug, thiserror::Error)]
 #[error("Unable to parse rust-releases source from '{0}'")]
 pub struct ParseReleaseSourceError(pub String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        rust_changelog = { "rust-changelog", ReleaseSource::RustChangelog },
+        rustup_installed = { "rustup-installed", ReleaseSource::RustupInstalled },
+        file = { "file:releases.txt", ReleaseSource::File(Utf8PathBuf::from("releases.txt")) },
+        file_absolute = { "file:/mirror/channel-rust-stable.toml", ReleaseSource::File(Utf8PathBuf::from("/mirror/channel-rust-stable.toml")) },
+    )]
+    fn roundtrip(input: &str, expected: ReleaseSource) {
+        let source = ReleaseSource::from_str(input).unwrap();
+
+        assert_eq!(source, expected);
+        assert_eq!(source.to_string(), input);
+    }
+
+    #[yare::parameterized(
+        unknown = { "rust-unknown" },
+        file_without_path = { "file:" },
+    )]
+    fn invalid(input: &str) {
+        assert!(ReleaseSource::from_str(input).is_err());
+    }
+}
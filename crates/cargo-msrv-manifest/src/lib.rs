@@ -1,9 +1,10 @@
 #![deny(clippy::all)]
 #![allow(clippy::uninlined_format_args)]
 
-use cargo_metadata::{Metadata, semver};
+use cargo_metadata::{Metadata, Package, semver};
 use cargo_msrv_types::BareVersion;
 use std::convert::TryFrom;
+use std::fmt;
 use toml_edit::{DocumentMut, TomlError};
 
 use cargo_msrv_types::bare_version;
@@ -72,6 +73,19 @@ impl TryFrom<Metadata> for CargoManifest {
     }
 }
 
+impl TryFrom<&Package> for CargoManifest {
+    type Error = ManifestParseError;
+
+    /// Like `TryFrom<Metadata>`, but for a single (workspace) package, instead of the root package.
+    fn try_from(package: &Package) -> Result<Self, Self::Error> {
+        let minimum_rust_version = package_msrv(package)?.map(|(version, _origin)| version);
+
+        Ok(Self {
+            minimum_rust_version,
+        })
+    }
+}
+
 /// Parse the minimum supported Rust version (MSRV) from `Cargo.toml` metadata.
 fn find_minimum_rust_version(
     metadata: &Metadata,
@@ -104,9 +118,78 @@ fn find_minimum_rust_version(
     }
 }
 
+/// Where the minimum supported Rust version (MSRV) of a package was specified.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+pub enum MsrvOrigin {
+    /// The `package.rust-version` field, as supported by Cargo since Rust 1.56.0
+    #[serde(rename = "package.rust-version")]
+    PackageRustVersion,
+    /// The `package.metadata.msrv` field, as supported by `cargo-msrv` prior to Rust 1.56.0
+    #[serde(rename = "package.metadata.msrv")]
+    PackageMetadataMsrv,
+    /// The `workspace.package.rust-version` field, inherited via `rust-version.workspace = true`
+    #[serde(rename = "workspace.package.rust-version")]
+    WorkspacePackageRustVersion,
+}
+
+impl fmt::Display for MsrvOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PackageRustVersion => write!(f, "package.rust-version"),
+            Self::PackageMetadataMsrv => write!(f, "package.metadata.msrv"),
+            Self::WorkspacePackageRustVersion => write!(f, "workspace.package.rust-version"),
+        }
+    }
+}
+
+/// Find the minimum supported Rust version (MSRV) of a package, and where it was specified.
+///
+/// Cargo resolves inherited fields, so to find out whether the `rust-version` was inherited from
+/// the workspace, the manifest of the package is read. If it can't be read, the MSRV is assumed to
+/// not be inherited.
+pub fn package_msrv(
+    package: &Package,
+) -> Result<Option<(BareVersion, MsrvOrigin)>, ManifestParseError> {
+    if let Some(version) = package.rust_version.as_ref() {
+        let inherited = std::fs::read_to_string(&package.manifest_path)
+            .ok()
+            .and_then(|contents| CargoManifestParser.parse::<DocumentMut>(&contents).ok())
+            .is_some_and(|document| inherits_rust_version(&document));
+
+        let origin = if inherited {
+            MsrvOrigin::WorkspacePackageRustVersion
+        } else {
+            MsrvOrigin::PackageRustVersion
+        };
+
+        return Ok(Some((BareVersion::from(version), origin)));
+    }
+
+    package
+        .metadata
+        .as_object()
+        .and_then(|metadata| metadata.get("msrv"))
+        .and_then(|msrv| msrv.as_str())
+        .map(|msrv| {
+            BareVersion::try_from(msrv).map(|version| (version, MsrvOrigin::PackageMetadataMsrv))
+        })
+        .transpose()
+        .map_err(ManifestParseError::from)
+}
+
+/// Whether the `[package]` of a Cargo manifest specifies `rust-version.workspace = true`.
+pub fn inherits_rust_version(document: &DocumentMut) -> bool {
+    document
+        .get("package")
+        .and_then(|package| package.get("rust-version"))
+        .and_then(|rust_version| rust_version.get("workspace"))
+        .and_then(toml_edit::Item::as_bool)
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod minimal_version_tests {
-    use crate::{BareVersion, CargoManifest};
+    use crate::{BareVersion, CargoManifest, MsrvOrigin, inherits_rust_version, package_msrv};
     use cargo_metadata::Metadata;
     use std::convert::TryFrom;
 
@@ -232,6 +315,51 @@ mod minimal_version_tests {
 
         assert!(manifest.is_err());
     }
+
+    #[yare::parameterized(
+        none = { None, None, None },
+        rust_version = { Some("1.56.0"), None, Some((BareVersion::ThreeComponents(1, 56, 0), MsrvOrigin::PackageRustVersion)) },
+        metadata_msrv = { None, Some(r#"{"msrv": "1.51"}"#), Some((BareVersion::TwoComponents(1, 51), MsrvOrigin::PackageMetadataMsrv)) },
+        rust_version_has_precedence = { Some("1.56.0"), Some(r#"{"msrv": "1.51"}"#), Some((BareVersion::ThreeComponents(1, 56, 0), MsrvOrigin::PackageRustVersion)) },
+    )]
+    fn package_msrv_with_origin(
+        rust_version: Option<&str>,
+        metadata: Option<&str>,
+        expected: Option<(BareVersion, MsrvOrigin)>,
+    ) {
+        let metadata = metadata_json(rust_version, metadata);
+        let metadata: Metadata = serde_json::from_str(&metadata).unwrap();
+
+        let actual = package_msrv(&metadata.packages[0]).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn manifest_from_package() {
+        let metadata = metadata_json(None, Some(r#"{"msrv": "1.51"}"#));
+        let metadata: Metadata = serde_json::from_str(&metadata).unwrap();
+
+        let manifest = CargoManifest::try_from(&metadata.packages[0]).unwrap();
+
+        assert_eq!(
+            manifest.minimum_rust_version(),
+            Some(&BareVersion::TwoComponents(1, 51))
+        );
+    }
+
+    #[yare::parameterized(
+        dotted = { "[package]\nrust-version.workspace = true\n", true },
+        inline = { "[package]\nrust-version = { workspace = true }\n", true },
+        explicitly_not = { "[package]\nrust-version = { workspace = false }\n", false },
+        version = { "[package]\nrust-version = \"1.56\"\n", false },
+        workspace_package = { "[workspace.package]\nrust-version = \"1.56\"\n", false },
+    )]
+    fn inherits_rust_version_from_workspace(manifest: &str, expected: bool) {
+        let document = manifest.parse::<toml_edit::DocumentMut>().unwrap();
+
+        assert_eq!(inherits_rust_version(&document), expected);
+    }
 }
 
 #[cfg(test)]
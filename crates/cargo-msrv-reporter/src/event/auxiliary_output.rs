@@ -26,12 +26,17 @@ impl From<AuxiliaryOutput> for Event {
 #[serde(tag = "type")]
 pub enum Destination {
     File { path: Utf8PathBuf },
+    Directory { path: Utf8PathBuf },
 }
 
 impl Destination {
     pub fn file(path: Utf8PathBuf) -> Self {
         Self::File { path }
     }
+
+    pub fn directory(path: Utf8PathBuf) -> Self {
+        Self::Directory { path }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
@@ -40,6 +45,7 @@ impl Destination {
 pub enum Item {
     Msrv { kind: MsrvKind },
     ToolchainFile { kind: ToolchainFileKind },
+    TargetDir { kind: TargetDirKind },
 }
 
 impl Item {
@@ -50,6 +56,10 @@ impl Item {
     pub fn toolchain_file(kind: ToolchainFileKind) -> Self {
         Self::ToolchainFile { kind }
     }
+
+    pub fn target_dir(kind: TargetDirKind) -> Self {
+        Self::TargetDir { kind }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
@@ -69,6 +79,14 @@ pub enum ToolchainFileKind {
     Toml,
 }
 
+/// What happened to the Cargo target directory of a checked toolchain, once the run was done.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetDirKind {
+    Kept,
+    Deleted,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,6 +100,8 @@ mod tests {
         rust_version_msrv = { Item::msrv(MsrvKind::RustVersion) },
         metadata_fallback_msrv = { Item::msrv(MsrvKind::MetadataFallback) },
         toolchain_file_toml = { Item::toolchain_file(ToolchainFileKind::Toml) },
+        target_dir_kept = { Item::target_dir(TargetDirKind::Kept) },
+        target_dir_deleted = { Item::target_dir(TargetDirKind::Deleted) },
     )]
     fn reported_action(item: Item) {
         let reporter = TestReporterWrapper::default();
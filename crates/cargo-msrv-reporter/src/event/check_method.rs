@@ -33,6 +33,10 @@ pub enum Method {
         args: Vec<String>,
         path: Utf8PathBuf,
     },
+    DirectRun {
+        args: Vec<String>,
+        path: Utf8PathBuf,
+    },
     #[cfg(test)]
     TestRunner,
 }
@@ -47,6 +51,17 @@ impl Method {
             path: path.as_ref().to_path_buf(),
         }
     }
+
+    /// A program of the toolchain, run directly, instead of through rustup.
+    pub fn direct_run(
+        args: impl IntoIterator<Item = impl AsRef<str>>,
+        path: impl AsRef<Utf8Path>,
+    ) -> Self {
+        Self::DirectRun {
+            args: args.into_iter().map(|s| s.as_ref().to_string()).collect(),
+            path: path.as_ref().to_path_buf(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -59,6 +74,7 @@ mod tests {
 
     #[yare::parameterized(
         rustup_run_with_path = { Method::rustup_run(["hello"], Utf8Path::new("haha")) },
+        direct_run_with_path = { Method::direct_run(["hello"], Utf8Path::new("haha")) },
         test_runner = { Method::TestRunner },
     )]
     fn reported_event(method: Method) {
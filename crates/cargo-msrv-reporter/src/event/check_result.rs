@@ -1,6 +1,7 @@
 use crate::Event;
 use crate::event::Message;
 use crate::event::shared::compatibility::Compatibility;
+use crate::event::shared::diagnosis::Diagnosis;
 use cargo_msrv_types::Toolchain;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
@@ -8,21 +9,36 @@ use cargo_msrv_types::Toolchain;
 pub struct CheckResult {
     #[serde(flatten)]
     pub compatibility: Compatibility,
+    /// Why the toolchain is incompatible, if the cause could be classified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnosis: Option<Diagnosis>,
 }
 
 impl CheckResult {
     pub fn compatible(toolchain: impl Into<Toolchain>) -> Self {
         Self {
             compatibility: Compatibility::compatible(toolchain),
+            diagnosis: None,
         }
     }
 
     pub fn incompatible(toolchain: impl Into<Toolchain>, error: Option<String>) -> Self {
         Self {
             compatibility: Compatibility::incompatible(toolchain, error),
+            diagnosis: None,
         }
     }
 
+    /// Attach the classified cause of an incompatibility.
+    pub fn with_diagnosis(mut self, diagnosis: Option<Diagnosis>) -> Self {
+        self.diagnosis = diagnosis;
+        self
+    }
+
+    pub fn diagnosis(&self) -> Option<&Diagnosis> {
+        self.diagnosis.as_ref()
+    }
+
     pub fn toolchain(&self) -> &Toolchain {
         self.compatibility.toolchain()
     }
@@ -81,4 +97,26 @@ mod tests {
             vec![Event::unscoped(Message::CheckResult(event)),]
         );
     }
+
+    #[test]
+    fn reported_diagnosed_incompatible_toolchain() {
+        use crate::event::{Diagnosis, IncompatibilityCause};
+
+        let reporter = TestReporterWrapper::default();
+        let event = CheckResult::incompatible(
+            Toolchain::new(semver::Version::new(1, 2, 3), "test_target", &[]),
+            None,
+        )
+        .with_diagnosis(Some(
+            Diagnosis::new(IncompatibilityCause::UnstableFeature, "unstable")
+                .with_location("src/lib.rs", Some(3)),
+        ));
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::CheckResult(event)),]
+        );
+    }
 }
@@ -0,0 +1,82 @@
+use crate::{Event, Message};
+use cargo_msrv_types::BareVersion;
+
+/// Suggests dependency downgrades which would bring the dependency tree within a target MSRV.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DowngradeSuggestions {
+    target_msrv: BareVersion,
+    /// The dependencies with a `rust-version` greater than the target MSRV.
+    dependencies: Vec<DowngradeSuggestion>,
+}
+
+impl DowngradeSuggestions {
+    pub fn new(target_msrv: BareVersion, dependencies: Vec<DowngradeSuggestion>) -> Self {
+        Self {
+            target_msrv,
+            dependencies,
+        }
+    }
+
+    pub fn target_msrv(&self) -> &BareVersion {
+        &self.target_msrv
+    }
+
+    pub fn dependencies(&self) -> &[DowngradeSuggestion] {
+        &self.dependencies
+    }
+}
+
+/// A dependency which raises the MSRV above the target, and the version to downgrade it to, if
+/// any version within the target MSRV was found.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DowngradeSuggestion {
+    pub dependency: String,
+    pub version: semver::Version,
+    pub rust_version: semver::Version,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_version: Option<semver::Version>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_rust_version: Option<BareVersion>,
+    /// The command which downgrades the dependency to the suggested version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+impl From<DowngradeSuggestions> for Event {
+    fn from(it: DowngradeSuggestions) -> Self {
+        Message::DowngradeSuggestions(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = DowngradeSuggestions::new(
+            BareVersion::TwoComponents(1, 70),
+            vec![DowngradeSuggestion {
+                dependency: "dep".to_string(),
+                version: semver::Version::new(1, 2, 0),
+                rust_version: semver::Version::new(1, 74, 0),
+                suggested_version: Some(semver::Version::new(1, 1, 0)),
+                suggested_rust_version: Some(BareVersion::TwoComponents(1, 70)),
+                command: Some("cargo update -p dep --precise 1.1.0".to_string()),
+            }],
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::DowngradeSuggestions(event)),]
+        );
+    }
+}
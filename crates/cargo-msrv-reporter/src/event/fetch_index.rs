@@ -7,12 +7,15 @@ use cargo_msrv_context::types::ReleaseSource;
 pub struct FetchIndex {
     #[serde(rename = "source")]
     from_source: ReleaseSource,
+    /// Whether only the locally cached copy of the index is used
+    offline: bool,
 }
 
 impl FetchIndex {
-    pub fn new(source: ReleaseSource) -> Self {
+    pub fn new(source: ReleaseSource, offline: bool) -> Self {
         Self {
             from_source: source,
+            offline,
         }
     }
 }
@@ -33,7 +36,20 @@ mod tests {
     #[test]
     fn reported_rust_changelog_source() {
         let reporter = TestReporterWrapper::default();
-        let event = FetchIndex::new(ReleaseSource::RustChangelog);
+        let event = FetchIndex::new(ReleaseSource::RustChangelog, false);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FetchIndex(event)),]
+        );
+    }
+
+    #[test]
+    fn reported_file_source() {
+        let reporter = TestReporterWrapper::default();
+        let event = FetchIndex::new(ReleaseSource::File("releases.txt".into()), false);
 
         reporter.get().report_event(event.clone()).unwrap();
 
@@ -47,7 +63,7 @@ mod tests {
     #[test]
     fn reported_rust_dist_source() {
         let reporter = TestReporterWrapper::default();
-        let event = FetchIndex::new(ReleaseSource::RustDist);
+        let event = FetchIndex::new(ReleaseSource::RustDist, true);
 
         reporter.get().report_event(event.clone()).unwrap();
 
@@ -0,0 +1,131 @@
+use crate::{Event, Message};
+
+/// The summary of a `find` run over multiple feature sets of a package.
+///
+/// The MSRV of each feature set is reported separately via a `FindResult`; this event
+/// combines them, and points out which features raise the MSRV.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FindFeatureSetsSummary {
+    feature_sets: Vec<FeatureSetMsrv>,
+    msrv: Option<semver::Version>,
+    raised_by: Option<Vec<String>>,
+}
+
+impl FindFeatureSetsSummary {
+    /// Create a new summary from the feature sets and their MSRV, if one could be determined.
+    ///
+    /// The feature sets must be ordered from small to large. The MSRV is the greatest MSRV of all
+    /// feature sets, and can only be determined if each feature set has an MSRV. The MSRV is raised
+    /// by the features of the first feature set with this MSRV.
+    pub fn new(feature_sets: Vec<FeatureSetMsrv>) -> Self {
+        let msrv = feature_sets
+            .iter()
+            .map(|set| set.msrv.as_ref())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|versions| versions.into_iter().max().cloned());
+
+        let raised_by = msrv.as_ref().and_then(|msrv| {
+            feature_sets
+                .iter()
+                .find(|set| set.msrv.as_ref() == Some(msrv))
+                .map(|set| set.features.clone())
+        });
+
+        Self {
+            feature_sets,
+            msrv,
+            raised_by,
+        }
+    }
+
+    pub fn feature_sets(&self) -> &[FeatureSetMsrv] {
+        &self.feature_sets
+    }
+
+    pub fn msrv(&self) -> Option<&semver::Version> {
+        self.msrv.as_ref()
+    }
+
+    /// The smallest feature set which has the greatest MSRV, if any.
+    ///
+    /// If this is the empty feature set, none of the features raise the MSRV.
+    pub fn raised_by(&self) -> Option<&[String]> {
+        self.raised_by.as_deref()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FeatureSetMsrv {
+    pub features: Vec<String>,
+    pub msrv: Option<semver::Version>,
+}
+
+impl FeatureSetMsrv {
+    pub fn new(features: Vec<String>, msrv: Option<semver::Version>) -> Self {
+        Self { features, msrv }
+    }
+}
+
+impl From<FindFeatureSetsSummary> for Event {
+    fn from(it: FindFeatureSetsSummary) -> Self {
+        Message::FindFeatureSetsSummary(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    fn set(features: &[&str], minor: Option<u64>) -> FeatureSetMsrv {
+        FeatureSetMsrv::new(
+            features.iter().map(ToString::to_string).collect(),
+            minor.map(|minor| semver::Version::new(1, minor, 0)),
+        )
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = FindFeatureSetsSummary::new(vec![set(&[], Some(56)), set(&["a"], Some(60))]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FindFeatureSetsSummary(event)),]
+        );
+    }
+
+    #[test]
+    fn msrv_is_raised_by_smallest_feature_set_with_greatest_msrv() {
+        let event = FindFeatureSetsSummary::new(vec![
+            set(&[], Some(56)),
+            set(&["a"], Some(56)),
+            set(&["b"], Some(60)),
+            set(&["a", "b"], Some(60)),
+        ]);
+
+        assert_eq!(event.msrv(), Some(&semver::Version::new(1, 60, 0)));
+        assert_eq!(event.raised_by(), Some(["b".to_string()].as_slice()));
+    }
+
+    #[test]
+    fn msrv_not_raised_by_any_feature() {
+        let event = FindFeatureSetsSummary::new(vec![set(&[], Some(56)), set(&["a"], Some(56))]);
+
+        assert_eq!(event.raised_by(), Some([].as_slice()));
+    }
+
+    #[test]
+    fn msrv_undetermined_if_any_feature_set_undetermined() {
+        let event = FindFeatureSetsSummary::new(vec![set(&[], Some(56)), set(&["a"], None)]);
+
+        assert!(event.msrv().is_none());
+        assert!(event.raised_by().is_none());
+    }
+}
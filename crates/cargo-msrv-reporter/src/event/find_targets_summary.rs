@@ -0,0 +1,134 @@
+use crate::{Event, Message};
+
+/// The summary of a `find` run over multiple targets.
+///
+/// The MSRV of each target is reported separately via a `FindResult`; this event
+/// combines them into a matrix, and points out which targets force a higher MSRV.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FindTargetsSummary {
+    targets: Vec<TargetMsrv>,
+    msrv: Option<semver::Version>,
+    raised_by: Vec<String>,
+}
+
+impl FindTargetsSummary {
+    /// Create a new summary from the targets and their MSRV, if one could be determined.
+    ///
+    /// The MSRV is the greatest MSRV of all targets, and can only be determined if each target
+    /// has an MSRV. The MSRV is raised by the targets with this MSRV, unless each target has the
+    /// same MSRV.
+    pub fn new(targets: Vec<TargetMsrv>) -> Self {
+        let versions = targets
+            .iter()
+            .map(|target| target.msrv.as_ref())
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+
+        let msrv = versions.iter().max().cloned().cloned();
+        let least = versions.iter().min().cloned().cloned();
+
+        let raised_by = match (&msrv, &least) {
+            (Some(msrv), Some(least)) if msrv > least => targets
+                .iter()
+                .filter(|target| target.msrv.as_ref() == Some(msrv))
+                .map(|target| target.target.clone())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            targets,
+            msrv,
+            raised_by,
+        }
+    }
+
+    pub fn targets(&self) -> &[TargetMsrv] {
+        &self.targets
+    }
+
+    pub fn msrv(&self) -> Option<&semver::Version> {
+        self.msrv.as_ref()
+    }
+
+    /// The targets which force a higher MSRV than the other targets.
+    pub fn raised_by(&self) -> &[String] {
+        &self.raised_by
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TargetMsrv {
+    pub target: String,
+    pub msrv: Option<semver::Version>,
+}
+
+impl TargetMsrv {
+    pub fn new(target: impl Into<String>, msrv: Option<semver::Version>) -> Self {
+        Self {
+            target: target.into(),
+            msrv,
+        }
+    }
+}
+
+impl From<FindTargetsSummary> for Event {
+    fn from(it: FindTargetsSummary) -> Self {
+        Message::FindTargetsSummary(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    fn target(name: &str, minor: Option<u64>) -> TargetMsrv {
+        TargetMsrv::new(name, minor.map(|minor| semver::Version::new(1, minor, 0)))
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = FindTargetsSummary::new(vec![target("x", Some(56)), target("y", Some(60))]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FindTargetsSummary(event)),]
+        );
+    }
+
+    #[test]
+    fn msrv_is_raised_by_targets_with_greatest_msrv() {
+        let event = FindTargetsSummary::new(vec![
+            target("x", Some(56)),
+            target("y", Some(60)),
+            target("z", Some(60)),
+        ]);
+
+        assert_eq!(event.msrv(), Some(&semver::Version::new(1, 60, 0)));
+        assert_eq!(event.raised_by(), ["y", "z"]);
+    }
+
+    #[test]
+    fn msrv_not_raised_if_equal_for_each_target() {
+        let event = FindTargetsSummary::new(vec![target("x", Some(56)), target("y", Some(56))]);
+
+        assert_eq!(event.msrv(), Some(&semver::Version::new(1, 56, 0)));
+        assert!(event.raised_by().is_empty());
+    }
+
+    #[test]
+    fn msrv_undetermined_if_any_target_undetermined() {
+        let event = FindTargetsSummary::new(vec![target("x", Some(56)), target("y", None)]);
+
+        assert!(event.msrv().is_none());
+        assert!(event.raised_by().is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{Event, Message};
+
+/// The summary of a `find` run over multiple workspace packages.
+///
+/// The MSRV of each package is reported separately via a `FindResult`; this event
+/// combines them, together with the MSRV of the workspace as a whole.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FindWorkspaceSummary {
+    packages: Vec<PackageMsrv>,
+    workspace_msrv: Option<semver::Version>,
+}
+
+impl FindWorkspaceSummary {
+    /// Create a new summary from the packages and their MSRV, if one could be determined.
+    ///
+    /// The workspace MSRV is the greatest MSRV of all packages, and can only be determined
+    /// if each package has an MSRV.
+    pub fn new(packages: Vec<PackageMsrv>) -> Self {
+        let workspace_msrv = packages
+            .iter()
+            .map(|pkg| pkg.msrv.as_ref())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|versions| versions.into_iter().max().cloned());
+
+        Self {
+            packages,
+            workspace_msrv,
+        }
+    }
+
+    pub fn packages(&self) -> &[PackageMsrv] {
+        &self.packages
+    }
+
+    pub fn workspace_msrv(&self) -> Option<&semver::Version> {
+        self.workspace_msrv.as_ref()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PackageMsrv {
+    pub name: String,
+    pub msrv: Option<semver::Version>,
+}
+
+impl PackageMsrv {
+    pub fn new(name: impl Into<String>, msrv: Option<semver::Version>) -> Self {
+        Self {
+            name: name.into(),
+            msrv,
+        }
+    }
+}
+
+impl From<FindWorkspaceSummary> for Event {
+    fn from(it: FindWorkspaceSummary) -> Self {
+        Message::FindWorkspaceSummary(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = FindWorkspaceSummary::new(vec![
+            PackageMsrv::new("a", Some(semver::Version::new(1, 56, 1))),
+            PackageMsrv::new("b", Some(semver::Version::new(1, 58, 1))),
+        ]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FindWorkspaceSummary(event)),]
+        );
+    }
+
+    #[test]
+    fn workspace_msrv_is_greatest_package_msrv() {
+        let event = FindWorkspaceSummary::new(vec![
+            PackageMsrv::new("a", Some(semver::Version::new(1, 56, 1))),
+            PackageMsrv::new("b", Some(semver::Version::new(1, 58, 1))),
+        ]);
+
+        assert_eq!(
+            event.workspace_msrv(),
+            Some(&semver::Version::new(1, 58, 1))
+        );
+    }
+
+    #[test]
+    fn workspace_msrv_undetermined_if_any_package_undetermined() {
+        let event = FindWorkspaceSummary::new(vec![
+            PackageMsrv::new("a", Some(semver::Version::new(1, 56, 1))),
+            PackageMsrv::new("b", None),
+        ]);
+
+        assert!(event.workspace_msrv().is_none());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{Event, Message};
+
+/// Names the dependency which limits the MSRV, i.e. which caused the Rust release preceding the
+/// found MSRV to be incompatible.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LimitingDependency {
+    dependency: String,
+    version: semver::Version,
+    /// The names of the packages from the root crate up to and including the dependency.
+    path: Vec<String>,
+    reason: LimitingReason,
+    /// The `rust-version` declared by the dependency, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rust_version: Option<String>,
+    /// The most recent incompatible Rust release, which precedes the found MSRV.
+    incompatible_version: semver::Version,
+
+    /// The workspace package for which the MSRV was searched, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<String>,
+}
+
+impl LimitingDependency {
+    pub fn new(
+        dependency: impl Into<String>,
+        version: semver::Version,
+        path: Vec<String>,
+        reason: LimitingReason,
+        incompatible_version: semver::Version,
+    ) -> Self {
+        Self {
+            dependency: dependency.into(),
+            version,
+            path,
+            reason,
+            rust_version: None,
+            incompatible_version,
+            package: None,
+        }
+    }
+
+    pub fn with_rust_version(mut self, rust_version: Option<String>) -> Self {
+        self.rust_version = rust_version;
+        self
+    }
+
+    /// Attribute the dependency to a single package of a workspace.
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    pub fn dependency(&self) -> &str {
+        &self.dependency
+    }
+
+    pub fn version(&self) -> &semver::Version {
+        &self.version
+    }
+
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    pub fn reason(&self) -> LimitingReason {
+        self.reason
+    }
+
+    pub fn rust_version(&self) -> Option<&str> {
+        self.rust_version.as_deref()
+    }
+
+    pub fn incompatible_version(&self) -> &semver::Version {
+        &self.incompatible_version
+    }
+
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+}
+
+/// Why a dependency limits the MSRV.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitingReason {
+    /// The dependency declares a `rust-version` greater than the incompatible Rust release.
+    RustVersion,
+    /// The dependency fails to build with the incompatible Rust release.
+    BuildFailure,
+}
+
+impl From<LimitingDependency> for Event {
+    fn from(it: LimitingDependency) -> Self {
+        Message::LimitingDependency(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = LimitingDependency::new(
+            "dep",
+            semver::Version::new(1, 2, 3),
+            vec!["root".to_string(), "dep".to_string()],
+            LimitingReason::RustVersion,
+            semver::Version::new(1, 69, 0),
+        )
+        .with_rust_version(Some("1.70".to_string()));
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::LimitingDependency(event)),]
+        );
+    }
+}
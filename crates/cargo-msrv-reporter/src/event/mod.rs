@@ -9,26 +9,46 @@ pub use scope::TestScopeGenerator;
 
 // events
 pub use auxiliary_output::{
-    AuxiliaryOutput, Destination, Item as AuxiliaryOutputItem, MsrvKind, ToolchainFileKind,
+    AuxiliaryOutput, Destination, Item as AuxiliaryOutputItem, MsrvKind, TargetDirKind,
+    ToolchainFileKind,
 };
 pub use check_method::{CheckMethod, Method};
 pub use check_result::CheckResult;
 pub use check_toolchain::CheckToolchain;
+pub use downgrade_suggestions::{DowngradeSuggestion, DowngradeSuggestions};
 pub use fetch_index::FetchIndex;
+pub use find_feature_sets_summary::{FeatureSetMsrv, FindFeatureSetsSummary};
+pub use find_targets_summary::{FindTargetsSummary, TargetMsrv};
+pub use find_workspace_summary::{FindWorkspaceSummary, PackageMsrv};
+pub use limiting_dependency::{LimitingDependency, LimitingReason};
 pub use meta::Meta;
+pub use non_monotonic_compatibility::NonMonotonicCompatibility;
 pub use progress::Progress;
 pub use search_method::FindMsrv;
 pub use selected_packages::SelectedPackages;
 pub use setup_toolchain::SetupToolchain;
+pub use stale_release_index::StaleReleaseIndex;
 pub use subcommand_init::SubcommandInit;
 pub use subcommand_result::SubcommandResult;
 pub use termination::TerminateWithFailure;
 pub use unable_to_confirm_valid_release_version::UnableToConfirmValidReleaseVersion;
+pub use uninstall_toolchain::UninstallToolchain;
+pub use verify_workspace_summary::{PackageVerifyStatus, VerifyStatus, VerifyWorkspaceSummary};
+
+// shared
+pub use shared::diagnosis::{Diagnosis, IncompatibilityCause};
 
 // types
 pub use types::{
-    find_result::FindResult, list_result::ListResult, set_result::SetResult,
-    show_result::ShowResult, verify_result::VerifyResult,
+    cache_result::CacheResult,
+    config_result::ConfigResult,
+    doctor_result::{DoctorCheck, DoctorResult, DoctorStatus},
+    find_result::FindResult,
+    list_result::ListResult,
+    set_result::SetResult,
+    show_result::{ShowPackage, ShowResult},
+    toolchains_result::ToolchainsResult,
+    verify_result::VerifyResult,
 };
 
 // internals defining an event
@@ -45,16 +65,25 @@ mod auxiliary_output;
 mod check_method;
 mod check_result;
 mod check_toolchain;
+mod downgrade_suggestions;
 mod fetch_index;
+mod find_feature_sets_summary;
+mod find_targets_summary;
+mod find_workspace_summary;
+mod limiting_dependency;
 mod meta;
+mod non_monotonic_compatibility;
 mod progress;
 mod search_method;
 mod selected_packages;
 mod setup_toolchain;
+mod stale_release_index;
 mod subcommand_init;
 mod subcommand_result;
 mod termination;
 mod unable_to_confirm_valid_release_version;
+mod uninstall_toolchain;
+mod verify_workspace_summary;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -130,6 +159,7 @@ pub enum Message {
 
     // get rust-releases index
     FetchIndex(FetchIndex), // todo!
+    StaleReleaseIndex(StaleReleaseIndex),
     UnableToConfirmValidReleaseVersion(UnableToConfirmValidReleaseVersion),
 
     // todo: ReducedSearchSpace
@@ -138,6 +168,7 @@ pub enum Message {
     // runner, setup of toolchain, method, result
     CheckToolchain(CheckToolchain),
     SetupToolchain(SetupToolchain),
+    UninstallToolchain(UninstallToolchain),
     CheckMethod(CheckMethod),
     CheckResult(CheckResult),
 
@@ -147,6 +178,17 @@ pub enum Message {
     // progression events for command: find
     FindMsrv(FindMsrv),
     Progress(Progress),
+    NonMonotonicCompatibility(NonMonotonicCompatibility),
+    LimitingDependency(LimitingDependency),
+    FindWorkspaceSummary(FindWorkspaceSummary),
+    FindFeatureSetsSummary(FindFeatureSetsSummary),
+    FindTargetsSummary(FindTargetsSummary),
+
+    // progression events for command: verify
+    VerifyWorkspaceSummary(VerifyWorkspaceSummary),
+
+    // progression events for command: list
+    DowngradeSuggestions(DowngradeSuggestions),
 
     // command init and final result
     SubcommandInit(SubcommandInit),
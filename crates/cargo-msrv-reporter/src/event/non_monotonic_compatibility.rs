@@ -0,0 +1,76 @@
+use crate::{Event, Message};
+
+/// Warns that some Rust releases more recent than the found MSRV are incompatible.
+///
+/// The search methods assume that once a Rust release is compatible, each more recent release
+/// is compatible too. When that's not the case, for example because a toolchain regressed, or a
+/// feature was removed again, the found MSRV may be wrong.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NonMonotonicCompatibility {
+    msrv: semver::Version,
+    /// The incompatible releases which are more recent than the MSRV.
+    holes: Vec<semver::Version>,
+
+    /// The workspace package for which the MSRV was searched, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<String>,
+}
+
+impl NonMonotonicCompatibility {
+    pub fn new(msrv: semver::Version, holes: Vec<semver::Version>) -> Self {
+        Self {
+            msrv,
+            holes,
+            package: None,
+        }
+    }
+
+    /// Attribute the warning to a single package of a workspace.
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    pub fn msrv(&self) -> &semver::Version {
+        &self.msrv
+    }
+
+    pub fn holes(&self) -> &[semver::Version] {
+        &self.holes
+    }
+
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+}
+
+impl From<NonMonotonicCompatibility> for Event {
+    fn from(it: NonMonotonicCompatibility) -> Self {
+        Message::NonMonotonicCompatibility(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = NonMonotonicCompatibility::new(
+            semver::Version::new(1, 56, 0),
+            vec![semver::Version::new(1, 60, 0)],
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::NonMonotonicCompatibility(event)),]
+        );
+    }
+}
@@ -32,6 +32,8 @@ mod tests {
     #[yare::parameterized(
         linear = { Method::Linear },
         bisect = { Method::Bisect },
+        multi_bisect = { Method::MultiBisect },
+        anchored = { Method::Anchored },
     )]
     fn reported_event(method: Method) {
         let reporter = TestReporterWrapper::default();
@@ -0,0 +1,88 @@
+use std::fmt;
+
+/// The classified cause of an incompatibility, and where it originates, if known.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Diagnosis {
+    cause: IncompatibilityCause,
+    /// The diagnostic message which led to the classification.
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+impl Diagnosis {
+    pub fn new(cause: IncompatibilityCause, message: impl Into<String>) -> Self {
+        Self {
+            cause,
+            message: message.into(),
+            file: None,
+            line: None,
+        }
+    }
+
+    /// The file, and optionally the line within that file, which is responsible.
+    pub fn with_location(mut self, file: impl Into<String>, line: Option<usize>) -> Self {
+        self.file = Some(file.into());
+        self.line = line;
+        self
+    }
+
+    pub fn cause(&self) -> IncompatibilityCause {
+        self.cause
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The location as `file:line`, or just `file` if the line is unknown.
+    pub fn location(&self) -> Option<String> {
+        let file = self.file.as_deref()?;
+
+        Some(match self.line {
+            Some(line) => format!("{}:{}", file, line),
+            None => file.to_string(),
+        })
+    }
+}
+
+/// Why a toolchain is incompatible.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncompatibilityCause {
+    /// A language or library feature which is unstable, or not yet stable, on this toolchain (E0658).
+    UnstableFeature,
+    /// A key in a Cargo manifest which is unknown to this version of Cargo.
+    UnknownManifestKey,
+    /// An edition which is too new for this toolchain.
+    UnsupportedEdition,
+    /// A dependency which declares a `rust-version` greater than this toolchain.
+    DependencyRustVersion,
+    /// A lockfile version which is too new for this version of Cargo.
+    LockfileVersion,
+}
+
+impl fmt::Display for IncompatibilityCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::UnstableFeature => "unstable feature",
+            Self::UnknownManifestKey => "unknown manifest key",
+            Self::UnsupportedEdition => "unsupported edition",
+            Self::DependencyRustVersion => "dependency requires a newer Rust version",
+            Self::LockfileVersion => "unsupported lockfile version",
+        };
+
+        f.write_str(description)
+    }
+}
@@ -2,3 +2,6 @@
 
 /// Reports whether a given Rust toolchain is compatible with the given crate.
 pub mod compatibility;
+
+/// Reports why a given Rust toolchain is incompatible with the given crate.
+pub mod diagnosis;
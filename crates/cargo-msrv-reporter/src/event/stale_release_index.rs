@@ -0,0 +1,91 @@
+use crate::{Event, Message};
+use cargo_msrv_context::types::ReleaseSource;
+use std::time::Duration;
+
+/// Warns that the index of Rust releases couldn't be fetched, and that a cached copy, which is
+/// older than it's normally used for, is used instead. Rust releases which were published since
+/// the copy was fetched are not considered.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct StaleReleaseIndex {
+    source: ReleaseSource,
+    /// How long ago the cached copy was fetched, in seconds
+    #[serde(rename = "age_secs", serialize_with = "as_secs")]
+    age: Duration,
+    /// Why the index couldn't be fetched
+    error: String,
+}
+
+impl StaleReleaseIndex {
+    pub fn new(source: ReleaseSource, age: Duration, error: impl Into<String>) -> Self {
+        Self {
+            source,
+            age,
+            error: error.into(),
+        }
+    }
+
+    pub fn source(&self) -> &ReleaseSource {
+        &self.source
+    }
+
+    pub fn age(&self) -> Duration {
+        self.age
+    }
+
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+}
+
+fn as_secs<S: serde::Serializer>(age: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(age.as_secs())
+}
+
+impl From<StaleReleaseIndex> for Event {
+    fn from(it: StaleReleaseIndex) -> Self {
+        Message::StaleReleaseIndex(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = StaleReleaseIndex::new(
+            ReleaseSource::RustChangelog,
+            Duration::from_secs(3 * 24 * 60 * 60),
+            "network unreachable",
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::StaleReleaseIndex(event)),]
+        );
+    }
+
+    #[test]
+    fn serialized_age_in_seconds() {
+        let event = StaleReleaseIndex::new(
+            ReleaseSource::RustChangelog,
+            Duration::from_secs(90_000),
+            "network unreachable",
+        );
+
+        let expected = serde_json::json!({
+            "source": "rust_changelog",
+            "age_secs": 90_000,
+            "error": "network unreachable",
+        });
+
+        assert_eq!(serde_json::to_value(event).unwrap(), expected);
+    }
+}
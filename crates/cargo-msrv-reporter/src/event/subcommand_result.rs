@@ -1,15 +1,22 @@
 use crate::Event;
 use crate::Message;
-use crate::event::{FindResult, ListResult, SetResult, ShowResult, VerifyResult};
+use crate::event::{
+    CacheResult, ConfigResult, DoctorResult, FindResult, ListResult, SetResult, ShowResult,
+    ToolchainsResult, VerifyResult,
+};
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "subcommand_id")]
 pub enum SubcommandResult {
+    Cache(CacheResult),
+    Config(ConfigResult),
+    Doctor(DoctorResult),
     Find(FindResult),
     List(ListResult),
     Set(SetResult),
     Show(ShowResult),
+    Toolchains(ToolchainsResult),
     Verify(VerifyResult),
 }
 
@@ -0,0 +1,88 @@
+use crate::Event;
+use crate::event::Message;
+use crate::event::subcommand_result::SubcommandResult;
+use camino::{Utf8Path, Utf8PathBuf};
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CacheResult {
+    result: ResultDetails,
+}
+
+impl CacheResult {
+    /// The cache located at `path` was cleared, by removing `removed_entries` outcomes.
+    pub fn cleared(path: Utf8PathBuf, removed_entries: usize) -> Self {
+        Self {
+            result: ResultDetails::Clear {
+                path,
+                removed_entries,
+            },
+        }
+    }
+
+    pub fn path(&self) -> &Utf8Path {
+        match &self.result {
+            ResultDetails::Clear { path, .. } => path,
+        }
+    }
+
+    pub fn removed_entries(&self) -> usize {
+        match &self.result {
+            ResultDetails::Clear {
+                removed_entries, ..
+            } => *removed_entries,
+        }
+    }
+}
+
+impl From<CacheResult> for SubcommandResult {
+    fn from(it: CacheResult) -> Self {
+        Self::Cache(it)
+    }
+}
+
+impl From<CacheResult> for Event {
+    fn from(it: CacheResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ResultDetails {
+    Clear {
+        path: Utf8PathBuf,
+        removed_entries: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = CacheResult::cleared(Utf8Path::new("cache").to_path_buf(), 3);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Cache(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::Cache(msg)) = &events[0].message {
+            assert_eq!(msg.path(), Utf8Path::new("cache"));
+            assert_eq!(msg.removed_entries(), 3);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::Event;
+use crate::event::Message;
+use crate::event::subcommand_result::SubcommandResult;
+use cargo_msrv_context::config::{Config, ConfigLayer};
+
+/// The configuration of the project, as printed by `--print-config`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigResult {
+    /// The configuration, merged from each of the layers
+    config: Config,
+    /// The layers of configuration, from lowest to highest precedence
+    layers: Vec<ConfigLayer>,
+}
+
+impl ConfigResult {
+    pub fn new(config: Config, layers: Vec<ConfigLayer>) -> Self {
+        Self { config, layers }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn layers(&self) -> &[ConfigLayer] {
+        &self.layers
+    }
+}
+
+impl From<ConfigResult> for SubcommandResult {
+    fn from(it: ConfigResult) -> Self {
+        Self::Config(it)
+    }
+}
+
+impl From<ConfigResult> for Event {
+    fn from(it: ConfigResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use cargo_msrv_context::config::ConfigSource;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let config = Config {
+            ignore_lockfile: Some(true),
+            ..Config::default()
+        };
+        let layers = vec![ConfigLayer::new(ConfigSource::CommandLine, config.clone())];
+        let event = ConfigResult::new(config, layers);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Config(event)
+            ))]
+        );
+    }
+
+    #[test]
+    fn serialized_config_uses_configuration_keys() {
+        let config = Config {
+            search_method: Some(cargo_msrv_context::SearchMethod::MultiBisect),
+            ignore_lockfile: Some(true),
+            ..Config::default()
+        };
+        let event = ConfigResult::new(config, vec![]);
+
+        assert_eq!(
+            serde_json::to_value(event).unwrap(),
+            serde_json::json!({
+                "config": {
+                    "search-method": "multi-bisect",
+                    "ignore-lockfile": true,
+                },
+                "layers": [],
+            })
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::Event;
+use crate::event::Message;
+use crate::event::subcommand_result::SubcommandResult;
+
+/// The outcome of each check of the environment, run by `cargo msrv doctor`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DoctorResult {
+    result: ResultDetails,
+}
+
+impl DoctorResult {
+    pub fn new(checks: Vec<DoctorCheck>) -> Self {
+        Self {
+            result: ResultDetails {
+                success: checks
+                    .iter()
+                    .all(|check| check.status != DoctorStatus::Fail),
+                checks,
+            },
+        }
+    }
+
+    /// Whether none of the checks failed. Warnings don't count as a failure.
+    pub fn is_success(&self) -> bool {
+        self.result.success
+    }
+
+    pub fn checks(&self) -> &[DoctorCheck] {
+        &self.result.checks
+    }
+}
+
+impl From<DoctorResult> for SubcommandResult {
+    fn from(it: DoctorResult) -> Self {
+        Self::Doctor(it)
+    }
+}
+
+impl From<DoctorResult> for Event {
+    fn from(it: DoctorResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct ResultDetails {
+    success: bool,
+    checks: Vec<DoctorCheck>,
+}
+
+/// The outcome of a single check of the environment.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DoctorCheck {
+    /// A name identifying the check, e.g. `rustup`
+    pub name: &'static str,
+    pub status: DoctorStatus,
+    /// What was found, or what went wrong
+    pub message: String,
+}
+
+impl DoctorCheck {
+    pub fn pass(name: &'static str, message: impl Into<String>) -> Self {
+        Self::new(name, DoctorStatus::Pass, message)
+    }
+
+    pub fn warn(name: &'static str, message: impl Into<String>) -> Self {
+        Self::new(name, DoctorStatus::Warn, message)
+    }
+
+    pub fn fail(name: &'static str, message: impl Into<String>) -> Self {
+        Self::new(name, DoctorStatus::Fail, message)
+    }
+
+    fn new(name: &'static str, status: DoctorStatus, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorStatus {
+    /// Nothing to worry about
+    Pass,
+    /// cargo-msrv can run, but some of its features may not work as expected
+    Warn,
+    /// cargo-msrv is unlikely to run successfully
+    Fail,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = DoctorResult::new(vec![
+            DoctorCheck::pass("rustup", "rustup 1.27.1"),
+            DoctorCheck::warn("lockfile", "no lockfile"),
+        ]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Doctor(event)
+            ))]
+        );
+    }
+
+    #[yare::parameterized(
+        pass = { DoctorStatus::Pass, true },
+        warn = { DoctorStatus::Warn, true },
+        fail = { DoctorStatus::Fail, false },
+    )]
+    fn success_unless_a_check_failed(status: DoctorStatus, success: bool) {
+        let result = DoctorResult::new(vec![
+            DoctorCheck::pass("rustup", "rustup 1.27.1"),
+            DoctorCheck::new("manifest", status, "message"),
+        ]);
+
+        assert_eq!(result.is_success(), success);
+    }
+}
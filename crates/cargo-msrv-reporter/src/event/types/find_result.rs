@@ -17,6 +17,18 @@ pub struct FindResult {
     #[serde(skip)]
     pub search_method: SearchMethod,
 
+    /// The workspace package for which the MSRV was searched, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<String>,
+
+    /// The feature set for which the MSRV was searched, if searching for multiple feature sets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    features: Option<Vec<String>>,
+
+    /// The target for which the MSRV was searched, if searching for multiple targets.
+    #[serde(rename = "target", skip_serializing_if = "Option::is_none")]
+    attributed_target: Option<String>,
+
     result: ResultDetails,
 }
 
@@ -34,6 +46,9 @@ impl FindResult {
             maximum_version: max,
 
             search_method,
+            package: None,
+            features: None,
+            attributed_target: None,
 
             result: ResultDetails::Determined {
                 version,
@@ -54,11 +69,44 @@ impl FindResult {
             maximum_version: max,
 
             search_method,
+            package: None,
+            features: None,
+            attributed_target: None,
 
             result: ResultDetails::Undetermined { success: False },
         }
     }
 
+    /// Attribute the result to a single package of a workspace.
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+
+    /// Attribute the result to a single feature set.
+    pub fn with_features(mut self, features: Vec<String>) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    pub fn features(&self) -> Option<&[String]> {
+        self.features.as_deref()
+    }
+
+    /// Attribute the result to its target, one of multiple targets searched.
+    pub fn with_attributed_target(mut self) -> Self {
+        self.attributed_target = Some(self.target.clone());
+        self
+    }
+
+    pub fn attributed_target(&self) -> Option<&str> {
+        self.attributed_target.as_deref()
+    }
+
     pub fn msrv(&self) -> Option<&semver::Version> {
         if let Self {
             result: ResultDetails::Determined { version, .. },
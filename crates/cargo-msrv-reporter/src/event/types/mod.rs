@@ -1,5 +1,9 @@
+pub mod cache_result;
+pub mod config_result;
+pub mod doctor_result;
 pub mod find_result;
 pub mod list_result;
 pub mod set_result;
 pub mod show_result;
+pub mod toolchains_result;
 pub mod verify_result;
@@ -2,6 +2,7 @@ use crate::Event;
 use crate::event::Message;
 use crate::event::subcommand_result::SubcommandResult;
 use camino::{Utf8Path, Utf8PathBuf};
+use cargo_msrv_manifest::MsrvOrigin;
 use cargo_msrv_types::BareVersion;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
@@ -13,19 +14,42 @@ pub struct ShowResult {
 impl ShowResult {
     pub fn new(version: impl Into<BareVersion>, manifest_path: Utf8PathBuf) -> Self {
         Self {
-            result: ResultDetails {
+            result: ResultDetails::Package {
                 version: version.into(),
                 manifest_path,
             },
         }
     }
 
-    pub fn version(&self) -> &BareVersion {
-        &self.result.version
+    /// The MSRV of each package of a workspace, and where it was specified.
+    pub fn workspace(packages: Vec<ShowPackage>) -> Self {
+        Self {
+            result: ResultDetails::Workspace { packages },
+        }
+    }
+
+    /// The MSRV, if this result was reported for a single package.
+    pub fn version(&self) -> Option<&BareVersion> {
+        match &self.result {
+            ResultDetails::Package { version, .. } => Some(version),
+            ResultDetails::Workspace { .. } => None,
+        }
     }
 
-    pub fn manifest_path(&self) -> &Utf8Path {
-        &self.result.manifest_path
+    /// The manifest path, if this result was reported for a single package.
+    pub fn manifest_path(&self) -> Option<&Utf8Path> {
+        match &self.result {
+            ResultDetails::Package { manifest_path, .. } => Some(manifest_path),
+            ResultDetails::Workspace { .. } => None,
+        }
+    }
+
+    /// The packages, if this result was reported for a workspace.
+    pub fn packages(&self) -> Option<&[ShowPackage]> {
+        match &self.result {
+            ResultDetails::Package { .. } => None,
+            ResultDetails::Workspace { packages } => Some(packages),
+        }
     }
 }
 
@@ -42,9 +66,42 @@ impl From<ShowResult> for Event {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
-struct ResultDetails {
-    version: BareVersion,
-    manifest_path: Utf8PathBuf,
+#[serde(untagged)]
+enum ResultDetails {
+    Package {
+        version: BareVersion,
+        manifest_path: Utf8PathBuf,
+    },
+    Workspace {
+        packages: Vec<ShowPackage>,
+    },
+}
+
+/// The declared MSRV of a single workspace package.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ShowPackage {
+    pub name: String,
+    pub version: Option<BareVersion>,
+    pub origin: Option<MsrvOrigin>,
+    pub manifest_path: Utf8PathBuf,
+}
+
+impl ShowPackage {
+    pub fn new(
+        name: impl Into<String>,
+        msrv: Option<(BareVersion, MsrvOrigin)>,
+        manifest_path: Utf8PathBuf,
+    ) -> Self {
+        let (version, origin) = msrv.unzip();
+
+        Self {
+            name: name.into(),
+            version,
+            origin,
+            manifest_path,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -74,8 +131,50 @@ mod tests {
         );
 
         if let Message::SubcommandResult(SubcommandResult::Show(msg)) = &events[0].message {
-            assert_eq!(msg.version(), &BareVersion::ThreeComponents(1, 2, 3));
-            assert_eq!(&msg.manifest_path(), &Utf8Path::new("lv"));
+            assert_eq!(msg.version(), Some(&BareVersion::ThreeComponents(1, 2, 3)));
+            assert_eq!(msg.manifest_path(), Some(Utf8Path::new("lv")));
+            assert!(msg.packages().is_none());
+        }
+    }
+
+    #[test]
+    fn reported_workspace_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = ShowResult::workspace(vec![
+            ShowPackage::new(
+                "a",
+                Some((
+                    BareVersion::TwoComponents(1, 56),
+                    MsrvOrigin::WorkspacePackageRustVersion,
+                )),
+                Utf8Path::new("a/Cargo.toml").to_path_buf(),
+            ),
+            ShowPackage::new("b", None, Utf8Path::new("b/Cargo.toml").to_path_buf()),
+        ]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Show(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::Show(msg)) = &events[0].message {
+            assert!(msg.version().is_none());
+            assert!(msg.manifest_path().is_none());
+
+            let packages = msg.packages().unwrap();
+            assert_eq!(packages.len(), 2);
+            assert_eq!(
+                packages[0].origin,
+                Some(MsrvOrigin::WorkspacePackageRustVersion)
+            );
+            assert!(packages[1].version.is_none());
         }
     }
 }
@@ -0,0 +1,72 @@
+use crate::Event;
+use crate::event::Message;
+use crate::event::subcommand_result::SubcommandResult;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ToolchainsResult {
+    result: ResultDetails,
+}
+
+impl ToolchainsResult {
+    /// The toolchains which cargo-msrv installed were pruned, by uninstalling `removed`.
+    pub fn pruned(removed: Vec<String>) -> Self {
+        Self {
+            result: ResultDetails::Prune { removed },
+        }
+    }
+
+    pub fn removed(&self) -> &[String] {
+        match &self.result {
+            ResultDetails::Prune { removed } => removed,
+        }
+    }
+}
+
+impl From<ToolchainsResult> for SubcommandResult {
+    fn from(it: ToolchainsResult) -> Self {
+        Self::Toolchains(it)
+    }
+}
+
+impl From<ToolchainsResult> for Event {
+    fn from(it: ToolchainsResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ResultDetails {
+    Prune { removed: Vec<String> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = ToolchainsResult::pruned(vec!["1.70.0-x86_64-unknown-linux-gnu".to_string()]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Toolchains(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::Toolchains(msg)) = &events[0].message {
+            assert_eq!(msg.removed(), ["1.70.0-x86_64-unknown-linux-gnu"]);
+        }
+    }
+}
@@ -7,22 +7,38 @@ use cargo_msrv_types::Toolchain;
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct VerifyResult {
+    /// The workspace package which was verified, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<String>,
+
     pub result: Compatibility,
 }
 
 impl VerifyResult {
     pub fn compatible(toolchain: impl Into<Toolchain>) -> Self {
         Self {
+            package: None,
             result: Compatibility::compatible(toolchain),
         }
     }
 
     pub fn incompatible(toolchain: impl Into<Toolchain>, error: Option<String>) -> Self {
         Self {
+            package: None,
             result: Compatibility::incompatible(toolchain, error),
         }
     }
 
+    /// Attribute the result to a single package of a workspace.
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+
     pub fn toolchain(&self) -> &Toolchain {
         self.result.toolchain()
     }
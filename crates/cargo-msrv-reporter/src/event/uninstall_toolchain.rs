@@ -0,0 +1,47 @@
+use crate::{Event, Message};
+
+/// A toolchain which was installed by cargo-msrv, was uninstalled again.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct UninstallToolchain {
+    /// The name of the toolchain, as listed by `rustup toolchain list`
+    toolchain: String,
+}
+
+impl UninstallToolchain {
+    pub fn new(toolchain: impl Into<String>) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+        }
+    }
+
+    pub fn toolchain(&self) -> &str {
+        &self.toolchain
+    }
+}
+
+impl From<UninstallToolchain> for Event {
+    fn from(it: UninstallToolchain) -> Self {
+        Message::UninstallToolchain(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = UninstallToolchain::new("1.70.0-x86_64-unknown-linux-gnu");
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::UninstallToolchain(event))]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{Event, Message};
+
+/// The summary of a `verify` run over multiple workspace packages.
+///
+/// The result of each verified package is reported separately via a `VerifyResult`; this event
+/// combines them, together with the packages which were skipped because they have no Rust
+/// version.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct VerifyWorkspaceSummary {
+    packages: Vec<PackageVerifyStatus>,
+}
+
+impl VerifyWorkspaceSummary {
+    pub fn new(packages: Vec<PackageVerifyStatus>) -> Self {
+        Self { packages }
+    }
+
+    pub fn packages(&self) -> &[PackageVerifyStatus] {
+        &self.packages
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PackageVerifyStatus {
+    pub name: String,
+    /// The Rust version the package was verified against, if it has one
+    pub rust_version: Option<semver::Version>,
+    pub status: VerifyStatus,
+}
+
+impl PackageVerifyStatus {
+    pub fn new(
+        name: impl Into<String>,
+        rust_version: Option<semver::Version>,
+        status: VerifyStatus,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            rust_version,
+            status,
+        }
+    }
+
+    /// A package without a Rust version, which was skipped.
+    pub fn unset(name: impl Into<String>) -> Self {
+        Self::new(name, None, VerifyStatus::Unset)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyStatus {
+    Compatible,
+    Incompatible,
+    /// The package has no Rust version, so it wasn't verified
+    Unset,
+}
+
+impl From<VerifyWorkspaceSummary> for Event {
+    fn from(it: VerifyWorkspaceSummary) -> Self {
+        Message::VerifyWorkspaceSummary(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestReporterWrapper;
+    use crate::event::Message;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = VerifyWorkspaceSummary::new(vec![
+            PackageVerifyStatus::new(
+                "a",
+                Some(semver::Version::new(1, 56, 1)),
+                VerifyStatus::Compatible,
+            ),
+            PackageVerifyStatus::unset("b"),
+        ]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::VerifyWorkspaceSummary(event)),]
+        );
+    }
+}
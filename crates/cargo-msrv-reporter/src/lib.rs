@@ -44,6 +44,7 @@ pub trait Reporter:
         Err = storyteller::EventReporterError<Event>,
         DisconnectToken = DisconnectToken,
     > + SupplyScopeGenerator
+    + Sync
 {
     /// Perform a (fallible) action within the scope of the `f` closure, and report the start and
     /// end of this action.
@@ -81,6 +82,7 @@ impl<R> Reporter for R where
             Err = storyteller::EventReporterError<Event>,
             DisconnectToken = DisconnectToken,
         > + SupplyScopeGenerator
+        + Sync
 {
 }
 
@@ -1,5 +1,8 @@
 use crate::event::{
-    CheckResult, CheckToolchain, FindResult, Message, Meta, SubcommandInit, SubcommandResult,
+    CheckResult, CheckToolchain, Diagnosis, DoctorStatus, DowngradeSuggestions,
+    FindFeatureSetsSummary, FindResult, FindTargetsSummary, FindWorkspaceSummary,
+    LimitingDependency, LimitingReason, Message, Meta, NonMonotonicCompatibility, ShowResult,
+    StaleReleaseIndex, SubcommandInit, SubcommandResult, VerifyStatus, VerifyWorkspaceSummary,
 };
 use crate::{Event, table_settings};
 use owo_colors::OwoColorize;
@@ -11,6 +14,8 @@ use storyteller::EventHandler;
 pub struct HumanProgressHandler {
     pb: indicatif::ProgressBar,
     sequence_number: AtomicU32,
+    // compatibility checks may run concurrently
+    running_checks: AtomicU32,
 }
 
 impl Default for HumanProgressHandler {
@@ -20,6 +25,7 @@ impl Default for HumanProgressHandler {
         Self {
             pb: mp,
             sequence_number: AtomicU32::new(1),
+            running_checks: AtomicU32::new(0),
         }
     }
 }
@@ -27,12 +33,23 @@ impl Default for HumanProgressHandler {
 impl HumanProgressHandler {
     fn start_runner_progress(&self, version: &semver::Version) {
         self.sequence_number.fetch_add(1, Ordering::SeqCst);
+        self.running_checks.fetch_add(1, Ordering::SeqCst);
         self.pb.reset();
         self.pb.set_message(format!("Rust {}", version));
     }
 
     fn finish_runner_progress(&self) {
-        self.pb.finish_and_clear();
+        let previously_running = self
+            .running_checks
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                Some(n.saturating_sub(1))
+            })
+            .unwrap_or_default();
+
+        // Keep spinning while other checks are still running
+        if previously_running <= 1 {
+            self.pb.finish_and_clear();
+        }
     }
 
     fn styled_progress_bar() -> indicatif::ProgressBar {
@@ -66,6 +83,9 @@ impl EventHandler for HumanProgressHandler {
                 self.pb.reset(); // We'll reset here to ensure the steady tick call below works
                 self.pb.enable_steady_tick(Duration::from_millis(150));
             }
+            Message::StaleReleaseIndex(it) => {
+                self.println(Status::warn(it.description()));
+            }
             Message::UnableToConfirmValidReleaseVersion(_) => {
                 let message = Status::info("Unable to verify if provided version is an existing Rust release version");
                 self.println(message);
@@ -78,19 +98,51 @@ impl EventHandler for HumanProgressHandler {
                 self.finish_runner_progress();
             }
             // Message::Compatibility(CheckResult {  compatibility_report: CompatibilityReport::Compatible, toolchain, .. }) => {
-            Message::CheckResult(CheckResult {  compatibility }) if compatibility.is_compatible() => {
-                let message = Status::ok("Is compatible");
+            Message::CheckResult(CheckResult {  compatibility, .. }) if compatibility.is_compatible() => {
+                let message = Status::ok(format_args!("Rust {} is compatible", compatibility.toolchain().version()));
                 self.println(message);
             }
-            Message::CheckResult(CheckResult { compatibility }) if !compatibility.is_compatible() => {
-                let message = Status::fail("Is incompatible");
+            Message::CheckResult(CheckResult { compatibility, diagnosis }) if !compatibility.is_compatible() => {
+                let message = Status::fail(format_args!("Rust {} is incompatible", compatibility.toolchain().version()));
                 self.println(message);
 
+                if let Some(diagnosis) = diagnosis {
+                    self.println(Status::with_lead("Cause".bright_yellow(), diagnosis.summary()));
+                }
+
                 if let Some(error_report) = compatibility.error() {
                     self.println(message_box(error_report));
                 }
             }
+            Message::UninstallToolchain(it) => {
+                let message = Status::with_lead(
+                    "Removed".bright_green(),
+                    format_args!("Toolchain '{}'", it.toolchain()),
+                );
+                self.println(message);
+            }
+            Message::NonMonotonicCompatibility(it) => {
+                self.println(Status::warn(it.description()));
+            }
+            Message::LimitingDependency(it) => {
+                self.println(Status::info(it.description()));
+            }
             Message::SubcommandResult(result) => self.handle_subcommand_result(result),
+            Message::FindWorkspaceSummary(summary) => {
+                self.println(format!("\n{}\n", summary.summary()));
+            }
+            Message::FindFeatureSetsSummary(summary) => {
+                self.println(format!("\n{}\n", summary.summary()));
+            }
+            Message::FindTargetsSummary(summary) => {
+                self.println(format!("\n{}\n", summary.summary()));
+            }
+            Message::VerifyWorkspaceSummary(summary) => {
+                self.println(format!("\n{}\n", summary.summary()));
+            }
+            Message::DowngradeSuggestions(suggestions) => {
+                self.println(format!("\n{}\n", suggestions.summary()));
+            }
             Message::TerminateWithFailure(termination) if termination.should_highlight() => {
                 self.println(format!("\n\n{}", termination.as_message().red()));
             }
@@ -105,6 +157,43 @@ impl EventHandler for HumanProgressHandler {
 impl HumanProgressHandler {
     fn handle_subcommand_result(&self, result: &SubcommandResult) {
         match result {
+            SubcommandResult::Cache(inner) => {
+                let message = Status::with_lead(
+                    "Cache".bright_green(),
+                    format_args!(
+                        "Removed {} cached outcome(s) from '{}'",
+                        inner.removed_entries(),
+                        inner.path()
+                    ),
+                );
+                self.println(message);
+            }
+            SubcommandResult::Config(inner) => {
+                if inner.layers().is_empty() {
+                    self.println(Status::info("No configuration found"));
+                }
+
+                for layer in inner.layers() {
+                    let message = Status::with_lead(
+                        "Config".bright_green(),
+                        format_args!("Read from {}", layer.source),
+                    );
+                    self.println(message);
+                }
+
+                self.println(format!("\n{}", inner.config().to_toml()));
+            }
+            SubcommandResult::Doctor(inner) => {
+                for check in inner.checks() {
+                    let message = format!("{}: {}", check.name, check.message);
+                    let message = match check.status {
+                        DoctorStatus::Pass => Status::ok(message),
+                        DoctorStatus::Warn => Status::warn(message),
+                        DoctorStatus::Fail => Status::fail(message),
+                    };
+                    self.println(message);
+                }
+            }
             SubcommandResult::Find(inner) => {
                 self.println(format!("\n{}\n", inner.summary()));
             }
@@ -118,15 +207,45 @@ impl HumanProgressHandler {
                 );
                 self.println(message);
             }
-            SubcommandResult::Show(inner) => {
+            SubcommandResult::Show(inner) => match inner.version() {
+                Some(version) => {
+                    let message = Status::with_lead(
+                        "Show".bright_green(),
+                        format_args!("MSRV is Rust {}", version),
+                    );
+                    self.println(message);
+                }
+                None => {
+                    self.println(format!("\n{}\n", inner.summary()));
+                }
+            },
+            SubcommandResult::Toolchains(inner) => {
                 let message = Status::with_lead(
-                    "Show".bright_green(),
-                    format_args!("MSRV is Rust {}", inner.version()),
+                    "Prune".bright_green(),
+                    format_args!(
+                        "Uninstalled {} toolchain(s) installed by cargo-msrv",
+                        inner.removed().len()
+                    ),
                 );
                 self.println(message);
             }
-            SubcommandResult::Verify(_inner) => {
-                // tbd.
+            SubcommandResult::Verify(inner) => {
+                // Results for a single crate are already shown by the compatibility check.
+                if let Some(package) = inner.package() {
+                    let version = inner.toolchain().version();
+                    let message = if inner.is_compatible() {
+                        Status::ok(format_args!(
+                            "Package '{}' is compatible with Rust {}",
+                            package, version
+                        ))
+                    } else {
+                        Status::fail(format_args!(
+                            "Package '{}' is not compatible with Rust {}",
+                            package, version
+                        ))
+                    };
+                    self.println(message);
+                }
             }
         }
     }
@@ -145,15 +264,399 @@ impl CheckToolchain {
     }
 }
 
+impl StaleReleaseIndex {
+    fn description(&self) -> String {
+        const HOUR: u64 = 60 * 60;
+        const DAY: u64 = 24 * HOUR;
+
+        let age = match self.age().as_secs() {
+            secs if secs >= DAY => format!("{} day(s)", secs / DAY),
+            secs if secs >= HOUR => format!("{} hour(s)", secs / HOUR),
+            _ => "less than an hour".to_string(),
+        };
+
+        format!(
+            "Unable to fetch the index of Rust releases from {} ({}). Using the cached index, \
+            fetched {} ago, instead, so more recent Rust releases are not considered.",
+            self.source(),
+            self.error(),
+            age
+        )
+    }
+}
+
+impl NonMonotonicCompatibility {
+    fn description(&self) -> String {
+        let holes = self
+            .holes()
+            .iter()
+            .map(|version| format!("Rust {}", version))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let subject = match self.package() {
+            Some(package) => format!("package '{}'", package),
+            None => "this crate".to_string(),
+        };
+
+        format!(
+            "Found incompatible Rust releases more recent than the MSRV (Rust {}) of {}: {}. \
+            Compatibility is not monotonic, so the MSRV may be wrong.",
+            self.msrv(),
+            subject,
+            holes
+        )
+    }
+}
+
+impl LimitingDependency {
+    fn description(&self) -> String {
+        let reason = match (self.reason(), self.rust_version()) {
+            (LimitingReason::RustVersion, Some(rust_version)) => {
+                format!("requires Rust {}", rust_version)
+            }
+            (LimitingReason::RustVersion, None) => {
+                format!(
+                    "requires a Rust version newer than {}",
+                    self.incompatible_version()
+                )
+            }
+            (LimitingReason::BuildFailure, _) => {
+                format!("fails to build with Rust {}", self.incompatible_version())
+            }
+        };
+
+        format!(
+            "The MSRV is limited by dependency '{}' v{}, which {} (via {})",
+            self.dependency(),
+            self.version(),
+            reason,
+            self.path().join(" → ")
+        )
+    }
+}
+
+impl Diagnosis {
+    fn summary(&self) -> String {
+        match self.location() {
+            Some(location) => format!("{} at {}: {}", self.cause(), location, self.message()),
+            None => format!("{}: {}", self.cause(), self.message()),
+        }
+    }
+}
+
 impl FindResult {
     fn summary(&self) -> String {
-        let title = "Result:".bold();
+        let title = match (self.package(), self.features()) {
+            _ if self.attributed_target().is_some() => {
+                format!("Result for target '{}':", self.target)
+                    .bold()
+                    .to_string()
+            }
+            (_, Some([])) => "Result without features:".bold().to_string(),
+            (_, Some(features)) => format!("Result for features '{}':", features.join(", "))
+                .bold()
+                .to_string(),
+            (Some(package), None) => format!("Result for package '{}':", package)
+                .bold()
+                .to_string(),
+            (None, None) => "Result:".bold().to_string(),
+        };
         let table = result_table(self);
 
         format!("{}\n{}", title, table)
     }
 }
 
+impl ShowResult {
+    fn summary(&self) -> String {
+        use tabled::builder::Builder;
+        use tabled::settings::{Alignment, Margin, Style};
+
+        let title = "Workspace MSRV:".bold();
+
+        let mut builder = Builder::default();
+        builder.push_record(["Package", "MSRV", "Origin"]);
+
+        for package in self.packages().unwrap_or_default() {
+            let msrv = package
+                .version
+                .as_ref()
+                .map(|version| version.to_string())
+                .unwrap_or_else(|| format!("{}", "N/A".red()));
+            let origin = package
+                .origin
+                .map(|origin| origin.to_string())
+                .unwrap_or_default();
+
+            builder.push_record([package.name.clone(), msrv, origin]);
+        }
+
+        let table = builder
+            .build()
+            .with(Style::modern_rounded())
+            .with(table_settings!())
+            .with(Alignment::left())
+            .with(Margin::new(2, 0, 0, 1))
+            .to_string();
+
+        format!("{}\n{}", title, table)
+    }
+}
+
+impl VerifyWorkspaceSummary {
+    fn summary(&self) -> String {
+        use tabled::builder::Builder;
+        use tabled::settings::{Alignment, Margin, Style};
+
+        let title = "Workspace result:".bold();
+
+        let mut builder = Builder::default();
+        builder.push_record(["Package", "Rust version", "Status"]);
+
+        for package in self.packages() {
+            let rust_version = package
+                .rust_version
+                .as_ref()
+                .map(|version| version.to_string())
+                .unwrap_or_else(|| "N/A".to_string());
+
+            let status = match package.status {
+                VerifyStatus::Compatible => format!("{}", "Compatible".green()),
+                VerifyStatus::Incompatible => format!("{}", "Incompatible".red()),
+                VerifyStatus::Unset => format!("{}", "Skipped, no rust-version".yellow()),
+            };
+
+            builder.push_record([package.name.clone(), rust_version, status]);
+        }
+
+        let table = builder
+            .build()
+            .with(Style::modern_rounded())
+            .with(table_settings!())
+            .with(Alignment::left())
+            .with(Margin::new(2, 0, 0, 1))
+            .to_string();
+
+        format!("{}\n{}", title, table)
+    }
+}
+
+impl FindWorkspaceSummary {
+    fn summary(&self) -> String {
+        use tabled::builder::Builder;
+        use tabled::settings::{Alignment, Margin, Style};
+
+        let title = "Workspace result:".bold();
+
+        let mut builder = Builder::default();
+        builder.push_record(["Package", "MSRV"]);
+
+        for package in self.packages() {
+            let msrv = package
+                .msrv
+                .as_ref()
+                .map(|version| version.to_string())
+                .unwrap_or_else(|| format!("{}", "N/A".red()));
+
+            builder.push_record([package.name.clone(), msrv]);
+        }
+
+        let table = builder
+            .build()
+            .with(Style::modern_rounded())
+            .with(table_settings!())
+            .with(Alignment::left())
+            .with(Margin::new(2, 0, 0, 1))
+            .to_string();
+
+        let workspace_msrv = self
+            .workspace_msrv()
+            .map(|version| format!("{}", version.green().bold().underline()))
+            .unwrap_or_else(|| format!("{}", "N/A".red()));
+
+        format!(
+            "{}\n{}\n{}",
+            title,
+            table,
+            Status::with_lead("MSRV".bright_green(), workspace_msrv)
+        )
+    }
+}
+
+impl FindFeatureSetsSummary {
+    fn summary(&self) -> String {
+        use tabled::builder::Builder;
+        use tabled::settings::{Alignment, Margin, Style};
+
+        let title = "Feature sets result:".bold();
+
+        let mut builder = Builder::default();
+        builder.push_record(["Features", "MSRV"]);
+
+        for set in self.feature_sets() {
+            let features = if set.features.is_empty() {
+                format!("{}", "(none)".dimmed())
+            } else {
+                set.features.join(", ")
+            };
+            let msrv = set
+                .msrv
+                .as_ref()
+                .map(|version| version.to_string())
+                .unwrap_or_else(|| format!("{}", "N/A".red()));
+
+            builder.push_record([features, msrv]);
+        }
+
+        let table = builder
+            .build()
+            .with(Style::modern_rounded())
+            .with(table_settings!())
+            .with(Alignment::left())
+            .with(Margin::new(2, 0, 0, 1))
+            .to_string();
+
+        let msrv = self
+            .msrv()
+            .map(|version| format!("{}", version.green().bold().underline()))
+            .unwrap_or_else(|| format!("{}", "N/A".red()));
+
+        let raised_by = match self.raised_by() {
+            Some([]) | None => String::new(),
+            Some(features) => format!(
+                "\n{}",
+                Status::with_lead(
+                    "Raised".bright_yellow(),
+                    format_args!(
+                        "The MSRV is raised by the features '{}'",
+                        features.join(", ")
+                    ),
+                )
+            ),
+        };
+
+        format!(
+            "{}\n{}\n{}{}",
+            title,
+            table,
+            Status::with_lead("MSRV".bright_green(), msrv),
+            raised_by
+        )
+    }
+}
+
+impl FindTargetsSummary {
+    fn summary(&self) -> String {
+        use tabled::builder::Builder;
+        use tabled::settings::{Alignment, Margin, Style};
+
+        let title = "Targets result:".bold();
+
+        let mut builder = Builder::default();
+        builder.push_record(["Target", "MSRV"]);
+
+        for target in self.targets() {
+            let msrv = target
+                .msrv
+                .as_ref()
+                .map(|version| version.to_string())
+                .unwrap_or_else(|| format!("{}", "N/A".red()));
+
+            builder.push_record([target.target.clone(), msrv]);
+        }
+
+        let table = builder
+            .build()
+            .with(Style::modern_rounded())
+            .with(table_settings!())
+            .with(Alignment::left())
+            .with(Margin::new(2, 0, 0, 1))
+            .to_string();
+
+        let msrv = self
+            .msrv()
+            .map(|version| format!("{}", version.green().bold().underline()))
+            .unwrap_or_else(|| format!("{}", "N/A".red()));
+
+        let raised_by = match self.raised_by() {
+            [] => String::new(),
+            targets => format!(
+                "\n{}",
+                Status::with_lead(
+                    "Raised".bright_yellow(),
+                    format_args!("The MSRV is raised by the targets '{}'", targets.join(", ")),
+                )
+            ),
+        };
+
+        format!(
+            "{}\n{}\n{}{}",
+            title,
+            table,
+            Status::with_lead("MSRV".bright_green(), msrv),
+            raised_by
+        )
+    }
+}
+
+impl DowngradeSuggestions {
+    fn summary(&self) -> String {
+        use tabled::builder::Builder;
+        use tabled::settings::{Alignment, Margin, Style};
+
+        let title = format!("Suggested downgrades for MSRV {}:", self.target_msrv())
+            .bold()
+            .to_string();
+
+        if self.dependencies().is_empty() {
+            return format!(
+                "{}\n{}",
+                title,
+                Status::ok("All dependencies are within the target MSRV")
+            );
+        }
+
+        let mut builder = Builder::default();
+        builder.push_record(["Dependency", "Version", "MSRV", "Downgrade to", "MSRV"]);
+
+        for dependency in self.dependencies() {
+            let not_found = || format!("{}", "N/A".red());
+
+            builder.push_record([
+                dependency.dependency.clone(),
+                dependency.version.to_string(),
+                dependency.rust_version.to_string(),
+                dependency
+                    .suggested_version
+                    .as_ref()
+                    .map_or_else(not_found, ToString::to_string),
+                dependency
+                    .suggested_rust_version
+                    .as_ref()
+                    .map_or_else(not_found, ToString::to_string),
+            ]);
+        }
+
+        let table = builder
+            .build()
+            .with(Style::modern_rounded())
+            .with(table_settings!())
+            .with(Alignment::left())
+            .with(Margin::new(2, 0, 0, 1))
+            .to_string();
+
+        let commands = self
+            .dependencies()
+            .iter()
+            .filter_map(|dependency| dependency.command.as_deref())
+            .map(|command| format!("  {}", command))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("{}\n{}\n{}", title, table, commands)
+    }
+}
+
 struct Status;
 
 impl Status {
@@ -174,6 +677,11 @@ impl Status {
         status(lead, message)
     }
 
+    fn warn(message: impl Display) -> String {
+        let lead = format!("[{}]", "WARN".bright_yellow());
+        status(lead, message)
+    }
+
     fn info(message: impl Display) -> String {
         let lead = format!("[{}]", "INFO".bright_yellow());
         status(lead, message)
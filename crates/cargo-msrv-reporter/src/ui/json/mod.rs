@@ -7,6 +7,12 @@ use std::sync::MutexGuard;
 use std::sync::{Arc, Mutex};
 use storyteller::EventHandler;
 
+#[cfg(test)]
+mod test_cache;
+
+#[cfg(test)]
+mod test_doctor;
+
 #[cfg(test)]
 mod test_find;
 
@@ -16,6 +22,9 @@ mod test_set;
 #[cfg(test)]
 mod test_show;
 
+#[cfg(test)]
+mod test_toolchains;
+
 #[cfg(test)]
 mod test_verify;
 
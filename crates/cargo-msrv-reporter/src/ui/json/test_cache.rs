@@ -0,0 +1,28 @@
+use crate::JsonHandler;
+use crate::event::CacheResult;
+use camino::Utf8Path;
+use storyteller::EventHandler;
+
+#[test]
+fn handler() {
+    let event = CacheResult::cleared(Utf8Path::new("/hello/world").to_path_buf(), 2);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "subcommand_result",
+        "subcommand_id": "cache",
+        "result": {
+            "action": "clear",
+            "path": "/hello/world",
+            "removed_entries": 2
+        }
+    });
+
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,40 @@
+use crate::JsonHandler;
+use crate::event::{DoctorCheck, DoctorResult};
+use storyteller::EventHandler;
+
+#[test]
+fn handler() {
+    let event = DoctorResult::new(vec![
+        DoctorCheck::pass("rustup", "rustup 1.27.1"),
+        DoctorCheck::warn("lockfile", "no lockfile"),
+    ]);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "subcommand_result",
+        "subcommand_id": "doctor",
+        "result": {
+            "success": true,
+            "checks": [
+                {
+                    "name": "rustup",
+                    "status": "pass",
+                    "message": "rustup 1.27.1"
+                },
+                {
+                    "name": "lockfile",
+                    "status": "warn",
+                    "message": "no lockfile"
+                }
+            ]
+        }
+    });
+
+    assert_eq!(actual, expected);
+}
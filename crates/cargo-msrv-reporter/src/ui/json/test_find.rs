@@ -1,5 +1,5 @@
 use crate::JsonHandler;
-use crate::event::FindResult;
+use crate::event::{FindResult, FindTargetsSummary, FindWorkspaceSummary, PackageMsrv, TargetMsrv};
 use cargo_msrv_context::context::SearchMethod;
 use cargo_msrv_types::BareVersion;
 use storyteller::EventHandler;
@@ -35,6 +35,125 @@ fn compatible_handler() {
     assert_eq!(actual, expected);
 }
 
+#[test]
+fn compatible_handler_for_package() {
+    let version = semver::Version::new(1, 2, 3);
+
+    let event = FindResult::new_msrv(
+        version,
+        "x",
+        BareVersion::TwoComponents(1, 0),
+        BareVersion::TwoComponents(1, 10),
+        SearchMethod::Linear,
+    )
+    .with_package("pkg");
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "subcommand_result",
+        "subcommand_id": "find",
+        "package": "pkg",
+        "result": {
+            "success" : true,
+            "version" : "1.2.3",
+        },
+    });
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn workspace_summary_handler() {
+    let event = FindWorkspaceSummary::new(vec![
+        PackageMsrv::new("a", Some(semver::Version::new(1, 56, 1))),
+        PackageMsrv::new("b", None),
+    ]);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "find_workspace_summary",
+        "packages": [
+            { "name": "a", "msrv": "1.56.1" },
+            { "name": "b", "msrv": null },
+        ],
+        "workspace_msrv": null,
+    });
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn compatible_handler_for_target() {
+    let version = semver::Version::new(1, 2, 3);
+
+    let event = FindResult::new_msrv(
+        version,
+        "x",
+        BareVersion::TwoComponents(1, 0),
+        BareVersion::TwoComponents(1, 10),
+        SearchMethod::Bisect,
+    )
+    .with_attributed_target();
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "subcommand_result",
+        "subcommand_id": "find",
+        "target": "x",
+        "result": {
+            "success" : true,
+            "version" : "1.2.3",
+        },
+    });
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn targets_summary_handler() {
+    let event = FindTargetsSummary::new(vec![
+        TargetMsrv::new("x", Some(semver::Version::new(1, 56, 1))),
+        TargetMsrv::new("y", Some(semver::Version::new(1, 58, 1))),
+    ]);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "find_targets_summary",
+        "targets": [
+            { "target": "x", "msrv": "1.56.1" },
+            { "target": "y", "msrv": "1.58.1" },
+        ],
+        "msrv": "1.58.1",
+        "raised_by": ["y"],
+    });
+
+    assert_eq!(actual, expected);
+}
+
 #[test]
 fn incompatible_handler() {
     let event = FindResult::none(
@@ -1,6 +1,7 @@
 use crate::JsonHandler;
-use crate::event::ShowResult;
+use crate::event::{ShowPackage, ShowResult};
 use camino::Utf8Path;
+use cargo_msrv_manifest::MsrvOrigin;
 use cargo_msrv_types::BareVersion;
 use storyteller::EventHandler;
 
@@ -47,3 +48,76 @@ fn event() {
     let actual = serde_json::to_value(event).unwrap();
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn workspace_event() {
+    let event = ShowResult::workspace(vec![
+        ShowPackage::new(
+            "a",
+            Some((
+                BareVersion::ThreeComponents(1, 64, 0),
+                MsrvOrigin::PackageRustVersion,
+            )),
+            Utf8Path::new("/ws/a/Cargo.toml").to_path_buf(),
+        ),
+        ShowPackage::new(
+            "b",
+            Some((
+                BareVersion::ThreeComponents(1, 66, 0),
+                MsrvOrigin::WorkspacePackageRustVersion,
+            )),
+            Utf8Path::new("/ws/b/Cargo.toml").to_path_buf(),
+        ),
+        ShowPackage::new(
+            "c",
+            Some((
+                BareVersion::TwoComponents(1, 64),
+                MsrvOrigin::PackageMetadataMsrv,
+            )),
+            Utf8Path::new("/ws/c/Cargo.toml").to_path_buf(),
+        ),
+        ShowPackage::new("d", None, Utf8Path::new("/ws/d/Cargo.toml").to_path_buf()),
+    ]);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "subcommand_result",
+        "subcommand_id": "show",
+        "result": {
+            "packages": [
+                {
+                    "name": "a",
+                    "version": "1.64.0",
+                    "origin": "package.rust-version",
+                    "manifest_path": "/ws/a/Cargo.toml"
+                },
+                {
+                    "name": "b",
+                    "version": "1.66.0",
+                    "origin": "workspace.package.rust-version",
+                    "manifest_path": "/ws/b/Cargo.toml"
+                },
+                {
+                    "name": "c",
+                    "version": "1.64",
+                    "origin": "package.metadata.msrv",
+                    "manifest_path": "/ws/c/Cargo.toml"
+                },
+                {
+                    "name": "d",
+                    "version": null,
+                    "origin": null,
+                    "manifest_path": "/ws/d/Cargo.toml"
+                }
+            ]
+        }
+    });
+
+    assert_eq!(actual, expected);
+}
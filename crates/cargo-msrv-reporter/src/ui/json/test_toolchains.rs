@@ -0,0 +1,26 @@
+use crate::JsonHandler;
+use crate::event::ToolchainsResult;
+use storyteller::EventHandler;
+
+#[test]
+fn handler() {
+    let event = ToolchainsResult::pruned(vec!["1.70.0-x86_64-unknown-linux-gnu".to_string()]);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "subcommand_result",
+        "subcommand_id": "toolchains",
+        "result": {
+            "action": "prune",
+            "removed": ["1.70.0-x86_64-unknown-linux-gnu"]
+        }
+    });
+
+    assert_eq!(actual, expected);
+}
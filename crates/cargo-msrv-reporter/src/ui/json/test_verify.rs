@@ -1,5 +1,5 @@
 use crate::JsonHandler;
-use crate::event::VerifyResult;
+use crate::event::{PackageVerifyStatus, VerifyResult, VerifyStatus, VerifyWorkspaceSummary};
 use cargo_msrv_types::Toolchain;
 use storyteller::EventHandler;
 
@@ -162,3 +162,57 @@ fn event_failure_without_message() {
     let actual = serde_json::to_value(event).unwrap();
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn event_package() {
+    let event = VerifyResult::compatible(Toolchain::new(
+        semver::Version::new(1, 2, 3),
+        "my-target",
+        &[],
+    ))
+    .with_package("a");
+
+    let expected = serde_json::json!({
+        "package": "a",
+        "result": {
+            "toolchain": {
+                "target": "my-target",
+                "version": "1.2.3",
+                "components": [],
+            },
+            "is_compatible": true,
+        }
+    });
+
+    let actual = serde_json::to_value(event).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn workspace_summary_handler() {
+    let event = VerifyWorkspaceSummary::new(vec![
+        PackageVerifyStatus::new(
+            "a",
+            Some(semver::Version::new(1, 56, 1)),
+            VerifyStatus::Incompatible,
+        ),
+        PackageVerifyStatus::unset("b"),
+    ]);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let expected = serde_json::json!({
+        "type": "verify_workspace_summary",
+        "packages": [
+            { "name": "a", "rust_version": "1.56.1", "status": "incompatible" },
+            { "name": "b", "rust_version": null, "status": "unset" },
+        ],
+    });
+
+    assert_eq!(actual, expected);
+}
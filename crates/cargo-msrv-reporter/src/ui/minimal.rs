@@ -1,6 +1,6 @@
 use crate::Event;
 use crate::Message;
-use crate::event::SubcommandResult;
+use crate::event::{DoctorStatus, SubcommandResult};
 use crate::io::SendWriter;
 use std::io;
 use std::io::{Stderr, Stdout};
@@ -75,11 +75,30 @@ impl<S: SendWriter, F: SendWriter> EventHandler for MinimalOutputHandler<S, F> {
 
         if let Message::SubcommandResult(result) = event.message() {
             match result {
-                SubcommandResult::Find(inner) => match inner.msrv() {
-                    Some(v) => {
+                SubcommandResult::Cache(inner) => {
+                    success_writeln!("{}", inner.removed_entries())
+                }
+                SubcommandResult::Config(inner) => {
+                    success_writeln!("{}", inner.config().to_toml().trim_end())
+                }
+                SubcommandResult::Doctor(inner) => {
+                    for check in inner.checks() {
+                        match check.status {
+                            DoctorStatus::Pass => success_writeln!("{} pass", check.name),
+                            DoctorStatus::Warn => success_writeln!("{} warn", check.name),
+                            DoctorStatus::Fail => failure_writeln!("{} fail", check.name),
+                        }
+                    }
+                }
+                SubcommandResult::Find(inner) => match (inner.package(), inner.msrv()) {
+                    (Some(package), Some(v)) => {
+                        success_writeln!("{} {}", package, v)
+                    }
+                    (None, Some(v)) => {
                         success_writeln!("{}", v)
                     }
-                    None => failure_writeln!("{}", "none"),
+                    (Some(package), None) => failure_writeln!("{} {}", package, "none"),
+                    (None, None) => failure_writeln!("{}", "none"),
                 },
                 SubcommandResult::List(_inner) => {
                     failure_writeln!("unsupported")
@@ -87,15 +106,28 @@ impl<S: SendWriter, F: SendWriter> EventHandler for MinimalOutputHandler<S, F> {
                 SubcommandResult::Set(inner) => {
                     success_writeln!("{}", inner.version())
                 }
-                SubcommandResult::Show(inner) => {
-                    success_writeln!("{}", inner.version())
-                }
-                SubcommandResult::Verify(inner) if inner.is_compatible() => {
-                    success_writeln!("true")
-                }
-                SubcommandResult::Verify(_inner) /* if !inner.is_compatible() */ => {
-                    failure_writeln!("false")
+                SubcommandResult::Show(inner) => match (inner.version(), inner.packages()) {
+                    (Some(version), _) => success_writeln!("{}", version),
+                    (None, packages) => {
+                        for package in packages.unwrap_or_default() {
+                            match &package.version {
+                                Some(v) => success_writeln!("{} {}", package.name, v),
+                                None => failure_writeln!("{} {}", package.name, "none"),
+                            }
+                        }
+                    }
+                },
+                SubcommandResult::Toolchains(inner) => {
+                    for toolchain in inner.removed() {
+                        success_writeln!("{}", toolchain)
+                    }
                 }
+                SubcommandResult::Verify(inner) => match (inner.package(), inner.is_compatible()) {
+                    (Some(package), true) => success_writeln!("{} true", package),
+                    (None, true) => success_writeln!("true"),
+                    (Some(package), false) => failure_writeln!("{} false", package),
+                    (None, false) => failure_writeln!("false"),
+                },
             }
         }
     }
@@ -103,13 +135,18 @@ impl<S: SendWriter, F: SendWriter> EventHandler for MinimalOutputHandler<S, F> {
 
 #[cfg(test)]
 mod tests {
-    use crate::event::{FindResult, ListResult, Progress, SetResult, ShowResult, VerifyResult};
+    use crate::event::{
+        ConfigResult, DoctorCheck, DoctorResult, FindResult, ListResult, Progress, SetResult,
+        ShowPackage, ShowResult, VerifyResult,
+    };
     use crate::ui::minimal::MinimalOutputHandler;
     use camino::Utf8Path;
     use cargo_metadata::PackageId;
+    use cargo_msrv_context::config::Config;
     use cargo_msrv_context::context::SearchMethod;
     use cargo_msrv_context::types::ListMsrvVariant;
     use cargo_msrv_manifest::DependencyGraph;
+    use cargo_msrv_manifest::MsrvOrigin;
     use cargo_msrv_types::BareVersion;
     use cargo_msrv_types::Toolchain;
     use storyteller::EventHandler;
@@ -142,6 +179,35 @@ mod tests {
         assert_eq!(f.as_ref(), "");
     }
 
+    #[test]
+    fn find_with_result_for_package() {
+        let msrv = semver::Version::new(1, 10, 100);
+        let min_available = BareVersion::ThreeComponents(1, 0, 0);
+        let max_available = BareVersion::ThreeComponents(2, 0, 0);
+
+        let event = FindResult::new_msrv(
+            msrv,
+            "x",
+            min_available,
+            max_available,
+            SearchMethod::Linear,
+        )
+        .with_package("pkg");
+
+        let s = Vec::new();
+        let f = Vec::new();
+        let handler = MinimalOutputHandler::new(s, f);
+        handler.handle(event.into());
+
+        let s = handler.inner_success_writer().clone();
+        let s = String::from_utf8_lossy(&s);
+        assert_eq!(s.as_ref(), "pkg 1.10.100\n");
+
+        let f = handler.inner_failure_writer().clone();
+        let f = String::from_utf8_lossy(&f);
+        assert_eq!(f.as_ref(), "");
+    }
+
     #[test]
     fn find_without_result() {
         let min_available = BareVersion::ThreeComponents(1, 0, 0);
@@ -206,6 +272,47 @@ mod tests {
         assert_eq!(f.as_ref(), "");
     }
 
+    #[test]
+    fn config_output() {
+        let config = Config {
+            min: Some(BareVersion::TwoComponents(1, 60)),
+            ignore_lockfile: Some(true),
+            ..Config::default()
+        };
+        let event = ConfigResult::new(config, vec![]);
+
+        let s = Vec::new();
+        let f = Vec::new();
+        let handler = MinimalOutputHandler::new(s, f);
+        handler.handle(event.into());
+
+        let s = handler.inner_success_writer().clone();
+        let s = String::from_utf8_lossy(&s);
+        assert_eq!(s.as_ref(), "min = \"1.60\"\nignore-lockfile = true\n");
+    }
+
+    #[test]
+    fn doctor_output() {
+        let event = DoctorResult::new(vec![
+            DoctorCheck::pass("rustup", "rustup 1.27.1"),
+            DoctorCheck::warn("lockfile", "no lockfile"),
+            DoctorCheck::fail("manifest", "not found"),
+        ]);
+
+        let s = Vec::new();
+        let f = Vec::new();
+        let handler = MinimalOutputHandler::new(s, f);
+        handler.handle(event.into());
+
+        let s = handler.inner_success_writer().clone();
+        let s = String::from_utf8_lossy(&s);
+        assert_eq!(s.as_ref(), "rustup pass\nlockfile warn\n");
+
+        let f = handler.inner_failure_writer().clone();
+        let f = String::from_utf8_lossy(&f);
+        assert_eq!(f.as_ref(), "manifest fail\n");
+    }
+
     #[test]
     fn show_output() {
         let event = ShowResult::new(
@@ -227,6 +334,56 @@ mod tests {
         assert_eq!(f.as_ref(), "");
     }
 
+    #[test]
+    fn show_workspace_output() {
+        let event = ShowResult::workspace(vec![
+            ShowPackage::new(
+                "a",
+                Some((
+                    BareVersion::TwoComponents(1, 56),
+                    MsrvOrigin::PackageRustVersion,
+                )),
+                Utf8Path::new("/a/Cargo.toml").to_path_buf(),
+            ),
+            ShowPackage::new("b", None, Utf8Path::new("/b/Cargo.toml").to_path_buf()),
+        ]);
+
+        let s = Vec::new();
+        let f = Vec::new();
+        let handler = MinimalOutputHandler::new(s, f);
+        handler.handle(event.into());
+
+        let s = handler.inner_success_writer().clone();
+        let s = String::from_utf8_lossy(&s);
+        assert_eq!(s.as_ref(), "a 1.56\n");
+
+        let f = handler.inner_failure_writer().clone();
+        let f = String::from_utf8_lossy(&f);
+        assert_eq!(f.as_ref(), "b none\n");
+    }
+
+    #[test]
+    fn verify_package() {
+        let event = VerifyResult::incompatible(
+            Toolchain::new(semver::Version::new(1, 2, 3), "test_target", &[]),
+            None,
+        )
+        .with_package("a");
+
+        let s = Vec::new();
+        let f = Vec::new();
+        let handler = MinimalOutputHandler::new(s, f);
+        handler.handle(event.into());
+
+        let s = handler.inner_success_writer().clone();
+        let s = String::from_utf8_lossy(&s);
+        assert_eq!(s.as_ref(), "");
+
+        let f = handler.inner_failure_writer().clone();
+        let f = String::from_utf8_lossy(&f);
+        assert_eq!(f.as_ref(), "a false\n");
+    }
+
     #[test]
     fn verify_true() {
         let event = VerifyResult::compatible(Toolchain::new(
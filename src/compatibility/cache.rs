@@ -0,0 +1,578 @@
+//! A persistent, on-disk cache of the outcomes of compatibility checks.
+//!
+//! An outcome is stored under a key which consists of the toolchain, where the toolchain comes
+//! from (rustup, or the directory given by `--toolchain-dir`), the check command (which includes
+//! the selected features), and a fingerprint of the inputs of the check.
+//! Only these inputs are part of the fingerprint:
+//!
+//! * the Rust source files and the Cargo manifests of the crate,
+//! * the lockfile of the crate, or of the workspace the crate is a member of,
+//! * the Cargo configuration files (`.cargo/config.toml`) which apply to the crate, and
+//! * the environment variables which configure Cargo and rustc, like `RUSTFLAGS` and `CARGO_*`.
+//!
+//! Other inputs, like the files read by a build script or by `include_str!`, and the sources of
+//! path dependencies outside of the crate, are not tracked, so changes to them don't invalidate
+//! a cached outcome. Hence, the cache is only used when the user opts in to it. Unrelated
+//! changes, like an edit to a README, don't invalidate the cache either.
+
+use crate::TResult;
+use crate::context::EnvironmentContext;
+use crate::error::{IoError, IoErrorSource};
+use crate::outcome::Compatibility;
+use crate::rust::Toolchain;
+use camino::{Utf8Path, Utf8PathBuf};
+use std::hash::{Hash, Hasher};
+use std::{fmt, fs, io};
+
+const COMPATIBLE: &str = "compatible";
+const INCOMPATIBLE: &str = "incompatible";
+
+/// Stores the outcome of compatibility checks, one file per key.
+#[derive(Clone, Debug)]
+pub struct CompatibilityCache {
+    dir: Utf8PathBuf,
+}
+
+impl CompatibilityCache {
+    pub fn new(dir: impl Into<Utf8PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache located in the cache directory of the user, if it can be determined.
+    pub fn in_user_cache_dir() -> Option<Self> {
+        dirs::cache_dir()
+            .and_then(|path| Utf8PathBuf::from_path_buf(path).ok())
+            .map(|path| Self::new(path.join("cargo-msrv").join("compatibility")))
+    }
+
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// Get a previously stored outcome.
+    ///
+    /// An entry which can't be read is treated as absent.
+    pub fn get(&self, key: &CacheKey, toolchain: &Toolchain) -> Option<Compatibility> {
+        let contents = fs::read_to_string(self.entry_path(key)).ok()?;
+        let (status, error_message) = contents.split_once('\n').unwrap_or((&contents, ""));
+        // The status of an incompatible outcome may be followed by its diagnosis, as JSON
+        let (status, diagnosis) = status.split_once(' ').unwrap_or((status, ""));
+
+        match status {
+            COMPATIBLE => Some(Compatibility::new_success(toolchain.clone())),
+            INCOMPATIBLE => Some(Compatibility::new_diagnosed_failure(
+                toolchain.clone(),
+                error_message.to_string(),
+                serde_json::from_str(diagnosis).ok(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Store an outcome, replacing the outcome previously stored under the same key.
+    pub fn insert(&self, key: &CacheKey, outcome: &Compatibility) -> TResult<()> {
+        let contents = match outcome {
+            Compatibility::Compatible(_) => format!("{}\n", COMPATIBLE),
+            Compatibility::Incompatible(f) => {
+                let diagnosis = f
+                    .diagnosis
+                    .as_ref()
+                    .and_then(|diagnosis| serde_json::to_string(diagnosis).ok());
+
+                match diagnosis {
+                    Some(diagnosis) => {
+                        format!("{} {}\n{}", INCOMPATIBLE, diagnosis, f.error_message)
+                    }
+                    None => format!("{}\n{}", INCOMPATIBLE, f.error_message),
+                }
+            }
+        };
+
+        fs::create_dir_all(&self.dir).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::CreateDir(self.dir.clone()),
+        })?;
+
+        let path = self.entry_path(key);
+        fs::write(&path, contents).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::WriteFile(path),
+        })?;
+
+        Ok(())
+    }
+
+    /// Remove all stored outcomes, and return how many were removed.
+    pub fn clear(&self) -> TResult<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => {
+                return Err(IoError {
+                    error,
+                    source: IoErrorSource::ReadDir(self.dir.clone()),
+                }
+                .into());
+            }
+        };
+
+        let mut removed = 0;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_file() {
+                fs::remove_file(&path).map_err(|error| IoError {
+                    error,
+                    source: IoErrorSource::RemoveFile(
+                        Utf8PathBuf::from_path_buf(path).unwrap_or_default(),
+                    ),
+                })?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> Utf8PathBuf {
+        self.dir.join(key.to_string())
+    }
+}
+
+/// The key under which the outcome of a compatibility check is stored.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CacheKey(u64);
+
+impl CacheKey {
+    /// The key of the outcome of checking with the `toolchain` from the given `origin`, see
+    /// [`ToolchainProvider::origin`](crate::rust::toolchain_provider::ToolchainProvider::origin).
+    pub fn new(
+        toolchain: &Toolchain,
+        origin: &str,
+        check_cmd: &[String],
+        fingerprint: &SourceFingerprint,
+    ) -> Self {
+        let mut hasher = Fnv1a::default();
+
+        toolchain.version().to_string().hash(&mut hasher);
+        toolchain.target().hash(&mut hasher);
+        toolchain.components().hash(&mut hasher);
+        origin.hash(&mut hasher);
+        check_cmd.hash(&mut hasher);
+        fingerprint.0.hash(&mut hasher);
+
+        Self(hasher.finish())
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// A fingerprint of the inputs of a compatibility check. See the [module](self) documentation
+/// for which inputs are included.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SourceFingerprint(u64);
+
+impl SourceFingerprint {
+    /// Fingerprint the sources found in the root of the environment, its Cargo configuration,
+    /// and the environment variables of the current process.
+    ///
+    /// If `ignore_lockfile` is set, the lockfile is not part of the fingerprint, since it's
+    /// removed during the check.
+    pub fn of(env: &EnvironmentContext, ignore_lockfile: bool) -> io::Result<Self> {
+        let vars = std::env::vars_os().filter_map(|(name, value)| {
+            Some((
+                name.into_string().ok()?,
+                value.to_string_lossy().into_owned(),
+            ))
+        });
+
+        Self::with_vars(env, ignore_lockfile, vars)
+    }
+
+    fn with_vars(
+        env: &EnvironmentContext,
+        ignore_lockfile: bool,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> io::Result<Self> {
+        let root = env.root();
+
+        let mut files = Vec::new();
+        collect_source_files(root, &mut files)?;
+        files.extend(workspace_files(root));
+        files.extend(cargo_config_files(root));
+        files.sort();
+
+        let mut hasher = Fnv1a::default();
+        root.hash(&mut hasher);
+        ignore_lockfile.hash(&mut hasher);
+
+        for file in files {
+            if ignore_lockfile && file.file_name() == Some("Cargo.lock") {
+                continue;
+            }
+
+            file.strip_prefix(root).unwrap_or(&file).hash(&mut hasher);
+            fs::read(&file)?.hash(&mut hasher);
+        }
+
+        let mut vars = vars
+            .into_iter()
+            .filter(|(name, _)| is_build_var(name))
+            .collect::<Vec<_>>();
+        vars.sort();
+        vars.hash(&mut hasher);
+
+        Ok(Self(hasher.finish()))
+    }
+}
+
+/// Whether an environment variable configures Cargo or rustc. The variables of cargo-msrv
+/// itself are excluded, since the options they set are already part of the key, if relevant.
+fn is_build_var(name: &str) -> bool {
+    const RUSTC_VARS: &[&str] = &[
+        "RUSTFLAGS",
+        "RUSTDOCFLAGS",
+        "RUSTC",
+        "RUSTC_WRAPPER",
+        "RUSTC_WORKSPACE_WRAPPER",
+        "RUSTC_BOOTSTRAP",
+    ];
+
+    (name.starts_with("CARGO_") && !name.starts_with("CARGO_MSRV_")) || RUSTC_VARS.contains(&name)
+}
+
+/// The lockfile and manifest of the workspace the crate is a member of, if the workspace root is
+/// a parent directory of the crate, since the lockfile of a member is located there.
+fn workspace_files(root: &Utf8Path) -> Vec<Utf8PathBuf> {
+    root.ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.lock").is_file())
+        .map(|dir| {
+            ["Cargo.lock", "Cargo.toml"]
+                .into_iter()
+                .map(|name| dir.join(name))
+                .filter(|path| path.is_file())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The Cargo configuration files which apply to the crate: those in the crate root and its
+/// parent directories, and the one in the Cargo home directory.
+fn cargo_config_files(root: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))
+        .and_then(|path| Utf8PathBuf::from_path_buf(path).ok());
+
+    root.ancestors()
+        .map(|dir| dir.join(".cargo"))
+        .chain(cargo_home)
+        .flat_map(|dir| [dir.join("config.toml"), dir.join("config")])
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Recursively collect the files which may affect the outcome of a check, skipping the build
+/// output and hidden directories.
+fn collect_source_files(dir: &Utf8Path, files: &mut Vec<Utf8PathBuf>) -> io::Result<()> {
+    for entry in dir.read_dir_utf8()? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+
+        if entry.file_type()?.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                collect_source_files(path, files)?;
+            }
+        } else if matches!(name, "Cargo.toml" | "Cargo.lock") || path.extension() == Some("rs") {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    Ok(())
+}
+
+/// The 64-bit FNV-1a hash. Unlike the `DefaultHasher`, its algorithm does not change between Rust
+/// releases, so cached outcomes remain valid when cargo-msrv is built with a newer compiler.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WorkspacePackages;
+    use assert_fs::prelude::*;
+    use rust_releases::semver;
+
+    fn toolchain() -> Toolchain {
+        Toolchain::new(semver::Version::new(1, 56, 1), "x", &[])
+    }
+
+    fn environment(tmp: &assert_fs::TempDir) -> EnvironmentContext {
+        EnvironmentContext {
+            root_crate_path: Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap(),
+            workspace_packages: WorkspacePackages::default(),
+        }
+    }
+
+    fn create_crate(tmp: &assert_fs::TempDir) {
+        tmp.child("Cargo.toml")
+            .write_str("[package]\nname = \"a\"\nversion = \"0.1.0\"\n")
+            .unwrap();
+        tmp.child("Cargo.lock").write_str("version = 3\n").unwrap();
+        tmp.child("src/lib.rs")
+            .write_str("pub fn a() {}\n")
+            .unwrap();
+        tmp.child("README.md").write_str("# a\n").unwrap();
+    }
+
+    fn fingerprint(tmp: &assert_fs::TempDir, ignore_lockfile: bool) -> SourceFingerprint {
+        SourceFingerprint::with_vars(&environment(tmp), ignore_lockfile, []).unwrap()
+    }
+
+    fn fingerprint_with_vars(tmp: &assert_fs::TempDir, vars: &[(&str, &str)]) -> SourceFingerprint {
+        let vars = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        SourceFingerprint::with_vars(&environment(tmp), false, vars).unwrap()
+    }
+
+    #[test]
+    fn outcomes_roundtrip() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache =
+            CompatibilityCache::new(Utf8PathBuf::from_path_buf(tmp.path().join("cache")).unwrap());
+
+        let compatible = CacheKey(1);
+        let incompatible = CacheKey(2);
+
+        cache
+            .insert(&compatible, &Compatibility::new_success(toolchain()))
+            .unwrap();
+        cache
+            .insert(
+                &incompatible,
+                &Compatibility::new_failure(toolchain(), "error\nmessage".to_string()),
+            )
+            .unwrap();
+
+        assert!(cache.get(&compatible, &toolchain()).unwrap().is_success());
+
+        let Some(Compatibility::Incompatible(outcome)) = cache.get(&incompatible, &toolchain())
+        else {
+            panic!("expected an incompatible outcome");
+        };
+        assert_eq!(outcome.error_message, "error\nmessage");
+
+        assert!(cache.get(&CacheKey(3), &toolchain()).is_none());
+    }
+
+    #[test]
+    fn diagnosis_roundtrip() {
+        use crate::reporter::event::{Diagnosis, IncompatibilityCause};
+
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache =
+            CompatibilityCache::new(Utf8PathBuf::from_path_buf(tmp.path().join("cache")).unwrap());
+        let diagnosis = Diagnosis::new(IncompatibilityCause::UnstableFeature, "unstable")
+            .with_location("src/lib.rs", Some(3));
+
+        cache
+            .insert(
+                &CacheKey(1),
+                &Compatibility::new_diagnosed_failure(
+                    toolchain(),
+                    "error\nmessage".to_string(),
+                    Some(diagnosis.clone()),
+                ),
+            )
+            .unwrap();
+
+        let Some(Compatibility::Incompatible(outcome)) = cache.get(&CacheKey(1), &toolchain())
+        else {
+            panic!("expected an incompatible outcome");
+        };
+        assert_eq!(outcome.error_message, "error\nmessage");
+        assert_eq!(outcome.diagnosis, Some(diagnosis));
+    }
+
+    #[test]
+    fn clear_removes_all_outcomes() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache =
+            CompatibilityCache::new(Utf8PathBuf::from_path_buf(tmp.path().join("cache")).unwrap());
+
+        assert_eq!(cache.clear().unwrap(), 0);
+
+        for key in [CacheKey(1), CacheKey(2)] {
+            cache
+                .insert(&key, &Compatibility::new_success(toolchain()))
+                .unwrap();
+        }
+
+        assert_eq!(cache.clear().unwrap(), 2);
+        assert!(cache.get(&CacheKey(1), &toolchain()).is_none());
+    }
+
+    #[test]
+    fn fingerprint_ignores_unrelated_files() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        create_crate(&tmp);
+
+        let before = fingerprint(&tmp, false);
+
+        tmp.child("README.md")
+            .write_str("# a, but longer\n")
+            .unwrap();
+        tmp.child("target/debug/a.rs").write_str("").unwrap();
+        tmp.child(".git/HEAD").write_str("").unwrap();
+
+        assert_eq!(fingerprint(&tmp, false), before);
+    }
+
+    #[yare::parameterized(
+        source = { "src/lib.rs" },
+        manifest = { "Cargo.toml" },
+        lockfile = { "Cargo.lock" },
+        new_source = { "src/new.rs" },
+        cargo_config = { ".cargo/config.toml" },
+    )]
+    fn fingerprint_changes_with_sources(file: &str) {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        create_crate(&tmp);
+
+        let before = fingerprint(&tmp, false);
+
+        tmp.child(file).write_str("# changed\n").unwrap();
+
+        assert_ne!(fingerprint(&tmp, false), before);
+    }
+
+    #[test]
+    fn fingerprint_changes_with_workspace_lockfile() {
+        let workspace = assert_fs::TempDir::new().unwrap();
+        workspace
+            .child("Cargo.toml")
+            .write_str("[workspace]\nmembers = [\"a\"]\n")
+            .unwrap();
+        workspace
+            .child("Cargo.lock")
+            .write_str("version = 3\n")
+            .unwrap();
+        workspace
+            .child("a/Cargo.toml")
+            .write_str("[package]\nname = \"a\"\nversion = \"0.1.0\"\n")
+            .unwrap();
+
+        let env = EnvironmentContext {
+            root_crate_path: Utf8PathBuf::from_path_buf(workspace.path().join("a")).unwrap(),
+            workspace_packages: WorkspacePackages::default(),
+        };
+        let fingerprint = || SourceFingerprint::with_vars(&env, false, []).unwrap();
+
+        let before = fingerprint();
+
+        workspace
+            .child("Cargo.lock")
+            .write_str("version = 4\n")
+            .unwrap();
+
+        assert_ne!(fingerprint(), before);
+    }
+
+    #[test]
+    fn fingerprint_changes_with_build_vars() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        create_crate(&tmp);
+
+        let before = fingerprint_with_vars(&tmp, &[("HOME", "/home/a")]);
+
+        assert_eq!(
+            fingerprint_with_vars(&tmp, &[("HOME", "/home/b"), ("CARGO_MSRV_LINEAR", "true")]),
+            before
+        );
+        assert_ne!(
+            fingerprint_with_vars(&tmp, &[("RUSTFLAGS", "-Dwarnings")]),
+            before
+        );
+        assert_ne!(
+            fingerprint_with_vars(&tmp, &[("CARGO_BUILD_TARGET", "x")]),
+            before
+        );
+    }
+
+    #[test]
+    fn fingerprint_without_lockfile() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        create_crate(&tmp);
+
+        let before = fingerprint(&tmp, true);
+
+        tmp.child("Cargo.lock").write_str("version = 4\n").unwrap();
+
+        assert_eq!(fingerprint(&tmp, true), before);
+    }
+
+    #[test]
+    fn key_depends_on_check_command() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        create_crate(&tmp);
+
+        let fingerprint = fingerprint(&tmp, false);
+        let check = vec!["cargo".to_string(), "check".to_string()];
+        let check_features = vec![
+            "cargo".to_string(),
+            "check".to_string(),
+            "--features".to_string(),
+            "a".to_string(),
+        ];
+
+        assert_eq!(
+            CacheKey::new(&toolchain(), "rustup", &check, &fingerprint),
+            CacheKey::new(&toolchain(), "rustup", &check, &fingerprint)
+        );
+        assert_ne!(
+            CacheKey::new(&toolchain(), "rustup", &check, &fingerprint),
+            CacheKey::new(&toolchain(), "rustup", &check_features, &fingerprint)
+        );
+    }
+
+    #[test]
+    fn key_depends_on_toolchain_origin() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        create_crate(&tmp);
+
+        let fingerprint = fingerprint(&tmp, false);
+        let check = vec!["cargo".to_string(), "check".to_string()];
+
+        assert_ne!(
+            CacheKey::new(&toolchain(), "rustup", &check, &fingerprint),
+            CacheKey::new(&toolchain(), "directory /toolchains", &check, &fingerprint)
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Classify why a toolchain is incompatible, from the output of a failed check.
+//!
+//! Problems which Cargo runs into before compiling, like a manifest or lockfile it can't
+//! parse, are reported as text on stderr. The diagnostics of the compiler are reported as
+//! JSON messages on stdout, if the check is run with `--message-format=json`.
+
+use crate::reporter::event::{Diagnosis, IncompatibilityCause};
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
+
+/// The Cargo subcommands which accept `--message-format`.
+const SUBCOMMANDS_WITH_MESSAGE_FORMAT: &[&str] = &["build", "check", "clippy", "rustc", "test"];
+
+/// The error code of the compiler for the use of an unstable, or not yet stable, feature.
+const UNSTABLE_FEATURE: &str = "E0658";
+
+/// The check command with `--message-format=json`, if the command is a Cargo subcommand which
+/// supports it, and the message format was not chosen by the user.
+pub fn with_json_message_format(check: &[String]) -> Option<Vec<String>> {
+    let [cargo, subcommand, args @ ..] = check else {
+        return None;
+    };
+
+    let supported =
+        cargo == "cargo" && SUBCOMMANDS_WITH_MESSAGE_FORMAT.contains(&subcommand.as_str());
+    let user_defined = args
+        .iter()
+        .take_while(|arg| arg.as_str() != "--")
+        .any(|arg| arg.starts_with("--message-format"));
+
+    (supported && !user_defined).then(|| {
+        let mut command = vec![cargo.clone(), subcommand.clone()];
+        command.push("--message-format=json".to_string());
+        command.extend(args.iter().cloned());
+        command
+    })
+}
+
+/// The human-readable output of a failed check, with the compiler diagnostics rendered
+/// as they would have been without `--message-format=json`.
+pub fn error_message(stdout: &str, stderr: &str) -> String {
+    let rendered = compiler_diagnostics(stdout)
+        .map(|diagnostic| match diagnostic.rendered {
+            Some(rendered) => rendered,
+            None => render(&diagnostic),
+        })
+        .collect::<String>();
+
+    format!("{}{}", rendered, stderr)
+}
+
+/// Render a diagnostic from its message and primary span, for older compilers, which don't
+/// include the rendered diagnostic in their JSON messages.
+fn render(diagnostic: &Diagnostic) -> String {
+    let code = diagnostic
+        .code
+        .as_ref()
+        .map(|code| format!("[{}]", code.code))
+        .unwrap_or_default();
+    let mut rendered = format!(
+        "{}{}: {}\n",
+        level(diagnostic.level),
+        code,
+        diagnostic.message
+    );
+
+    let span = diagnostic
+        .spans
+        .iter()
+        .find(|span| span.is_primary)
+        .or_else(|| diagnostic.spans.first());
+    if let Some(span) = span {
+        rendered.push_str(&format!(
+            " --> {}:{}:{}\n",
+            span.file_name, span.line_start, span.column_start
+        ));
+    }
+
+    for child in &diagnostic.children {
+        rendered.push_str(&format!(" = {}: {}\n", level(child.level), child.message));
+    }
+
+    rendered
+}
+
+fn level(level: DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Ice => "error: internal compiler error",
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+        DiagnosticLevel::FailureNote => "failure-note",
+        DiagnosticLevel::Help => "help",
+        _ => "note",
+    }
+}
+
+/// Classify the cause of a failed check.
+///
+/// Problems reported by Cargo take precedence, since these prevent compilation altogether.
+pub fn diagnose(stdout: &str, stderr: &str) -> Option<Diagnosis> {
+    diagnose_cargo(stderr)
+        .or_else(|| compiler_diagnostics(stdout).find_map(diagnose_compiler))
+        .or_else(|| diagnose_unused_manifest_key(stderr))
+}
+
+fn compiler_diagnostics(stdout: &str) -> impl Iterator<Item = Diagnostic> + '_ {
+    Message::parse_stream(stdout.as_bytes()).filter_map(|message| match message {
+        Ok(Message::CompilerMessage(message)) => Some(message.message),
+        _ => None,
+    })
+}
+
+fn diagnose_compiler(diagnostic: Diagnostic) -> Option<Diagnosis> {
+    let code = diagnostic.code.as_ref()?;
+
+    if code.code != UNSTABLE_FEATURE {
+        return None;
+    }
+
+    let span = diagnostic
+        .spans
+        .iter()
+        .find(|span| span.is_primary)
+        .or_else(|| diagnostic.spans.first());
+    let diagnosis = Diagnosis::new(IncompatibilityCause::UnstableFeature, &diagnostic.message);
+
+    Some(match span {
+        Some(span) => diagnosis.with_location(&span.file_name, Some(span.line_start)),
+        None => diagnosis,
+    })
+}
+
+fn diagnose_cargo(stderr: &str) -> Option<Diagnosis> {
+    let lines = stderr.lines().map(str::trim).collect::<Vec<_>>();
+
+    let classified = |cause: IncompatibilityCause, line: &str, file: Option<&str>| {
+        let diagnosis = Diagnosis::new(cause, line);
+
+        match file {
+            Some(file) => diagnosis.with_location(file, location_line(&lines, file)),
+            None => diagnosis,
+        }
+    };
+
+    let manifest = quoted_path_after(&lines, "failed to parse manifest at");
+    let lockfile = lines
+        .iter()
+        .find_map(|line| line.split_once("failed to parse lock file at:"))
+        .map(|(_, path)| path.trim());
+
+    if let Some(line) = lines.iter().find(|line| is_lockfile_version(line)) {
+        return Some(classified(
+            IncompatibilityCause::LockfileVersion,
+            line,
+            lockfile,
+        ));
+    }
+
+    if let Some(line) = lines.iter().find(|line| is_dependency_rust_version(line)) {
+        return Some(classified(
+            IncompatibilityCause::DependencyRustVersion,
+            line,
+            None,
+        ));
+    }
+
+    if let Some(line) = lines.iter().find(|line| is_edition(line)) {
+        return Some(classified(
+            IncompatibilityCause::UnsupportedEdition,
+            line,
+            manifest,
+        ));
+    }
+
+    // Any other manifest which can't be parsed, contains a key (or value) which this version
+    // of Cargo doesn't know about
+    if manifest.is_some() {
+        let line = lines
+            .iter()
+            .find(|line| line.contains("unknown field") || line.contains("for key `"))
+            .or_else(|| {
+                lines
+                    .iter()
+                    .find(|line| line.contains("failed to parse manifest"))
+            })?;
+
+        return Some(classified(
+            IncompatibilityCause::UnknownManifestKey,
+            line,
+            manifest,
+        ));
+    }
+
+    None
+}
+
+/// Unknown keys which don't fail the parsing of the manifest are reported as warnings, which
+/// are a likely, but not a certain, cause of the failure.
+fn diagnose_unused_manifest_key(stderr: &str) -> Option<Diagnosis> {
+    stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| line.contains("unused manifest key:"))
+        .map(|line| Diagnosis::new(IncompatibilityCause::UnknownManifestKey, line))
+}
+
+fn is_lockfile_version(line: &str) -> bool {
+    line.contains("lock file version") || line.contains("does not understand this lock file")
+}
+
+fn is_dependency_rust_version(line: &str) -> bool {
+    // The first form is used by Cargo 1.56 up to 1.78, the second by more recent versions
+    line.contains("cannot be built because it requires rustc")
+        || line.contains("is not supported by the following package")
+}
+
+fn is_edition(line: &str) -> bool {
+    (line.contains("feature `edition") && line.contains("is required"))
+        || (line.contains("edition") && line.contains("unknown variant"))
+        || line.contains("is older than the `")
+}
+
+/// The path quoted with backticks, which follows `prefix` in one of the lines.
+fn quoted_path_after<'a>(lines: &[&'a str], prefix: &str) -> Option<&'a str> {
+    lines.iter().find_map(|line| {
+        let (_, rest) = line.split_once(prefix)?;
+        let rest = rest.trim().strip_prefix('`')?;
+        rest.split_once('`').map(|(path, _)| path)
+    })
+}
+
+/// The line in `file` pointed at by a location marker, like `--> Cargo.toml:7:1`.
+fn location_line(lines: &[&str], file: &str) -> Option<usize> {
+    let file_name = camino::Utf8Path::new(file).file_name()?;
+
+    lines.iter().find_map(|line| {
+        let location = line.strip_prefix("-->")?.trim();
+        let mut parts = location.rsplitn(3, ':');
+        let (_column, line, path) = (parts.next()?, parts.next()?, parts.next()?);
+
+        path.ends_with(file_name)
+            .then(|| line.parse().ok())
+            .flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &str) -> Vec<String> {
+        command.split_whitespace().map(String::from).collect()
+    }
+
+    #[yare::parameterized(
+        check = { "cargo check", Some("cargo check --message-format=json") },
+        check_with_args = { "cargo check --features a -- -D warnings", Some("cargo check --message-format=json --features a -- -D warnings") },
+        test = { "cargo test --no-run", Some("cargo test --message-format=json --no-run") },
+        user_defined = { "cargo check --message-format=short", None },
+        unsupported_subcommand = { "cargo fmt --check", None },
+        not_cargo = { "make check", None },
+        too_short = { "cargo", None },
+    )]
+    fn json_message_format(command: &str, expected: Option<&str>) {
+        assert_eq!(with_json_message_format(&args(command)), expected.map(args));
+    }
+
+    const UNSTABLE_LET_ELSE: &str = r#"{"reason":"compiler-artifact","package_id":"path+file:///a#0.1.0","manifest_path":"/a/Cargo.toml","target":{"kind":["lib"],"crate_types":["lib"],"name":"dep","src_path":"/a/src/lib.rs","edition":"2021","doc":true,"doctest":true,"test":true},"profile":{"opt_level":"0","debuginfo":2,"debug_assertions":true,"overflow_checks":true,"test":false},"features":[],"filenames":[],"executable":null,"fresh":true}
+{"reason":"compiler-message","package_id":"path+file:///a#0.1.0","manifest_path":"/a/Cargo.toml","target":{"kind":["lib"],"crate_types":["lib"],"name":"a","src_path":"/a/src/lib.rs","edition":"2021","doc":true,"doctest":true,"test":true},"message":{"rendered":"error[E0658]: `let...else` statements are unstable\n --> src/lib.rs:3:5\n","$message_type":"diagnostic","children":[],"code":{"code":"E0658","explanation":null},"level":"error","message":"`let...else` statements are unstable","spans":[{"byte_end":60,"byte_start":30,"column_end":35,"column_start":5,"expansion":null,"file_name":"src/lib.rs","is_primary":true,"label":null,"line_end":3,"line_start":3,"suggested_replacement":null,"suggestion_applicability":null,"text":[]}]}}
+{"reason":"build-finished","success":false}
+"#;
+
+    const STDERR_COULD_NOT_COMPILE: &str = "error: could not compile `a` due to previous error\n";
+
+    #[test]
+    fn unstable_feature() {
+        let diagnosis = diagnose(UNSTABLE_LET_ELSE, STDERR_COULD_NOT_COMPILE).unwrap();
+
+        assert_eq!(diagnosis.cause(), IncompatibilityCause::UnstableFeature);
+        assert_eq!(diagnosis.message(), "`let...else` statements are unstable");
+        assert_eq!(diagnosis.location().as_deref(), Some("src/lib.rs:3"));
+    }
+
+    #[test]
+    fn error_message_includes_rendered_diagnostics() {
+        let message = error_message(UNSTABLE_LET_ELSE, STDERR_COULD_NOT_COMPILE);
+
+        assert_eq!(
+            message,
+            "error[E0658]: `let...else` statements are unstable\n --> src/lib.rs:3:5\n\
+            error: could not compile `a` due to previous error\n"
+        );
+    }
+
+    // Like `UNSTABLE_LET_ELSE`, but without a rendered message, as reported by older compilers
+    const UNRENDERED_LET_ELSE: &str = r#"{"reason":"compiler-message","package_id":"a 0.1.0 (path+file:///a)","target":{"kind":["lib"],"crate_types":["lib"],"name":"a","src_path":"/a/src/lib.rs","edition":"2018"},"message":{"children":[{"children":[],"code":null,"level":"help","message":"add `#![feature(let_else)]` to the crate attributes to enable","rendered":null,"spans":[]}],"code":{"code":"E0658","explanation":null},"level":"error","message":"`let...else` statements are unstable","spans":[{"byte_end":60,"byte_start":30,"column_end":35,"column_start":5,"expansion":null,"file_name":"src/lib.rs","is_primary":true,"label":null,"line_end":3,"line_start":3,"suggested_replacement":null,"suggestion_applicability":null,"text":[]}]}}
+"#;
+
+    #[test]
+    fn error_message_renders_unrendered_diagnostics() {
+        let message = error_message(UNRENDERED_LET_ELSE, STDERR_COULD_NOT_COMPILE);
+
+        assert_eq!(
+            message,
+            "error[E0658]: `let...else` statements are unstable\n --> src/lib.rs:3:5\n \
+            = help: add `#![feature(let_else)]` to the crate attributes to enable\n\
+            error: could not compile `a` due to previous error\n"
+        );
+    }
+
+    #[test]
+    fn error_message_without_json_diagnostics() {
+        assert_eq!(error_message("", "error: x\n"), "error: x\n");
+    }
+
+    #[yare::parameterized(
+        edition_required = {
+            "error: failed to parse manifest at `/a/Cargo.toml`\n\nCaused by:\n  feature `edition2021` is required\n",
+            IncompatibilityCause::UnsupportedEdition,
+            "feature `edition2021` is required",
+            Some("/a/Cargo.toml"),
+        },
+        edition_unknown_variant = {
+            "error: failed to parse manifest at `/a/Cargo.toml`\n\nCaused by:\n  unknown variant `2018`, expected `2015` for key `package.edition`\n",
+            IncompatibilityCause::UnsupportedEdition,
+            "unknown variant `2018`, expected `2015` for key `package.edition`",
+            Some("/a/Cargo.toml"),
+        },
+        dependency_rust_version = {
+            "error: package `dep v1.2.3` cannot be built because it requires rustc 1.70 or newer, while the currently active rustc version is 1.60.0\n",
+            IncompatibilityCause::DependencyRustVersion,
+            "error: package `dep v1.2.3` cannot be built because it requires rustc 1.70 or newer, while the currently active rustc version is 1.60.0",
+            None,
+        },
+        dependency_rust_version_recent_cargo = {
+            "error: rustc 1.80.0 is not supported by the following package:\n  dep@1.2.3 requires rustc 1.81\n",
+            IncompatibilityCause::DependencyRustVersion,
+            "error: rustc 1.80.0 is not supported by the following package:",
+            None,
+        },
+        lockfile_version = {
+            "error: failed to parse lock file at: /a/Cargo.lock\n\nCaused by:\n  lock file version 4 requires `-Znext-lockfile-bump`\n",
+            IncompatibilityCause::LockfileVersion,
+            "lock file version 4 requires `-Znext-lockfile-bump`",
+            Some("/a/Cargo.lock"),
+        },
+        lockfile_version_not_understood = {
+            "error: failed to parse lock file at: /a/Cargo.lock\n\nCaused by:\n  lock file version `4` was found, but this version of Cargo does not understand this lock file, perhaps Cargo needs to be updated?\n",
+            IncompatibilityCause::LockfileVersion,
+            "lock file version `4` was found, but this version of Cargo does not understand this lock file, perhaps Cargo needs to be updated?",
+            Some("/a/Cargo.lock"),
+        },
+        unknown_manifest_key = {
+            "error: failed to parse manifest at `/a/Cargo.toml`\n\nCaused by:\n  invalid type: map, expected a string\n  for key `package.version`\n",
+            IncompatibilityCause::UnknownManifestKey,
+            "for key `package.version`",
+            Some("/a/Cargo.toml"),
+        },
+        unused_manifest_key = {
+            "warning: unused manifest key: lints\nerror: could not compile `a`\n",
+            IncompatibilityCause::UnknownManifestKey,
+            "warning: unused manifest key: lints",
+            None,
+        },
+    )]
+    fn cargo_problem(stderr: &str, cause: IncompatibilityCause, message: &str, file: Option<&str>) {
+        let diagnosis = diagnose("", stderr).unwrap();
+
+        assert_eq!(diagnosis.cause(), cause);
+        assert_eq!(diagnosis.message(), message);
+        assert_eq!(diagnosis.file(), file);
+    }
+
+    #[test]
+    fn manifest_location() {
+        let stderr = "error: failed to parse manifest at `/a/Cargo.toml`\n\nCaused by:\n  TOML parse error at line 7, column 1\n  --> /a/Cargo.toml:7:1\n  unknown variant `2024`, expected one of `2015`, `2018`, `2021` for key `package.edition`\n";
+        let diagnosis = diagnose("", stderr).unwrap();
+
+        assert_eq!(diagnosis.cause(), IncompatibilityCause::UnsupportedEdition);
+        assert_eq!(diagnosis.location().as_deref(), Some("/a/Cargo.toml:7"));
+    }
+
+    #[test]
+    fn cargo_problem_takes_precedence() {
+        let stderr = "error: package `dep v1.2.3` cannot be built because it requires rustc 1.70 or newer, while the currently active rustc version is 1.60.0\n";
+        let diagnosis = diagnose(UNSTABLE_LET_ELSE, stderr).unwrap();
+
+        assert_eq!(
+            diagnosis.cause(),
+            IncompatibilityCause::DependencyRustVersion
+        );
+    }
+
+    #[test]
+    fn unclassified() {
+        let stderr = "error[E0425]: cannot find value `x` in this scope\n";
+
+        assert!(diagnose("", stderr).is_none());
+    }
+}
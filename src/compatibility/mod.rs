@@ -2,12 +2,20 @@ use crate::external_command::cargo_command::CargoCommand;
 use crate::rust::Toolchain;
 use cargo_msrv_context::{CheckCommandContext, FindContext, ToolchainContext, VerifyContext};
 
-mod rustup_toolchain_check;
+mod cache;
+mod diagnostics;
+mod msrv_lockfile;
+mod scratch;
+mod target_dir;
 #[cfg(test)]
 mod testing;
+mod toolchain_check;
 
 use crate::{Compatibility, TResult};
-pub use rustup_toolchain_check::{RunCommand, RustupToolchainCheck};
+pub use cache::{CacheKey, CompatibilityCache, SourceFingerprint};
+pub use scratch::WorkspaceSnapshots;
+pub use target_dir::ToolchainTargetDirs;
+pub use toolchain_check::{RunCommand, ToolchainCheck};
 
 #[cfg(test)]
 pub use testing::TestRunner;
@@ -15,7 +23,9 @@ pub use testing::TestRunner;
 /// Implementers of this trait must determine whether a Rust toolchain is _supported_
 /// for a Rust project. This is a step in the process of determining the _minimally
 /// supported_ Rust version; the MSRV.
-pub trait IsCompatible {
+///
+/// Compatibility checks may be run concurrently, from multiple threads.
+pub trait IsCompatible: Sync {
     fn before(&self, _toolchain: &Toolchain) -> TResult<()> {
         Ok(())
     }
@@ -32,6 +42,12 @@ pub trait RunCommandProvider {
     fn provide_run_command(&self) -> RunCommand;
 }
 
+/// The command which is run to determine whether a Rust toolchain is compatible with a
+/// single package of a workspace.
+pub trait PackageRunCommandProvider {
+    fn provide_package_run_command(&self, package: &str) -> RunCommand;
+}
+
 impl RunCommandProvider for FindContext {
     fn provide_run_command(&self) -> RunCommand {
         run_command(&self.check_cmd, &self.toolchain)
@@ -44,12 +60,34 @@ impl RunCommandProvider for VerifyContext {
     }
 }
 
+impl PackageRunCommandProvider for FindContext {
+    fn provide_package_run_command(&self, package: &str) -> RunCommand {
+        run_command_for_package(&self.check_cmd, &self.toolchain, Some(package))
+    }
+}
+
+impl PackageRunCommandProvider for VerifyContext {
+    fn provide_package_run_command(&self, package: &str) -> RunCommand {
+        run_command_for_package(&self.check_cmd, &self.toolchain, Some(package))
+    }
+}
+
 fn run_command(check_cmd: &CheckCommandContext, toolchain: &ToolchainContext) -> RunCommand {
+    run_command_for_package(check_cmd, toolchain, None)
+}
+
+// A custom check command is used as-is, since we can't know where to place the package selection.
+fn run_command_for_package(
+    check_cmd: &CheckCommandContext,
+    toolchain: &ToolchainContext,
+    package: Option<&str>,
+) -> RunCommand {
     if let Some(custom) = &check_cmd.rustup_command {
         RunCommand::custom(custom.clone())
     } else {
         let cargo_command = CargoCommand::default()
             .target(Some(toolchain.target))
+            .package(package)
             .features(check_cmd.cargo_features.clone())
             .all_features(check_cmd.cargo_all_features)
             .no_default_features(check_cmd.cargo_no_default_features);
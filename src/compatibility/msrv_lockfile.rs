@@ -0,0 +1,139 @@
+//! Resolve a lockfile for the checked toolchain.
+//!
+//! In a scratch copy of the workspace, the `rust-version` of each workspace member is set to the
+//! version of the toolchain. A lockfile is then generated by Cargo's MSRV-aware resolver, which
+//! prefers dependency versions that are compatible with this `rust-version`. The lockfile of the
+//! user is never touched.
+
+use crate::TResult;
+use crate::compatibility::scratch::ScratchWorkspace;
+use crate::error::{IoError, IoErrorSource};
+use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::rust::Toolchain;
+use camino::Utf8Path;
+use rust_releases::semver;
+use std::ffi::OsString;
+use std::fs;
+use std::process::{Command, Output};
+use toml_edit::{DocumentMut, value};
+
+/// The first Cargo release in which `resolver.incompatible-rust-versions = "fallback"` is stable.
+/// Older releases require the unstable `-Zmsrv-policy` flag.
+const STABLE_MSRV_POLICY: semver::Version = semver::Version::new(1, 84, 0);
+
+/// Generate the lockfile of the scratch workspace for the toolchain, with the Cargo which runs
+/// cargo-msrv.
+///
+/// The output is returned as is, since a failure to resolve the dependencies for the
+/// `rust-version` of the toolchain, is a reason for the toolchain to be incompatible.
+pub fn resolve_msrv_lockfile(scratch: &ScratchWorkspace, toolchain: &Toolchain) -> TResult<Output> {
+    for manifest in scratch.member_manifests() {
+        set_rust_version(manifest, toolchain.version())?;
+    }
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let version = cargo_version(&cargo, scratch.dir())?;
+
+    let mut command = Command::new(&cargo);
+    command
+        .current_dir(scratch.dir())
+        .env("CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS", "fallback");
+
+    if version.is_some_and(|version| version < STABLE_MSRV_POLICY) {
+        // Unstable flags are accepted by a stable Cargo when bootstrapping
+        command.arg("-Zmsrv-policy").env("RUSTC_BOOTSTRAP", "1");
+    }
+
+    command.arg("generate-lockfile");
+    info!(?command, "generating MSRV-aware lockfile");
+
+    let output = command.output().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::WaitForProcessAndCollectOutput(cargo),
+    })?;
+
+    Ok(output)
+}
+
+/// The version of the given Cargo, if it could be determined.
+fn cargo_version(cargo: &OsString, dir: &Utf8Path) -> TResult<Option<semver::Version>> {
+    let output = Command::new(cargo)
+        .current_dir(dir)
+        .arg("--version")
+        .output()
+        .map_err(|error| IoError {
+            error,
+            source: IoErrorSource::SpawnProcess(cargo.to_owned()),
+        })?;
+
+    // e.g. `cargo 1.84.0 (66221abde 2024-11-19)`
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(1)
+        .and_then(|version| semver::Version::parse(version).ok()))
+}
+
+/// Set the `rust-version` of the package of the given manifest, if it is not a virtual manifest.
+/// A `rust-version` inherited from the workspace is replaced as well.
+fn set_rust_version(cargo_toml: &Utf8Path, version: &semver::Version) -> TResult<()> {
+    let contents = fs::read_to_string(cargo_toml).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
+    })?;
+
+    let mut manifest = CargoManifestParser.parse::<DocumentMut>(&contents)?;
+
+    if let Some(package) = manifest
+        .get_mut("package")
+        .and_then(|package| package.as_table_like_mut())
+    {
+        package.insert("rust-version", value(version.to_string()));
+
+        fs::write(cargo_toml, manifest.to_string()).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::WriteFile(cargo_toml.to_path_buf()),
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+
+    fn manifest_path(tmp: &assert_fs::TempDir) -> Utf8PathBuf {
+        Utf8PathBuf::from_path_buf(tmp.path().join("Cargo.toml")).unwrap()
+    }
+
+    #[yare::parameterized(
+        without_rust_version = { "[package]\nname = \"a\"\n" },
+        with_rust_version = { "[package]\nname = \"a\"\nrust-version = \"1.80\"\n" },
+        inherited_rust_version = { "[package]\nname = \"a\"\nrust-version.workspace = true\n" },
+    )]
+    fn sets_rust_version(manifest: &str) {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cargo_toml = manifest_path(&tmp);
+        fs::write(&cargo_toml, manifest).unwrap();
+
+        set_rust_version(&cargo_toml, &semver::Version::new(1, 70, 0)).unwrap();
+
+        let manifest = fs::read_to_string(&cargo_toml).unwrap();
+        let manifest = manifest.parse::<DocumentMut>().unwrap();
+        assert_eq!(manifest["package"]["rust-version"].as_str(), Some("1.70.0"));
+        assert_eq!(manifest["package"]["name"].as_str(), Some("a"));
+    }
+
+    #[test]
+    fn leaves_virtual_manifest_untouched() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cargo_toml = manifest_path(&tmp);
+        let manifest = "[workspace]\nmembers = [\"a\"]\n";
+        fs::write(&cargo_toml, manifest).unwrap();
+
+        set_rust_version(&cargo_toml, &semver::Version::new(1, 70, 0)).unwrap();
+
+        assert_eq!(fs::read_to_string(&cargo_toml).unwrap(), manifest);
+    }
+}
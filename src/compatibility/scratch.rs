@@ -0,0 +1,667 @@
+//! Copies of the workspace, in which compatibility checks can run without touching the
+//! working tree of the user.
+//!
+//! The working tree is copied once per run, to a snapshot. The scratch copy of each toolchain is
+//! copied from this snapshot, so every toolchain checks the same sources, even if the working
+//! tree is edited while the checks run.
+
+use crate::TResult;
+use crate::error::{IoError, IoErrorSource};
+use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::rust::Toolchain;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::{Metadata, MetadataCommand};
+use std::fs;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use toml_edit::{DocumentMut, TableLike};
+
+/// Directories of version control systems, which are not copied.
+const VCS_DIRS: &[&str] = &[".git", ".hg", ".jj", ".svn", ".bzr", ".pijul", "_darcs"];
+
+/// Where a scratch copy of the workspace is placed, and where it keeps its build output.
+#[derive(Clone, Copy, Debug)]
+pub enum ScratchLocation<'a> {
+    /// Within the target directory of the workspace, sharing the target directory.
+    TargetDir,
+    /// Within an isolation directory outside of the workspace, with a target directory for each
+    /// toolchain.
+    Isolated(&'a IsolationDir),
+}
+
+/// A temporary directory outside of the workspace, in which isolated checks copy the workspace,
+/// and keep their build output. It's removed when dropped.
+#[derive(Debug)]
+pub struct IsolationDir {
+    dir: Utf8PathBuf,
+}
+
+impl IsolationDir {
+    /// A new, unique, directory within the temporary directory of the system. The directory is
+    /// created once the first scratch workspace is placed in it.
+    pub fn new() -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let name = format!(
+            "cargo-msrv-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let temp_dir = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .unwrap_or_else(|_| Utf8PathBuf::from("."));
+
+        Self {
+            dir: temp_dir.join(name),
+        }
+    }
+
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+}
+
+impl Drop for IsolationDir {
+    fn drop(&mut self) {
+        if self.dir.is_dir()
+            && let Err(err) = fs::remove_dir_all(&self.dir)
+        {
+            info!(%err, dir = %self.dir, "unable to remove isolation directory");
+        }
+    }
+}
+
+/// The snapshots of the workspaces checked during a run, each taken when the first scratch copy
+/// of its workspace is created. The snapshots are removed when dropped.
+#[derive(Debug)]
+pub struct WorkspaceSnapshots {
+    dir: IsolationDir,
+    taken: Mutex<Vec<Snapshot>>,
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    workspace_root: Utf8PathBuf,
+    dir: Utf8PathBuf,
+}
+
+impl WorkspaceSnapshots {
+    /// Snapshots placed in a new, unique, directory within the temporary directory of the system.
+    pub fn new() -> Self {
+        Self {
+            dir: IsolationDir::new(),
+            taken: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The snapshot of the given workspace, which is taken if it wasn't already.
+    ///
+    /// Relative path dependencies which point outside of the workspace, are rewritten to the
+    /// absolute path of the dependency, since they aren't part of the snapshot.
+    fn snapshot(&self, workspace_root: &Utf8Path, metadata: &Metadata) -> TResult<Utf8PathBuf> {
+        // held while the snapshot is taken, so concurrent checks wait for the same snapshot
+        let mut taken = self
+            .taken
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(snapshot) = taken
+            .iter()
+            .find(|snapshot| snapshot.workspace_root == workspace_root)
+        {
+            return Ok(snapshot.dir.clone());
+        }
+
+        let dir = self.dir.dir().join(taken.len().to_string());
+        info!(%workspace_root, %dir, "taking snapshot of workspace");
+
+        remove_dir(&dir)?;
+        copy_dir(workspace_root, &dir, &metadata.target_directory)?;
+
+        let mut manifests = metadata
+            .workspace_packages()
+            .into_iter()
+            .map(|package| canonicalize(&package.manifest_path))
+            .collect::<Vec<_>>();
+        manifests.push(workspace_root.join("Cargo.toml"));
+        manifests.sort();
+        manifests.dedup();
+
+        for manifest in manifests {
+            if let Ok(relative) = manifest.strip_prefix(workspace_root) {
+                absolutize_external_path_dependencies(
+                    &manifest,
+                    &dir.join(relative),
+                    workspace_root,
+                )?;
+            }
+        }
+
+        taken.push(Snapshot {
+            workspace_root: workspace_root.to_path_buf(),
+            dir: dir.clone(),
+        });
+
+        Ok(dir)
+    }
+}
+
+impl Default for WorkspaceSnapshots {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A copy of the workspace, which is removed when dropped.
+#[derive(Debug)]
+pub struct ScratchWorkspace {
+    dir: Utf8PathBuf,
+    crate_root: Utf8PathBuf,
+    target_dir: Utf8PathBuf,
+    member_manifests: Vec<Utf8PathBuf>,
+}
+
+impl ScratchWorkspace {
+    /// Copy the snapshot of the workspace of the crate at `crate_root` to a scratch directory for
+    /// the given toolchain, replacing any previous copy. The snapshot is taken if it wasn't
+    /// already.
+    ///
+    /// The target directory, and version control directories like `.git`, are not copied.
+    pub fn create(
+        snapshots: &WorkspaceSnapshots,
+        crate_root: &Utf8Path,
+        toolchain: &Toolchain,
+        location: ScratchLocation<'_>,
+    ) -> TResult<Self> {
+        let metadata = MetadataCommand::new()
+            .current_dir(crate_root)
+            .manifest_path(crate_root.join("Cargo.toml"))
+            .no_deps()
+            .exec()?;
+
+        let workspace_root = canonicalize(&metadata.workspace_root);
+        let toolchain_name = toolchain.to_string();
+
+        let (dir, target_dir) = match location {
+            ScratchLocation::TargetDir => (
+                metadata
+                    .target_directory
+                    .join("cargo-msrv")
+                    .join("scratch")
+                    .join(&toolchain_name),
+                metadata.target_directory.clone(),
+            ),
+            ScratchLocation::Isolated(isolation) => (
+                isolation.dir().join("workspace").join(&toolchain_name),
+                isolation.dir().join("target").join(&toolchain_name),
+            ),
+        };
+
+        let snapshot = snapshots.snapshot(&workspace_root, &metadata)?;
+
+        remove_dir(&dir)?;
+        copy_dir(&snapshot, &dir, &metadata.target_directory)?;
+
+        let member_manifests = metadata
+            .workspace_packages()
+            .into_iter()
+            .filter_map(|package| {
+                canonicalize(&package.manifest_path)
+                    .strip_prefix(&workspace_root)
+                    .map(|manifest| dir.join(manifest))
+                    .ok()
+            })
+            .collect();
+
+        Ok(Self {
+            crate_root: dir.join(
+                canonicalize(crate_root)
+                    .strip_prefix(&workspace_root)
+                    .unwrap_or(Utf8Path::new("")),
+            ),
+            dir,
+            target_dir,
+            member_manifests,
+        })
+    }
+
+    /// The root of the copy of the workspace.
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// The copy of the crate root.
+    pub fn crate_root(&self) -> &Utf8Path {
+        &self.crate_root
+    }
+
+    /// The target directory in which the copy is built.
+    ///
+    /// Unless isolated, it's the target directory of the original workspace, so the build
+    /// artifacts of dependencies are re-used between checks.
+    pub fn target_dir(&self) -> &Utf8Path {
+        &self.target_dir
+    }
+
+    /// The copied lockfile of the workspace.
+    pub fn lockfile(&self) -> Utf8PathBuf {
+        self.dir.join("Cargo.lock")
+    }
+
+    /// The copied manifests of the workspace members.
+    pub fn member_manifests(&self) -> &[Utf8PathBuf] {
+        &self.member_manifests
+    }
+}
+
+impl Drop for ScratchWorkspace {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_dir_all(&self.dir) {
+            info!(%err, dir = %self.dir, "unable to remove scratch workspace");
+        }
+    }
+}
+
+/// Paths reported by `cargo metadata` are canonical, so ours must be as well, to compare them.
+fn canonicalize(path: &Utf8Path) -> Utf8PathBuf {
+    dunce::canonicalize(path)
+        .ok()
+        .and_then(|path| Utf8PathBuf::from_path_buf(path).ok())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn remove_dir(dir: &Utf8Path) -> TResult<()> {
+    if dir.is_dir() {
+        fs::remove_dir_all(dir).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::RemoveDir(dir.to_path_buf()),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Recursively copy the directory `from` to `to`, skipping version control directories, the
+/// target directory and any other directory which is tagged as a cache, like the build output of
+/// Cargo. Other hidden directories, like `.cargo`, are copied. Symbolic links are recreated, with
+/// the same target, rather than followed, so a link to an ancestor directory isn't copied forever.
+fn copy_dir(from: &Utf8Path, to: &Utf8Path, target_dir: &Utf8Path) -> TResult<()> {
+    fs::create_dir_all(to).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::CreateDir(to.to_path_buf()),
+    })?;
+
+    let entries = from.read_dir_utf8().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadDir(from.to_path_buf()),
+    })?;
+
+    for entry in entries {
+        let entry = entry.map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(from.to_path_buf()),
+        })?;
+        let path = entry.path();
+        let destination = to.join(entry.file_name());
+        let file_type = entry.file_type().map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(from.to_path_buf()),
+        })?;
+
+        if file_type.is_symlink() {
+            copy_symlink(path, &destination)?;
+        } else if file_type.is_dir() {
+            let skip = VCS_DIRS.contains(&entry.file_name())
+                || path == target_dir
+                || path.join("CACHEDIR.TAG").is_file();
+
+            if !skip {
+                copy_dir(path, &destination, target_dir)?;
+            }
+        } else {
+            fs::copy(path, &destination).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::CopyFile(path.to_path_buf()),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a symbolic link at `to`, with the same target as the symbolic link at `from`.
+fn copy_symlink(from: &Utf8Path, to: &Utf8Path) -> TResult<()> {
+    let io_error = |error| IoError {
+        error,
+        source: IoErrorSource::CopyFile(from.to_path_buf()),
+    };
+
+    let target = fs::read_link(from).map_err(io_error)?;
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&target, to).map_err(io_error)?;
+
+    #[cfg(windows)]
+    if from.is_dir() {
+        std::os::windows::fs::symlink_dir(&target, to).map_err(io_error)?;
+    } else {
+        std::os::windows::fs::symlink_file(&target, to).map_err(io_error)?;
+    }
+
+    Ok(())
+}
+
+/// Rewrite the relative paths of the path dependencies in the `copy` of the `original` manifest,
+/// which point outside of the workspace, and therefore outside of the copy, to the absolute path
+/// of the dependency.
+fn absolutize_external_path_dependencies(
+    original: &Utf8Path,
+    copy: &Utf8Path,
+    workspace_root: &Utf8Path,
+) -> TResult<()> {
+    let Some(original_dir) = original.parent() else {
+        return Ok(());
+    };
+
+    let contents = fs::read_to_string(copy).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(copy.to_path_buf()),
+    })?;
+    let mut manifest = CargoManifestParser.parse::<DocumentMut>(&contents)?;
+
+    let external_path = |path: &str| {
+        let dependency = canonicalize(&original_dir.join(path));
+        (!dependency.starts_with(workspace_root)).then(|| dependency.into_string())
+    };
+
+    if rewrite_dependency_paths(manifest.as_table_mut(), false, &external_path) {
+        fs::write(copy, manifest.to_string()).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::WriteFile(copy.to_path_buf()),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Rewrite the `path` of each dependency in the table, including those in the dependency tables
+/// of `[target]`, `[workspace]`, `[patch]` and `[replace]`. Returns whether any path was
+/// rewritten.
+fn rewrite_dependency_paths(
+    table: &mut dyn TableLike,
+    in_dependencies: bool,
+    rewrite: &dyn Fn(&str) -> Option<String>,
+) -> bool {
+    let mut rewritten = false;
+
+    for (key, item) in table.iter_mut() {
+        if in_dependencies
+            && key.get() == "path"
+            && let Some(value) = item.as_value_mut()
+            && let Some(path) = value.as_str().and_then(rewrite)
+        {
+            let decor = value.decor().clone();
+            *value = path.into();
+            *value.decor_mut() = decor;
+            rewritten = true;
+        } else if let Some(table) = item.as_table_like_mut() {
+            let in_dependencies = in_dependencies
+                || key.get().ends_with("dependencies")
+                || matches!(key.get(), "patch" | "replace");
+
+            rewritten |= rewrite_dependency_paths(table, in_dependencies, rewrite);
+        }
+    }
+
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    #[test]
+    fn copies_sources_but_not_build_output() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".git/HEAD"), "").unwrap();
+        fs::create_dir_all(root.join(".jj")).unwrap();
+        fs::create_dir_all(root.join(".cargo")).unwrap();
+        fs::write(root.join(".cargo/config.toml"), "").unwrap();
+        fs::create_dir_all(root.join("out/debug")).unwrap();
+        fs::write(root.join("out/CACHEDIR.TAG"), "").unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+
+        let copy = root.join("target/copy");
+        copy_dir(&root, &copy, &root.join("target")).unwrap();
+
+        assert!(copy.join("Cargo.toml").is_file());
+        assert!(copy.join("src/lib.rs").is_file());
+        assert!(copy.join(".cargo/config.toml").is_file());
+        assert!(!copy.join(".git").exists());
+        assert!(!copy.join(".jj").exists());
+        assert!(!copy.join("out").exists());
+        assert!(!copy.join("target").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recreates_symlinks_instead_of_following_them() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+        let crate_root = root.join("crate");
+
+        fs::create_dir_all(crate_root.join("src")).unwrap();
+        fs::write(crate_root.join("src/lib.rs"), "").unwrap();
+        std::os::unix::fs::symlink("..", crate_root.join("src/parent")).unwrap();
+        std::os::unix::fs::symlink("lib.rs", crate_root.join("src/alias.rs")).unwrap();
+
+        let copy = root.join("copy");
+        copy_dir(&crate_root, &copy, &crate_root.join("target")).unwrap();
+
+        assert!(copy.join("src/parent").is_symlink());
+        assert_eq!(
+            fs::read_link(copy.join("src/parent")).unwrap(),
+            Utf8Path::new("..")
+        );
+        assert!(copy.join("src/parent/src/lib.rs").is_file());
+        assert!(copy.join("src/alias.rs").is_symlink());
+        assert!(copy.join("src/alias.rs").is_file());
+    }
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(
+            semver::Version::new(1, minor, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        )
+    }
+
+    fn write_package(root: &Utf8Path, dependencies: &str) {
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n{}",
+                root.file_name().unwrap(),
+                dependencies
+            ),
+        )
+        .unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+    }
+
+    #[test]
+    fn isolated_copy_of_workspace() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(tmp.path().join("a")).unwrap();
+        write_package(&root, "");
+
+        let isolation = IsolationDir::new();
+        let snapshots = WorkspaceSnapshots::new();
+        let scratch = ScratchWorkspace::create(
+            &snapshots,
+            &root,
+            &toolchain(70),
+            ScratchLocation::Isolated(&isolation),
+        )
+        .unwrap();
+
+        assert!(scratch.crate_root().starts_with(isolation.dir()));
+        assert!(scratch.target_dir().starts_with(isolation.dir()));
+        assert!(scratch.crate_root().join("src/lib.rs").is_file());
+        assert_eq!(
+            scratch.member_manifests(),
+            &[scratch.dir().join("Cargo.toml")]
+        );
+
+        let dir = scratch.dir().to_path_buf();
+        drop(scratch);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn scratch_workspaces_are_copied_from_one_snapshot() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(tmp.path().join("a")).unwrap();
+        write_package(&root, "");
+
+        let isolation = IsolationDir::new();
+        let snapshots = WorkspaceSnapshots::new();
+        let location = ScratchLocation::Isolated(&isolation);
+
+        let first = ScratchWorkspace::create(&snapshots, &root, &toolchain(70), location).unwrap();
+        // edited while the checks run
+        fs::write(root.join("src/lib.rs"), "edited").unwrap();
+        let second = ScratchWorkspace::create(&snapshots, &root, &toolchain(71), location).unwrap();
+
+        assert_ne!(first.dir(), second.dir());
+        assert_eq!(
+            fs::read_to_string(second.crate_root().join("src/lib.rs")).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn copies_cargo_config_and_path_dependencies_outside_of_workspace() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(tmp.path().join("a")).unwrap();
+        let dependency = Utf8PathBuf::from_path_buf(tmp.path().join("b")).unwrap();
+        write_package(&root, "b = { path = \"../b\" }\n");
+        write_package(&dependency, "");
+        fs::create_dir_all(root.join(".cargo")).unwrap();
+        fs::write(
+            root.join(".cargo/config.toml"),
+            "[build]\nrustflags = [\"--cfg\", \"scratch\"]\n",
+        )
+        .unwrap();
+
+        let isolation = IsolationDir::new();
+        let snapshots = WorkspaceSnapshots::new();
+        let scratch = ScratchWorkspace::create(
+            &snapshots,
+            &root,
+            &toolchain(70),
+            ScratchLocation::Isolated(&isolation),
+        )
+        .unwrap();
+
+        assert!(scratch.dir().join(".cargo/config.toml").is_file());
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(scratch.dir().join("Cargo.toml"))
+            .exec()
+            .unwrap();
+        let b = metadata
+            .packages
+            .iter()
+            .find(|package| package.name.as_str() == "b")
+            .unwrap();
+        assert_eq!(
+            b.manifest_path,
+            canonicalize(&dependency.join("Cargo.toml"))
+        );
+    }
+
+    #[test]
+    fn rewrites_only_dependency_paths_outside_of_workspace() {
+        let manifest = r#"
+[package]
+name = "a"
+
+[lib]
+path = "../lib.rs"
+
+[dependencies]
+inside = { path = "inside" }
+outside = { path = "../outside" } # comment
+path = { path = "../path" }
+
+[target.'cfg(unix)'.dev-dependencies.unix]
+path = "../unix"
+
+[workspace.dependencies]
+shared = { path = "../shared" }
+
+[patch.crates-io]
+patched = { path = "../patched" }
+"#;
+        let mut manifest = manifest.parse::<DocumentMut>().unwrap();
+        let rewrite = |path: &str| {
+            path.strip_prefix("../")
+                .map(|path| format!("/abs/{}", path))
+        };
+
+        assert!(rewrite_dependency_paths(
+            manifest.as_table_mut(),
+            false,
+            &rewrite
+        ));
+
+        assert_eq!(manifest["lib"]["path"].as_str(), Some("../lib.rs"));
+        let dependencies = &manifest["dependencies"];
+        assert_eq!(dependencies["inside"]["path"].as_str(), Some("inside"));
+        assert_eq!(
+            dependencies["outside"]["path"].as_str(),
+            Some("/abs/outside")
+        );
+        assert_eq!(dependencies["path"]["path"].as_str(), Some("/abs/path"));
+        assert_eq!(
+            manifest["target"]["cfg(unix)"]["dev-dependencies"]["unix"]["path"].as_str(),
+            Some("/abs/unix")
+        );
+        assert_eq!(
+            manifest["workspace"]["dependencies"]["shared"]["path"].as_str(),
+            Some("/abs/shared")
+        );
+        assert_eq!(
+            manifest["patch"]["crates-io"]["patched"]["path"].as_str(),
+            Some("/abs/patched")
+        );
+        assert!(manifest.to_string().contains("# comment"));
+    }
+
+    #[test]
+    fn isolation_dirs_are_unique() {
+        let first = IsolationDir::new();
+        let second = IsolationDir::new();
+
+        assert_ne!(first.dir(), second.dir());
+    }
+
+    #[test]
+    fn isolation_dir_is_removed_when_dropped() {
+        let isolation = IsolationDir::new();
+        let dir = isolation.dir().to_path_buf();
+        fs::create_dir_all(dir.join("workspace")).unwrap();
+
+        drop(isolation);
+
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,191 @@
+//! The Cargo target directory of each checked toolchain.
+//!
+//! Alternating between toolchains, like a bisection does, invalidates the build artifacts in a
+//! shared target directory on each check, and may cause an older toolchain to fail on the crate
+//! metadata written by a newer one (`E0514`). Each toolchain therefore builds in its own target
+//! directory.
+
+use crate::context::{EnvironmentContext, TargetDirCleanup};
+use crate::error::{IoError, IoErrorSource};
+use crate::reporter::event::{AuxiliaryOutput, AuxiliaryOutputItem, Destination, TargetDirKind};
+use crate::rust::Toolchain;
+use crate::{Reporter, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::MetadataCommand;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+/// The target directories of the toolchains checked during a run, which are cleaned up according
+/// to the cleanup policy once the run is done.
+#[derive(Debug)]
+pub struct ToolchainTargetDirs {
+    dir: Utf8PathBuf,
+    cleanup: TargetDirCleanup,
+    used: Mutex<BTreeSet<Utf8PathBuf>>,
+}
+
+impl ToolchainTargetDirs {
+    /// Target directories placed in `<target-dir>/cargo-msrv`, where `<target-dir>` is the target
+    /// directory of the workspace, as reported by `cargo metadata`. It takes `CARGO_TARGET_DIR`
+    /// and the `build.target-dir` configuration into account, and defaults to the `target`
+    /// directory of the workspace root.
+    pub fn new(environment: &EnvironmentContext, cleanup: TargetDirCleanup) -> TResult<Self> {
+        // Cargo configuration is discovered from the current directory, like it is for the checks
+        let metadata = MetadataCommand::new()
+            .current_dir(environment.root())
+            .manifest_path(environment.manifest())
+            .no_deps()
+            .exec()?;
+
+        Ok(Self::in_dir(
+            metadata.target_directory.join("cargo-msrv"),
+            cleanup,
+        ))
+    }
+
+    pub fn in_dir(dir: impl Into<Utf8PathBuf>, cleanup: TargetDirCleanup) -> Self {
+        Self {
+            dir: dir.into(),
+            cleanup,
+            used: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// The target directory of the given toolchain, i.e. `<dir>/<toolchain-spec>`.
+    ///
+    /// Concurrent checks always check different toolchains, so they never share a directory.
+    pub fn for_toolchain(&self, toolchain: &Toolchain) -> Utf8PathBuf {
+        let dir = self.dir.join(toolchain.spec());
+
+        self.used
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(dir.clone());
+
+        dir
+    }
+
+    /// Keep or delete each target directory used during the run, according to the cleanup
+    /// policy, and report it.
+    pub fn clean_up(&self, reporter: &impl Reporter) -> TResult<()> {
+        let used = std::mem::take(
+            &mut *self
+                .used
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+
+        for dir in used.into_iter().filter(|dir| dir.is_dir()) {
+            let kind = match self.cleanup {
+                TargetDirCleanup::Keep => TargetDirKind::Kept,
+                TargetDirCleanup::Delete => {
+                    remove_dir(&dir)?;
+                    TargetDirKind::Deleted
+                }
+            };
+
+            reporter.report_event(AuxiliaryOutput::new(
+                Destination::directory(dir),
+                AuxiliaryOutputItem::target_dir(kind),
+            ))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn remove_dir(dir: &Utf8Path) -> TResult<()> {
+    std::fs::remove_dir_all(dir).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::RemoveDir(dir.to_path_buf()),
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WorkspacePackages;
+    use crate::reporter::{Event, TestReporterWrapper};
+    use crate::semver;
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    #[test]
+    fn target_dir_per_toolchain() {
+        let dirs = ToolchainTargetDirs::in_dir("target/cargo-msrv", TargetDirCleanup::Keep);
+
+        assert_eq!(
+            dirs.for_toolchain(&toolchain(70)),
+            Utf8PathBuf::from("target/cargo-msrv/1.70.0-x")
+        );
+        assert_ne!(
+            dirs.for_toolchain(&toolchain(70)),
+            dirs.for_toolchain(&toolchain(71))
+        );
+    }
+
+    #[test]
+    fn target_dirs_in_target_directory_of_workspace() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+        std::fs::create_dir_all(root.join("member/src")).unwrap();
+        std::fs::create_dir_all(root.join(".cargo")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"member\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("member/Cargo.toml"),
+            "[package]\nname = \"member\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("member/src/lib.rs"), "").unwrap();
+        std::fs::write(
+            root.join(".cargo/config.toml"),
+            "[build]\ntarget-dir = \"out\"\n",
+        )
+        .unwrap();
+
+        let environment = EnvironmentContext {
+            root_crate_path: root.join("member"),
+            workspace_packages: WorkspacePackages::default(),
+        };
+        let dirs = ToolchainTargetDirs::new(&environment, TargetDirCleanup::Keep).unwrap();
+
+        let dir = dirs.for_toolchain(&toolchain(70));
+        assert!(dir.ends_with("out/cargo-msrv/1.70.0-x"));
+        assert!(dir.starts_with(dunce::canonicalize(&root).unwrap()));
+    }
+
+    #[yare::parameterized(
+        keep = { TargetDirCleanup::Keep, TargetDirKind::Kept, true },
+        delete = { TargetDirCleanup::Delete, TargetDirKind::Deleted, false },
+    )]
+    fn cleans_up_used_target_dirs(cleanup: TargetDirCleanup, kind: TargetDirKind, exists: bool) {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+        let dirs = ToolchainTargetDirs::in_dir(&root, cleanup);
+
+        let used = dirs.for_toolchain(&toolchain(70));
+        std::fs::create_dir_all(used.join("debug")).unwrap();
+        // used, but never created, e.g. because the toolchain was unavailable
+        dirs.for_toolchain(&toolchain(71));
+
+        let reporter = TestReporterWrapper::default();
+        dirs.clean_up(reporter.get()).unwrap();
+
+        assert_eq!(used.exists(), exists);
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::from(AuxiliaryOutput::new(
+                Destination::directory(used),
+                AuxiliaryOutputItem::target_dir(kind),
+            ))]
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use crate::compatibility::IsCompatible;
+use crate::compatibility::cache::{CacheKey, CompatibilityCache, SourceFingerprint};
+use crate::compatibility::diagnostics;
+use crate::compatibility::msrv_lockfile::resolve_msrv_lockfile;
+use crate::compatibility::scratch::{
+    IsolationDir, ScratchLocation, ScratchWorkspace, WorkspaceSnapshots,
+};
+use crate::compatibility::target_dir::ToolchainTargetDirs;
+use crate::context::EnvironmentContext;
+use crate::error::{IoError, IoErrorSource, LockfileHandlerError};
+use crate::external_command::cargo_command::CargoCommand;
+use crate::lockfile::LockfileHandler;
+use crate::outcome::Incompatible;
+use crate::reporter::event::{CheckMethod, CheckResult, CheckToolchain, SetupToolchain};
+use crate::rust::Toolchain;
+use crate::rust::toolchain_provider::{
+    RustupToolchainProvider, ToolchainCommand, ToolchainProvider,
+};
+use crate::{CargoMSRVError, Compatibility, Reporter, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::fmt;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+/// Checks whether a toolchain is compatible, by running the check command with it. Toolchains are
+/// set up, and run, by a [`ToolchainProvider`], which is rustup unless another one is given.
+pub struct ToolchainCheck<'reporter, 'env, R: Reporter> {
+    reporter: &'reporter R,
+    settings: Settings<'env>,
+    cache: Option<CompatibilityCache>,
+    isolation: Option<IsolationDir>,
+    snapshots: Arc<WorkspaceSnapshots>,
+    target_dirs: Option<&'env ToolchainTargetDirs>,
+    provider: &'env dyn ToolchainProvider,
+}
+
+/// Toolchains are installed and run with rustup, unless another provider is given.
+const DEFAULT_PROVIDER: RustupToolchainProvider<'static> = RustupToolchainProvider::new();
+
+impl<'reporter, 'env, R: Reporter> ToolchainCheck<'reporter, 'env, R> {
+    pub fn new(
+        reporter: &'reporter R,
+        ignore_lockfile: bool,
+        no_check_feedback: bool,
+        skip_unavailable_toolchains: bool,
+        environment: &'env EnvironmentContext,
+        run_command: RunCommand,
+    ) -> Self {
+        Self {
+            reporter,
+            settings: Settings {
+                ignore_lockfile,
+                resolve_msrv_lockfile: false,
+                no_check_feedback,
+                skip_unavailable_toolchains,
+                environment,
+                check_cmd: run_command,
+            },
+            cache: None,
+            isolation: None,
+            snapshots: Arc::new(WorkspaceSnapshots::new()),
+            target_dirs: None,
+            provider: &DEFAULT_PROVIDER,
+        }
+    }
+
+    /// Build each toolchain in its own target directory, instead of in the target directory of
+    /// the crate.
+    pub fn with_target_dirs(mut self, target_dirs: &'env ToolchainTargetDirs) -> Self {
+        self.target_dirs = Some(target_dirs);
+        self
+    }
+
+    /// Install and run the checked toolchains with the given provider, instead of with rustup.
+    pub fn with_toolchain_provider(mut self, provider: &'env dyn ToolchainProvider) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    /// Opt in to running each check in a copy of the workspace, outside of the working tree, and
+    /// with a target directory for each toolchain. The working tree of the user is not touched,
+    /// not even temporarily, so it can be edited while the checks run.
+    pub fn with_isolation(mut self, isolated: bool) -> Self {
+        self.isolation = isolated.then(IsolationDir::new);
+        self
+    }
+
+    /// Copy the scratch workspaces from the given snapshots, so checks of other packages, targets
+    /// or feature sets in the same run, share the snapshot of the workspace.
+    pub fn with_snapshots(mut self, snapshots: Arc<WorkspaceSnapshots>) -> Self {
+        self.snapshots = snapshots;
+        self
+    }
+
+    /// Opt in to checking each toolchain against a lockfile which was resolved for it, in a
+    /// scratch copy of the workspace, instead of against the lockfile of the crate.
+    pub fn with_msrv_lockfile(mut self, resolve_msrv_lockfile: bool) -> Self {
+        self.settings.resolve_msrv_lockfile = resolve_msrv_lockfile;
+        self
+    }
+
+    /// Opt in to re-using the outcomes of previous checks, stored in the given cache, if any.
+    ///
+    /// The outcomes are keyed by a fingerprint of the sources, which is taken before each check,
+    /// so changes made to the sources while the checks run are taken into account.
+    pub fn with_cache(mut self, cache: Option<CompatibilityCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+}
+
+impl<R: Reporter> IsCompatible for ToolchainCheck<'_, '_, R> {
+    fn is_compatible(&self, toolchain: &Toolchain) -> TResult<Compatibility> {
+        self.check(toolchain)
+    }
+}
+
+impl<R: Reporter> ToolchainCheck<'_, '_, R> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Compatibility> {
+        let settings = &self.settings;
+        let target_dir = self
+            .target_dirs
+            .map(|target_dirs| target_dirs.for_toolchain(toolchain));
+        let target_dir = target_dir.as_deref();
+
+        self.reporter
+            .run_scoped_event(CheckToolchain::new(toolchain.to_owned()), || {
+                info!(
+                    ignore_lockfile_enabled = settings.ignore_lockfile(),
+                    resolve_msrv_lockfile_enabled = settings.resolve_msrv_lockfile(),
+                    isolated = self.isolation.is_some(),
+                );
+
+                let cached = self.cache.as_ref().and_then(|cache| {
+                    let fingerprint = settings.fingerprint()?;
+                    let key = CacheKey::new(
+                        toolchain,
+                        &self.provider.origin(),
+                        &settings.cache_key_command(),
+                        &fingerprint,
+                    );
+                    Some((cache, key, fingerprint))
+                });
+
+                if let Some(outcome) = cached
+                    .as_ref()
+                    .and_then(|(cache, key, _)| cache.get(key, toolchain))
+                {
+                    info!(%toolchain, "using cached compatibility check outcome");
+                    report_outcome(self.reporter, &outcome, settings.no_check_feedback())?;
+
+                    return Ok(outcome);
+                }
+
+                // a scratch copy of the workspace is used, when the working tree must not be touched
+                let in_scratch_workspace =
+                    self.isolation.is_some() || settings.resolve_msrv_lockfile();
+
+                // temporarily move the lockfile if the user opted to ignore it, and it exists,
+                // unless the lockfile of the scratch copy can be removed instead
+                let ignore_lockfile = settings.ignore_lockfile() && !in_scratch_workspace;
+                let handle_wrap = create_lockfile_handle(ignore_lockfile, settings.environment)?
+                    .map(|handle| handle.move_lockfile())
+                    .transpose()?;
+
+                // Exit early, while marking this version as incompatible, when `skip_unavailable_toolchains`
+                // is set and the setup failed.
+                match setup_toolchain(self.reporter, self.provider, toolchain) {
+                    Ok(()) => Ok(()),
+                    Err(err) if settings.skip_unavailable_toolchains() => {
+                        return Ok(Compatibility::Incompatible(Incompatible {
+                            toolchain_spec: toolchain.clone(),
+                            error_message: err.to_string(),
+                            diagnosis: None,
+                        }));
+                    }
+                    Err(err) => Err(err),
+                }?;
+
+                if handle_wrap.is_some() {
+                    remove_lockfile(&settings.lockfile_path())?;
+                }
+
+                let cmd = &self.settings.check_cmd;
+
+                let outcome = if in_scratch_workspace {
+                    run_check_command_in_scratch_workspace(
+                        self.reporter,
+                        self.provider,
+                        toolchain,
+                        settings,
+                        self.isolation.as_ref(),
+                        &self.snapshots,
+                        target_dir,
+                    )?
+                } else {
+                    run_check_command(
+                        self.reporter,
+                        self.provider,
+                        toolchain,
+                        settings.crate_root_path(),
+                        target_dir,
+                        cmd.components(),
+                    )?
+                };
+
+                // report outcome to UI
+                report_outcome(self.reporter, &outcome, settings.no_check_feedback())?;
+
+                // the outcome is only stored if the sources didn't change during the check, and
+                // a failure to store it only costs us a re-check next time
+                if let Some((cache, key, fingerprint)) = cached {
+                    if settings.fingerprint() != Some(fingerprint) {
+                        info!(%toolchain, "sources changed during check, not caching the outcome");
+                    } else if let Err(err) = cache.insert(&key, &outcome) {
+                        info!(%err, "unable to store compatibility check outcome");
+                    }
+                }
+
+                // move the lockfile back - we do this explicitly for clarity
+                if let Some(handle) = handle_wrap {
+                    drop(handle);
+                }
+
+                Ok(outcome)
+            })
+    }
+}
+
+impl<R: Reporter> fmt::Debug for ToolchainCheck<'_, '_, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.settings))
+    }
+}
+
+#[instrument(skip(reporter, provider, toolchain))]
+fn setup_toolchain(
+    reporter: &impl Reporter,
+    provider: &dyn ToolchainProvider,
+    toolchain: &Toolchain,
+) -> TResult<()> {
+    reporter.run_scoped_event(SetupToolchain::new(toolchain.to_owned()), || {
+        provider.install(toolchain)
+    })
+}
+
+/// Run the check in a scratch copy of the snapshot of the workspace, optionally against a
+/// lockfile which was resolved for the `rust-version` of the toolchain.
+///
+/// Unless isolated, the scratch copy is placed in the target directory of the crate, and builds
+/// in the target directory of the toolchain, if given, or the target directory of the crate.
+/// Isolated copies are placed in the isolation directory, with a target directory for each
+/// toolchain.
+fn run_check_command_in_scratch_workspace(
+    reporter: &impl Reporter,
+    provider: &dyn ToolchainProvider,
+    toolchain: &Toolchain,
+    settings: &Settings,
+    isolation: Option<&IsolationDir>,
+    snapshots: &WorkspaceSnapshots,
+    target_dir: Option<&Utf8Path>,
+) -> TResult<Compatibility> {
+    let location = isolation.map_or(ScratchLocation::TargetDir, ScratchLocation::Isolated);
+    let scratch =
+        ScratchWorkspace::create(snapshots, settings.crate_root_path(), toolchain, location)?;
+
+    if settings.resolve_msrv_lockfile() {
+        let output = resolve_msrv_lockfile(&scratch, toolchain)?;
+
+        if !output.status.success() {
+            // e.g. the `rust-version` of the toolchain precedes the edition of the crate
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            info!(?toolchain, %stderr, "unable to generate MSRV-aware lockfile");
+
+            return Ok(Compatibility::new_diagnosed_failure(
+                toolchain.to_owned(),
+                stderr.to_string(),
+                diagnostics::diagnose("", &stderr),
+            ));
+        }
+    } else if settings.ignore_lockfile() {
+        remove_lockfile(&scratch.lockfile())?;
+    }
+
+    let target_dir = match (isolation, target_dir) {
+        (None, Some(target_dir)) => target_dir,
+        _ => scratch.target_dir(),
+    };
+
+    run_check_command(
+        reporter,
+        provider,
+        toolchain,
+        scratch.crate_root(),
+        Some(target_dir),
+        settings.check_cmd.components(),
+    )
+}
+
+fn run_check_command(
+    reporter: &impl Reporter,
+    provider: &dyn ToolchainProvider,
+    toolchain: &Toolchain,
+    dir: &Utf8Path,
+    target_dir: Option<&Utf8Path>,
+    check: &[String],
+) -> TResult<Compatibility> {
+    // Prefer compiler diagnostics as JSON, so the cause of an incompatibility can be classified
+    let check = diagnostics::with_json_message_format(check).unwrap_or_else(|| check.to_vec());
+
+    let command = ToolchainCommand {
+        args: &check,
+        dir,
+        target_dir,
+    };
+
+    reporter.report_event(CheckMethod::new(
+        toolchain.to_owned(),
+        provider.method(toolchain, &command),
+    ))?;
+
+    let output =
+        provider
+            .run(toolchain, &command)
+            .map_err(|_| CargoMSRVError::UnableToRunCheck {
+                command: check.join(" "),
+                cwd: dir.to_path_buf(),
+            })?;
+
+    if output.status.success() {
+        Ok(Compatibility::new_success(toolchain.to_owned()))
+    } else {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let command = check.join(" ");
+
+        info!(
+            ?toolchain,
+            %stderr,
+            cmd = command.as_str(),
+            "try_building run failed"
+        );
+
+        let diagnosis = diagnostics::diagnose(&stdout, &stderr);
+        info!(?diagnosis, "classified incompatibility");
+
+        Ok(Compatibility::new_diagnosed_failure(
+            toolchain.to_owned(),
+            diagnostics::error_message(&stdout, &stderr),
+            diagnosis,
+        ))
+    }
+}
+
+fn report_outcome(
+    reporter: &impl Reporter,
+    outcome: &Compatibility,
+    no_error_report: bool,
+) -> TResult<()> {
+    match outcome {
+        Compatibility::Compatible(outcome) => {
+            // report compatibility with this toolchain
+            reporter.report_event(CheckResult::compatible(outcome.toolchain_spec.to_owned()))?
+        }
+        Compatibility::Incompatible(outcome) if no_error_report => {
+            // report incompatibility with this toolchain
+            reporter.report_event(
+                CheckResult::incompatible(outcome.toolchain_spec.to_owned(), None)
+                    .with_diagnosis(outcome.diagnosis.clone()),
+            )?
+        }
+        Compatibility::Incompatible(outcome) => {
+            // report incompatibility with this toolchain
+            reporter.report_event(
+                CheckResult::incompatible(
+                    outcome.toolchain_spec.to_owned(),
+                    Some(outcome.error_message.clone()),
+                )
+                .with_diagnosis(outcome.diagnosis.clone()),
+            )?
+        }
+    };
+
+    Ok(())
+}
+
+/// Creates a lockfile handle, iff the lockfile exists and the user opted
+/// to ignore it.
+fn create_lockfile_handle(
+    ignore_lockfile: bool,
+    env: &EnvironmentContext,
+) -> Result<Option<LockfileHandler>, LockfileHandlerError> {
+    ignore_lockfile
+        .then(|| env.lock())
+        .filter(|lockfile| lockfile.is_file())
+        .map(LockfileHandler::try_new)
+        .transpose()
+}
+
+fn remove_lockfile(lock_file: &Utf8Path) -> TResult<()> {
+    if lock_file.is_file() {
+        std::fs::remove_file(lock_file).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::RemoveFile(lock_file.to_path_buf()),
+        })?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Settings<'env> {
+    ignore_lockfile: bool,
+    resolve_msrv_lockfile: bool,
+    no_check_feedback: bool,
+    skip_unavailable_toolchains: bool,
+
+    environment: &'env EnvironmentContext,
+    check_cmd: RunCommand,
+}
+
+impl Settings<'_> {
+    pub fn ignore_lockfile(&self) -> bool {
+        self.ignore_lockfile
+    }
+
+    pub fn resolve_msrv_lockfile(&self) -> bool {
+        self.resolve_msrv_lockfile
+    }
+
+    /// The check command, as part of the key of a cached outcome. Outcomes of checks against
+    /// a resolved lockfile are kept apart from those against the lockfile of the crate.
+    pub fn cache_key_command(&self) -> Vec<String> {
+        let mut command = self.check_cmd.components().to_vec();
+
+        if self.resolve_msrv_lockfile {
+            command.push("--resolve-msrv-lockfile".to_string());
+        }
+
+        command
+    }
+
+    /// A fingerprint of the inputs of the check, or `None` if these can't be fingerprinted, in
+    /// which case the cache is not used.
+    pub fn fingerprint(&self) -> Option<SourceFingerprint> {
+        // the lockfile of the crate isn't used when a lockfile is resolved for each toolchain
+        let ignore_lockfile = self.ignore_lockfile || self.resolve_msrv_lockfile;
+
+        SourceFingerprint::of(self.environment, ignore_lockfile)
+            .inspect_err(|err| info!(%err, "unable to fingerprint sources, not using the cache"))
+            .ok()
+    }
+
+    pub fn no_check_feedback(&self) -> bool {
+        self.no_check_feedback
+    }
+
+    pub fn skip_unavailable_toolchains(&self) -> bool {
+        self.skip_unavailable_toolchains
+    }
+
+    pub fn crate_root_path(&self) -> &Utf8Path {
+        self.environment.root()
+    }
+
+    pub fn lockfile_path(&self) -> Utf8PathBuf {
+        self.environment.lock()
+    }
+}
+
+#[derive(Debug)]
+pub struct RunCommand {
+    command: Vec<String>,
+}
+
+impl RunCommand {
+    pub fn from_cargo_command(cargo_command: CargoCommand) -> Self {
+        Self {
+            command: cargo_command.into_args(),
+        }
+    }
+
+    pub fn custom(command: Vec<String>) -> Self {
+        Self { command }
+    }
+
+    pub fn components(&self) -> &[String] {
+        self.command.as_ref()
+    }
+}
@@ -0,0 +1,239 @@
+//! Suggest downgrades of the dependencies which raise the MSRV above a target MSRV.
+
+use crate::dependency_graph::DependencyGraph;
+use crate::dependency_graph::registry_index::IndexedVersion;
+use crate::reporter::event::DowngradeSuggestion;
+use cargo_metadata::{DependencyKind, Package};
+use cargo_msrv_types::BareVersion;
+use petgraph::Direction;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::Bfs;
+
+/// For each registry dependency with a `rust-version` greater than the target MSRV, find the
+/// newest older version with a `rust-version` of at most the target MSRV, which is accepted by
+/// the version requirements of its dependents.
+///
+/// The `versions` of a crate are provided by the registry index.
+pub fn suggest_downgrades(
+    graph: &DependencyGraph,
+    target_msrv: &BareVersion,
+    versions: impl Fn(&str) -> Vec<IndexedVersion>,
+) -> Vec<DowngradeSuggestion> {
+    let Some(&root) = graph.index().get(graph.root_crate()) else {
+        return vec![];
+    };
+
+    let packages = graph.packages();
+    let mut bfs = Bfs::new(packages, NodeIndex::new(root));
+    let mut dependencies = Vec::new();
+
+    while let Some(node) = bfs.next(packages) {
+        if node.index() != root {
+            dependencies.push(node);
+        }
+    }
+
+    dependencies
+        .iter()
+        .filter_map(|&node| {
+            let package = &packages[node];
+            let rust_version = package.rust_version.as_ref()?;
+
+            (is_registry_package(package) && !target_msrv.is_at_most(rust_version))
+                .then(|| (node, rust_version.clone()))
+        })
+        .map(|(node, rust_version)| {
+            let package = &packages[node];
+            let dependents = packages
+                .neighbors_directed(node, Direction::Incoming)
+                .map(|dependent| &packages[dependent])
+                .collect::<Vec<_>>();
+
+            let downgrade = versions(&package.name)
+                .into_iter()
+                .filter(|candidate| !candidate.yanked && candidate.version < package.version)
+                .filter(|candidate| {
+                    candidate
+                        .rust_version
+                        .as_ref()
+                        .is_some_and(|version| target_msrv.is_at_most(&version.to_semver_version()))
+                })
+                .filter(|candidate| is_accepted_by(&dependents, package, candidate))
+                .max_by(|lhs, rhs| lhs.version.cmp(&rhs.version));
+
+            // When multiple versions of a crate are in the tree, the version to update is named
+            let is_ambiguous = dependencies
+                .iter()
+                .filter(|&&other| packages[other].name == package.name)
+                .count()
+                > 1;
+            let spec = if is_ambiguous {
+                format!("{}@{}", package.name, package.version)
+            } else {
+                package.name.to_string()
+            };
+
+            DowngradeSuggestion {
+                dependency: package.name.to_string(),
+                version: package.version.clone(),
+                rust_version,
+                command: downgrade.as_ref().map(|downgrade| {
+                    format!("cargo update -p {} --precise {}", spec, downgrade.version)
+                }),
+                suggested_version: downgrade.as_ref().map(|d| d.version.clone()),
+                suggested_rust_version: downgrade.and_then(|d| d.rust_version),
+            }
+        })
+        .collect()
+}
+
+/// Only dependencies from a registry can be downgraded to a version from the registry index.
+fn is_registry_package(package: &Package) -> bool {
+    package.source.as_ref().is_some_and(|source| {
+        source.repr.starts_with("registry+") || source.repr.starts_with("sparse+")
+    })
+}
+
+/// Whether the version requirements of each of the dependents accept the candidate version.
+fn is_accepted_by(dependents: &[&Package], package: &Package, candidate: &IndexedVersion) -> bool {
+    dependents
+        .iter()
+        .flat_map(|dependent| &dependent.dependencies)
+        .filter(|dependency| dependency.name == package.name.as_str())
+        .filter(|dependency| dependency.kind != DependencyKind::Development)
+        .all(|dependency| dependency.req.matches(&candidate.version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    const REGISTRY: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+    fn package(
+        name: &str,
+        version: &str,
+        rust_version: Option<&str>,
+        dependencies: &[(&str, &str)],
+    ) -> Package {
+        let dependencies = dependencies
+            .iter()
+            .map(|(name, req)| {
+                serde_json::json!({
+                    "name": name,
+                    "req": req,
+                    "kind": null,
+                    "optional": false,
+                    "uses_default_features": true,
+                    "features": [],
+                    "target": null,
+                    "rename": null,
+                    "registry": null,
+                    "source": REGISTRY,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "version": version,
+            "id": format!("{}@{}", name, version),
+            "source": (name != "root").then_some(REGISTRY),
+            "dependencies": dependencies,
+            "targets": [],
+            "features": {},
+            "manifest_path": format!("/{}/Cargo.toml", name),
+            "rust_version": rust_version,
+        }))
+        .unwrap()
+    }
+
+    // root → a (1.74) → b (1.60)
+    fn graph() -> DependencyGraph {
+        let packages = [
+            package("root", "0.1.0", None, &[("a", "^1.1")]),
+            package("a", "1.3.0", Some("1.74"), &[("b", "^2")]),
+            package("b", "2.0.0", Some("1.60"), &[]),
+        ];
+
+        let mut graph = DependencyGraph::with_capacity(packages[0].id.clone(), packages.len());
+        for package in packages {
+            graph.add_package(package);
+        }
+
+        let index = |name: &str| {
+            graph
+                .index()
+                .iter()
+                .find(|(id, _)| id.repr.starts_with(&format!("{}@", name)))
+                .map(|(_, &index)| index)
+                .unwrap()
+        };
+
+        let edges = [("root", "a"), ("a", "b")].map(|(from, to)| (index(from), index(to)));
+        for (from, to) in edges {
+            graph.add_dependency(from, to);
+        }
+
+        graph
+    }
+
+    fn indexed(version: &str, rust_version: Option<&str>, yanked: bool) -> IndexedVersion {
+        IndexedVersion {
+            version: semver::Version::parse(version).unwrap(),
+            rust_version: rust_version.map(|v| v.parse().unwrap()),
+            yanked,
+        }
+    }
+
+    fn versions_of_a(name: &str) -> Vec<IndexedVersion> {
+        match name {
+            "a" => vec![
+                indexed("1.0.0", Some("1.56"), false),
+                indexed("1.1.0", Some("1.65"), false),
+                indexed("1.1.1", Some("1.68"), true),
+                indexed("1.2.0", Some("1.72"), false),
+                indexed("1.3.0", Some("1.74"), false),
+            ],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn suggests_newest_version_within_target_msrv() {
+        let suggestions =
+            suggest_downgrades(&graph(), &BareVersion::TwoComponents(1, 70), versions_of_a);
+
+        assert_eq!(
+            suggestions,
+            vec![DowngradeSuggestion {
+                dependency: "a".to_string(),
+                version: semver::Version::new(1, 3, 0),
+                rust_version: semver::Version::new(1, 74, 0),
+                suggested_version: Some(semver::Version::new(1, 1, 0)),
+                suggested_rust_version: Some(BareVersion::TwoComponents(1, 65)),
+                command: Some("cargo update -p a --precise 1.1.0".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn respects_version_requirements_of_dependents() {
+        // 1.0.0 is within the target MSRV, but isn't accepted by the `^1.1` requirement
+        let suggestions =
+            suggest_downgrades(&graph(), &BareVersion::TwoComponents(1, 60), versions_of_a);
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].suggested_version, None);
+        assert_eq!(suggestions[0].command, None);
+    }
+
+    #[test]
+    fn nothing_to_downgrade_within_target_msrv() {
+        let suggestions =
+            suggest_downgrades(&graph(), &BareVersion::TwoComponents(1, 74), versions_of_a);
+
+        assert!(suggestions.is_empty());
+    }
+}
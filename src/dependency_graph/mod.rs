@@ -1,3 +1,5 @@
+pub(crate) mod downgrades;
+pub(crate) mod registry_index;
 pub(crate) mod resolver;
 
 pub use cargo_msrv_manifest::DependencyGraph;
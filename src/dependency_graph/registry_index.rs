@@ -0,0 +1,215 @@
+//! Read the versions of a crate from the local cache of the cargo registry index.
+//!
+//! Cargo caches the index entries of each crate it resolved, in
+//! `$CARGO_HOME/registry/index/<registry>/.cache/<prefix>/<name>`. Reading this cache requires
+//! no network access, but only knows about crates which were resolved before.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_msrv_types::BareVersion;
+use rust_releases::semver;
+use std::fs;
+use std::str::FromStr;
+
+/// The version of the cache file format which we can read.
+const CACHE_VERSION: u8 = 3;
+
+/// A version of a crate, as listed by the registry index.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexedVersion {
+    pub version: semver::Version,
+    pub rust_version: Option<BareVersion>,
+    pub yanked: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct IndexEntry {
+    vers: semver::Version,
+    #[serde(default)]
+    rust_version: Option<String>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// The locally cached registry indices.
+#[derive(Debug)]
+pub struct RegistryIndexCache {
+    registries: Vec<Utf8PathBuf>,
+}
+
+impl RegistryIndexCache {
+    /// The registry index caches of the cargo home directory, as given by `CARGO_HOME`, or
+    /// `~/.cargo` otherwise.
+    pub fn from_cargo_home() -> Self {
+        let cargo_home = std::env::var("CARGO_HOME")
+            .ok()
+            .map(Utf8PathBuf::from)
+            .or_else(|| {
+                dirs::home_dir()
+                    .and_then(|home| Utf8PathBuf::from_path_buf(home).ok())
+                    .map(|home| home.join(".cargo"))
+            });
+
+        match cargo_home {
+            Some(cargo_home) => Self::in_dir(cargo_home.join("registry").join("index")),
+            None => Self { registries: vec![] },
+        }
+    }
+
+    /// The caches of each registry index in the given directory.
+    pub fn in_dir(dir: impl AsRef<Utf8Path>) -> Self {
+        let registries = fs::read_dir(dir.as_ref())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| Utf8PathBuf::from_path_buf(entry.path()).ok())
+            .map(|registry| registry.join(".cache"))
+            .filter(|cache| cache.is_dir())
+            .collect();
+
+        Self { registries }
+    }
+
+    /// The cached versions of the given crate, or an empty list if the crate is not cached.
+    pub fn versions(&self, name: &str) -> Vec<IndexedVersion> {
+        let path = prefix_path(name);
+
+        self.registries
+            .iter()
+            .find_map(|registry| fs::read(registry.join(&path)).ok())
+            .map(|contents| parse_cache_file(&contents))
+            .unwrap_or_default()
+    }
+}
+
+/// The path of the index file of a crate, relative to the root of the index.
+fn prefix_path(name: &str) -> Utf8PathBuf {
+    let name = name.to_lowercase();
+
+    match name.len() {
+        1 => Utf8PathBuf::from("1").join(&name),
+        2 => Utf8PathBuf::from("2").join(&name),
+        3 => Utf8PathBuf::from("3").join(&name[..1]).join(&name),
+        _ => Utf8PathBuf::from(&name[..2]).join(&name[2..4]).join(&name),
+    }
+}
+
+/// Parse a cache file, which consists of a header, followed by null terminated pairs of a
+/// version and its index entry, as JSON.
+///
+/// The header consists of the cache version (1 byte), the index format version (4 bytes), and
+/// the null terminated revision of the index.
+fn parse_cache_file(contents: &[u8]) -> Vec<IndexedVersion> {
+    let Some((&CACHE_VERSION, rest)) = contents.split_first() else {
+        info!("unsupported registry index cache version");
+        return vec![];
+    };
+
+    let Some(rest) = rest.get(4..) else {
+        return vec![];
+    };
+
+    rest.split(|&byte| byte == 0)
+        // skip the revision of the index
+        .skip(1)
+        // skip the versions, which precede each entry
+        .skip(1)
+        .step_by(2)
+        .filter_map(|entry| serde_json::from_slice::<IndexEntry>(entry).ok())
+        .map(|entry| IndexedVersion {
+            version: entry.vers,
+            rust_version: entry
+                .rust_version
+                .and_then(|rust_version| BareVersion::from_str(&rust_version).ok()),
+            yanked: entry.yanked,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_file(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut contents = vec![CACHE_VERSION, 2, 0, 0, 0];
+        contents.extend_from_slice(b"Unknown\0");
+
+        for (version, entry) in entries {
+            contents.extend_from_slice(version.as_bytes());
+            contents.push(0);
+            contents.extend_from_slice(entry.as_bytes());
+            contents.push(0);
+        }
+
+        contents
+    }
+
+    #[yare::parameterized(
+        one = { "a", "1/a" },
+        two = { "ab", "2/ab" },
+        three = { "abc", "3/a/abc" },
+        four = { "abcd", "ab/cd/abcd" },
+        long = { "Serde_Json", "se/rd/serde_json" },
+    )]
+    fn prefix(name: &str, expected: &str) {
+        assert_eq!(prefix_path(name), Utf8PathBuf::from(expected));
+    }
+
+    #[test]
+    fn parses_entries() {
+        let contents = cache_file(&[
+            (
+                "1.0.0",
+                r#"{"name":"a","vers":"1.0.0","deps":[],"yanked":false}"#,
+            ),
+            (
+                "1.1.0",
+                r#"{"name":"a","vers":"1.1.0","deps":[],"yanked":true,"rust_version":"1.60"}"#,
+            ),
+        ]);
+
+        assert_eq!(
+            parse_cache_file(&contents),
+            vec![
+                IndexedVersion {
+                    version: semver::Version::new(1, 0, 0),
+                    rust_version: None,
+                    yanked: false,
+                },
+                IndexedVersion {
+                    version: semver::Version::new(1, 1, 0),
+                    rust_version: Some(BareVersion::TwoComponents(1, 60)),
+                    yanked: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_cache_version() {
+        let mut contents = cache_file(&[("1.0.0", r#"{"vers":"1.0.0"}"#)]);
+        contents[0] = 1;
+
+        assert!(parse_cache_file(&contents).is_empty());
+    }
+
+    #[test]
+    fn reads_versions_from_registry_directory() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let index = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+        let cache = index
+            .join("index.crates.io-0123456789abcdef")
+            .join(".cache");
+
+        fs::create_dir_all(cache.join("se/rd")).unwrap();
+        fs::write(
+            cache.join("se/rd/serde"),
+            cache_file(&[("1.0.0", r#"{"vers":"1.0.0"}"#)]),
+        )
+        .unwrap();
+
+        let registry = RegistryIndexCache::in_dir(&index);
+
+        assert_eq!(registry.versions("serde").len(), 1);
+        assert!(registry.versions("unknown").is_empty());
+    }
+}
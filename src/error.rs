@@ -7,7 +7,7 @@ use crate::cli::rust_releases_opts::ParseEditionOrVersionError;
 use crate::manifest::ManifestParseError;
 use cargo_msrv_context::types::{
     ParseEditionError, ParseListMsrvVariantError, ParseLogLevelError, ParseOutputFormatError,
-    ParseReleaseSourceError, ParseTracingTargetOptionError,
+    ParseReleaseSourceError, ParseTracingTargetOptionError, ReleaseSource,
 };
 use cargo_msrv_types::{BareVersion, NoVersionMatchesManifestMsrvError};
 use rust_releases::Release;
@@ -31,6 +31,12 @@ pub enum CargoMSRVError {
     #[error(transparent)]
     Context(#[from] ContextError),
 
+    #[error(
+        "The environment is not ready for cargo-msrv, since the following check(s) failed: {}",
+        .0.join(", ")
+    )]
+    DoctorChecksFailed(Vec<&'static str>),
+
     #[error(transparent)]
     Env(#[from] env::VarError),
 
@@ -58,15 +64,36 @@ pub enum CargoMSRVError {
     #[error(transparent)]
     ManifestParseError(#[from] ManifestParseError),
 
+    #[error("Unable to determine the cache folder of the current user")]
+    NoCacheFolder,
+
+    #[error(
+        "No cached copy of the Rust release index (source: {release_source}) was found at '{path}', run without --offline to fetch it"
+    )]
+    NoCachedReleaseIndex {
+        release_source: ReleaseSource,
+        path: Utf8PathBuf,
+    },
+
+    #[error(
+        "No installed stable toolchains were found. Run `rustup toolchain list` for an overview of installed toolchains."
+    )]
+    NoInstalledToolchains,
+
+    #[error("Unable to read the Rust release index from '{path}': {reason}")]
+    InvalidReleaseIndexFile { path: Utf8PathBuf, reason: String },
+
+    #[error(
+        "Searching the MSRV of feature sets requires a single package, but {selected} packages were selected. Use `--package` to select one."
+    )]
+    FeatureSetsRequireSinglePackage { selected: usize },
+
     #[error("No crate root found for given crate")]
     NoCrateRootFound,
 
     #[error(transparent)]
     NoToolchainsToTry(#[from] NoToolchainsToTryError),
 
-    #[error("Unable to set MSRV for workspace, try setting it for individual packages instead.")]
-    WorkspaceFound,
-
     #[error(transparent)]
     NoVersionMatchesManifestMSRV(#[from] NoVersionMatchesManifestMsrvError),
 
@@ -104,6 +131,12 @@ pub enum CargoMSRVError {
     #[error(transparent)]
     SemverError(#[from] rust_releases::semver::Error),
 
+    #[error(
+        "Unable to uninstall each toolchain installed during the run:\n{}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+    )]
+    UninstallToolchains(Vec<CargoMSRVError>),
+
     #[error(transparent)]
     SetMsrv(#[from] SetMsrvError),
 
@@ -124,6 +157,29 @@ pub enum CargoMSRVError {
     )]
     ToolchainNotInstalled,
 
+    #[error(
+        "Rust {version} is not installed with rustup. The 'rustup-installed' release source only checks the installed toolchains, and doesn't install any; run `rustup toolchain install {version}` to install it"
+    )]
+    ToolchainNotInstalledWithRustup {
+        version: rust_releases::semver::Version,
+    },
+
+    #[error(
+        "Toolchain '{version}' could not be found in '{dir}', expected 'cargo' and 'rustc' at '{dir}/{version}/bin'"
+    )]
+    ToolchainNotInDirectory {
+        version: rust_releases::semver::Version,
+        dir: Utf8PathBuf,
+    },
+
+    #[error(
+        "Target '{target}' is not available for the toolchain at '{toolchain_dir}', expected '{toolchain_dir}/lib/rustlib/{target}'"
+    )]
+    TargetNotInToolchainDirectory {
+        target: String,
+        toolchain_dir: Utf8PathBuf,
+    },
+
     #[error(
         "The given target could not be found. Run `rustup target list` for an overview of available toolchains."
     )]
@@ -206,6 +262,16 @@ pub enum SetMsrvError {
         "Unable to set the MSRV in the 'package.metadata' table: 'package.metadata' is not a table"
     )]
     NotATable,
+
+    #[error(
+        "Unable to set the MSRV in the 'workspace.package' table: 'workspace.package' is not a table"
+    )]
+    WorkspacePackageNotATable,
+
+    #[error(
+        "Unable to set the MSRV inherited by '{0}': no workspace manifest found in its parent directories"
+    )]
+    WorkspaceManifestNotFound(Utf8PathBuf),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -267,6 +333,8 @@ pub enum RustupError {
     Install(#[from] RustupInstallError),
     AddComponent(#[from] RustupAddComponentError),
     AddTarget(#[from] RustupAddTargetError),
+    ListToolchains(#[from] RustupListToolchainsError),
+    Uninstall(#[from] RustupUninstallError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -306,6 +374,26 @@ pub struct RustupAddTargetError {
     pub stderr: String,
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Unable to list the installed toolchains, rustup reported:\n    {}",
+    stderr.trim_end().lines().collect::<Vec<_>>().join("\n    ").dimmed()
+)]
+pub struct RustupListToolchainsError {
+    pub stderr: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Unable to uninstall toolchain '{}', rustup reported:\n    {}",
+    toolchain,
+    stderr.trim_end().lines().collect::<Vec<_>>().join("\n    ").dimmed()
+)]
+pub struct RustupUninstallError {
+    pub toolchain: String,
+    pub stderr: String,
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("Unable to set cleanup handler for lockfile")]
 pub struct LockfileHandlerError;
@@ -4,6 +4,7 @@ pub struct CargoCommand {
     all_features: bool,
     no_default_features: bool,
     target: Option<String>,
+    package: Option<String>,
 }
 
 impl CargoCommand {
@@ -31,6 +32,12 @@ impl CargoCommand {
         self
     }
 
+    /// Set the package to be forwarded as `cargo <cmd> --package`
+    pub fn package(mut self, package: Option<impl ToString>) -> Self {
+        self.package = package.map(|p| p.to_string());
+        self
+    }
+
     /// Intended to be used in conjunction with [`RunCommand`] and/or [`RustupCommand`].
     ///
     /// [`RunCommand`]: crate::compatibility::RunCommand
@@ -48,6 +55,11 @@ impl CargoCommand {
         // This value does open the path to use cargo build for Rust < 1.16
         args.extend_from_slice(&["cargo".to_string(), "check".to_string()]);
 
+        if let Some(package) = self.package {
+            args.push("--package".to_string());
+            args.push(package);
+        }
+
         if let Some(features) = self.features {
             let features = features.join(",");
 
@@ -148,6 +160,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_package_some() {
+        let cargo_command = CargoCommand::default();
+        let cargo_command = cargo_command.package(Some("pika"));
+        assert_eq!(
+            cargo_command.into_args().join(" "),
+            "cargo check --package pika".to_string()
+        );
+    }
+
     #[test]
     fn combination_of_everything() {
         let cargo_command = CargoCommand::default();
@@ -155,12 +177,14 @@ mod tests {
             .features(Some(vec!["pika".to_string(), "chu".to_string()]))
             .all_features(true)
             .no_default_features(true)
-            .target(Some("pickme"));
+            .target(Some("pickme"))
+            .package(Some("chris"));
 
         let cmd = cargo_command.into_args().join(" ");
         assert!(cmd.contains("--all-features"));
         assert!(cmd.contains("--features pika,chu"));
         assert!(cmd.contains("--no-default-features"));
         assert!(cmd.contains("--target pickme"));
+        assert!(cmd.contains("--package chris"));
     }
 }
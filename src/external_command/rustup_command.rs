@@ -48,6 +48,11 @@ impl RustupCommand {
         self
     }
 
+    pub fn with_env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.command.env(key, value);
+        self
+    }
+
     pub fn with_stdout(mut self) -> Self {
         self.stdout = Stdio::piped();
         self
@@ -76,6 +81,20 @@ impl RustupCommand {
         self.execute(OsStr::new("component"))
     }
 
+    pub fn toolchain(self) -> TResult<RustupOutput> {
+        self.execute(OsStr::new("toolchain"))
+    }
+
+    /// Execute `rustup which [...]`
+    pub fn which(self) -> TResult<RustupOutput> {
+        self.execute(OsStr::new("which"))
+    }
+
+    /// Execute `rustup --version`
+    pub fn version(self) -> TResult<RustupOutput> {
+        self.execute(OsStr::new("--version"))
+    }
+
     /// Execute a given `rustup` command.
     ///
     /// See also:
@@ -143,4 +162,8 @@ impl RustupOutput {
     pub fn exit_status(&self) -> std::process::ExitStatus {
         self.output.status
     }
+
+    pub fn into_output(self) -> std::process::Output {
+        self.output
+    }
 }
@@ -21,16 +21,32 @@ extern crate core;
 extern crate tracing;
 
 pub use crate::outcome::Compatibility;
-pub use crate::sub_command::{Find, List, Set, Show, SubCommand, Verify};
+pub use crate::sub_command::{
+    Cache, Doctor, Find, FindFeatureSets, FindTargets, FindWorkspace, List, PrintConfig, Set, Show,
+    SubCommand, Toolchains, Verify, VerifyWorkspace,
+};
+use cargo_msrv_context::types::ReleaseSource;
 pub use cargo_msrv_context::types::{OutputFormat, TracingTargetOption};
 pub use cargo_msrv_context::{Context, TracingOptions};
+use cargo_msrv_context::{
+    EnvironmentContext, FindContext, RustReleasesContext, TargetDirCleanup, ToolchainContext,
+    ToolchainSource, VerifyContext,
+};
 
-use crate::compatibility::{RunCommandProvider, RustupToolchainCheck};
+use crate::compatibility::{
+    CompatibilityCache, PackageRunCommandProvider, RunCommand, RunCommandProvider, ToolchainCheck,
+    ToolchainTargetDirs, WorkspaceSnapshots,
+};
 use crate::error::{CargoMSRVError, TResult};
 use crate::reporter::Reporter;
 use crate::reporter::event::{Meta, SelectedPackages, SubcommandInit};
+use rust::installed_toolchains::{InstalledToolchains, ToolchainRecord};
 use rust::release_index;
+use rust::toolchain_provider::{
+    DirectoryToolchainProvider, RustupToolchainProvider, ToolchainProvider,
+};
 use rust_releases::semver;
+use std::sync::Arc;
 
 pub use cargo_msrv_cli::cli;
 pub use cargo_msrv_context::context;
@@ -73,44 +89,263 @@ pub fn run_app(ctx: &Context, reporter: &impl Reporter) -> TResult<()> {
     reporter.report_event(SubcommandInit::new(ctx.reporting_name()))?;
 
     match ctx {
+        Context::Cache(ctx) => {
+            let cache =
+                CompatibilityCache::in_user_cache_dir().ok_or(CargoMSRVError::NoCacheFolder)?;
+            Cache::new(cache).run(ctx, reporter)?;
+        }
+        Context::Doctor(ctx) => {
+            Doctor.run(ctx, reporter)?;
+        }
+        Context::Find(ctx) if !ctx.additional_targets.is_empty() => {
+            let index = release_index::fetch_index(reporter, &ctx.rust_releases)?;
+            let run = CheckRun::new(ctx.into())?;
+            let provider = run.toolchain_provider();
+
+            let runner_factory = |run_command: RunCommand| {
+                compatibility_check(reporter, &run, provider.as_ref(), run_command)
+            };
+            let result = FindTargets::new(&index, runner_factory).run(ctx, reporter);
+            run.finish(reporter, result)?;
+        }
+        Context::Find(ctx) if ctx.feature_sets.is_some() => {
+            let index = release_index::fetch_index(reporter, &ctx.rust_releases)?;
+            let run = CheckRun::new(ctx.into())?;
+            let provider = run.toolchain_provider();
+
+            let runner_factory = |run_command: RunCommand| {
+                compatibility_check(reporter, &run, provider.as_ref(), run_command)
+            };
+            let result = FindFeatureSets::new(&index, runner_factory).run(ctx, reporter);
+            run.finish(reporter, result)?;
+        }
+        Context::Find(ctx) if ctx.environment.workspace_packages.is_explicit_selection() => {
+            let index = release_index::fetch_index(reporter, &ctx.rust_releases)?;
+            let run = CheckRun::new(ctx.into())?;
+            let provider = run.toolchain_provider();
+
+            let runner_factory = |package: &str| {
+                let run_command = ctx.provide_package_run_command(package);
+                compatibility_check(reporter, &run, provider.as_ref(), run_command)
+            };
+            let result = FindWorkspace::new(&index, runner_factory).run(ctx, reporter);
+            run.finish(reporter, result)?;
+        }
         Context::Find(ctx) => {
-            let index = release_index::fetch_index(reporter, ctx.rust_releases.release_source)?;
-
-            let runner = RustupToolchainCheck::new(
-                reporter,
-                ctx.ignore_lockfile,
-                ctx.no_check_feedback,
-                ctx.skip_unavailable_toolchains,
-                &ctx.environment,
-                ctx.provide_run_command(),
-            );
-            Find::new(&index, runner).run(ctx, reporter)?;
+            let index = release_index::fetch_index(reporter, &ctx.rust_releases)?;
+            let run = CheckRun::new(ctx.into())?;
+            let provider = run.toolchain_provider();
+
+            let runner =
+                compatibility_check(reporter, &run, provider.as_ref(), ctx.provide_run_command());
+            let result = Find::new(&index, runner).run(ctx, reporter);
+            run.finish(reporter, result)?;
         }
         Context::List(ctx) => {
             List.run(ctx, reporter)?;
         }
+        Context::PrintConfig(ctx) => {
+            PrintConfig.run(ctx, reporter)?;
+        }
         Context::Set(ctx) => {
-            let index = release_index::fetch_index(reporter, ctx.rust_releases.release_source).ok();
+            // The index is only used to validate the given MSRV, so failing to fetch it from the
+            // network is not fatal. A local source however is explicitly chosen by the user.
+            let index = match release_index::fetch_index(reporter, &ctx.rust_releases) {
+                Ok(index) => Some(index),
+                Err(_) if ctx.rust_releases.release_source.requires_network() => None,
+                Err(err) => return Err(err),
+            };
             Set::new(index.as_ref()).run(ctx, reporter)?;
         }
         Context::Show(ctx) => {
             Show.run(ctx, reporter)?;
         }
-        Context::Verify(ctx) => {
-            let index = release_index::fetch_index(reporter, ctx.rust_releases.release_source)?;
+        Context::Toolchains(ctx) => {
+            let record =
+                ToolchainRecord::in_user_cache_dir().ok_or(CargoMSRVError::NoCacheFolder)?;
+            Toolchains::new(record).run(ctx, reporter)?;
+        }
+        Context::Verify(ctx) if ctx.environment.workspace_packages.is_explicit_selection() => {
+            let index = release_index::fetch_index(reporter, &ctx.rust_releases)?;
+            let run = CheckRun::new(ctx.into())?;
+            let provider = run.toolchain_provider();
 
-            let runner = RustupToolchainCheck::new(
-                reporter,
-                ctx.ignore_lockfile,
-                ctx.no_check_feedback,
-                false,
-                &ctx.environment,
-                ctx.provide_run_command(),
-            );
+            let runner_factory = |package: &str| {
+                let run_command = ctx.provide_package_run_command(package);
+                compatibility_check(reporter, &run, provider.as_ref(), run_command)
+            };
+            let result = VerifyWorkspace::new(&index, runner_factory).run(ctx, reporter);
+            run.finish(reporter, result)?;
+        }
+        Context::Verify(ctx) => {
+            let index = release_index::fetch_index(reporter, &ctx.rust_releases)?;
+            let run = CheckRun::new(ctx.into())?;
+            let provider = run.toolchain_provider();
 
-            Verify::new(&index, runner).run(ctx, reporter)?;
+            let runner =
+                compatibility_check(reporter, &run, provider.as_ref(), ctx.provide_run_command());
+            let result = Verify::new(&index, runner).run(ctx, reporter);
+            run.finish(reporter, result)?;
         }
     }
 
     Ok(())
 }
+
+/// The provider of the checked toolchains. Only toolchains installed with rustup are recorded,
+/// since toolchains are never installed into a directory of toolchains.
+///
+/// When the releases are the installed toolchains, toolchains are never installed, so the network
+/// is not accessed.
+fn toolchain_provider<'a>(
+    toolchain: &ToolchainContext,
+    rust_releases: &RustReleasesContext,
+    installed_toolchains: &'a InstalledToolchains,
+) -> Box<dyn ToolchainProvider + 'a> {
+    match &toolchain.source {
+        ToolchainSource::Rustup => Box::new(
+            RustupToolchainProvider::new()
+                .with_installed_toolchains(installed_toolchains)
+                .installed_only(rust_releases.release_source == ReleaseSource::RustupInstalled),
+        ),
+        ToolchainSource::Directory(dir) => Box::new(DirectoryToolchainProvider::new(dir)),
+    }
+}
+
+/// The settings of the compatibility checks of a `find` or `verify` run.
+struct CheckSettings<'ctx> {
+    ignore_lockfile: bool,
+    resolve_msrv_lockfile: bool,
+    isolated: bool,
+    target_dir_cleanup: TargetDirCleanup,
+    cleanup_toolchains: bool,
+    cache: bool,
+    no_check_feedback: bool,
+    skip_unavailable_toolchains: bool,
+    rust_releases: &'ctx RustReleasesContext,
+    toolchain: &'ctx ToolchainContext,
+    environment: &'ctx EnvironmentContext,
+}
+
+impl<'ctx> From<&'ctx FindContext> for CheckSettings<'ctx> {
+    fn from(ctx: &'ctx FindContext) -> Self {
+        Self {
+            ignore_lockfile: ctx.ignore_lockfile,
+            resolve_msrv_lockfile: ctx.resolve_msrv_lockfile,
+            isolated: ctx.isolated,
+            target_dir_cleanup: ctx.target_dir_cleanup,
+            cleanup_toolchains: ctx.cleanup_toolchains,
+            cache: ctx.cache,
+            no_check_feedback: ctx.no_check_feedback,
+            skip_unavailable_toolchains: ctx.skip_unavailable_toolchains,
+            rust_releases: &ctx.rust_releases,
+            toolchain: &ctx.toolchain,
+            environment: &ctx.environment,
+        }
+    }
+}
+
+impl<'ctx> From<&'ctx VerifyContext> for CheckSettings<'ctx> {
+    fn from(ctx: &'ctx VerifyContext) -> Self {
+        Self {
+            ignore_lockfile: ctx.ignore_lockfile,
+            resolve_msrv_lockfile: ctx.resolve_msrv_lockfile,
+            isolated: ctx.isolated,
+            target_dir_cleanup: ctx.target_dir_cleanup,
+            cleanup_toolchains: ctx.cleanup_toolchains,
+            cache: ctx.cache,
+            no_check_feedback: ctx.no_check_feedback,
+            skip_unavailable_toolchains: false,
+            rust_releases: &ctx.rust_releases,
+            toolchain: &ctx.toolchain,
+            environment: &ctx.environment,
+        }
+    }
+}
+
+/// What the compatibility checks of a `find` or `verify` run share, and which is cleaned up once
+/// the run is done.
+struct CheckRun<'ctx> {
+    settings: CheckSettings<'ctx>,
+    target_dirs: ToolchainTargetDirs,
+    installed_toolchains: InstalledToolchains,
+    snapshots: Arc<WorkspaceSnapshots>,
+}
+
+impl<'ctx> CheckRun<'ctx> {
+    fn new(settings: CheckSettings<'ctx>) -> TResult<Self> {
+        Ok(Self {
+            target_dirs: ToolchainTargetDirs::new(
+                settings.environment,
+                settings.target_dir_cleanup,
+            )?,
+            installed_toolchains: InstalledToolchains::new(settings.cleanup_toolchains),
+            snapshots: Arc::new(WorkspaceSnapshots::new()),
+            settings,
+        })
+    }
+
+    fn toolchain_provider(&self) -> Box<dyn ToolchainProvider + '_> {
+        toolchain_provider(
+            self.settings.toolchain,
+            self.settings.rust_releases,
+            &self.installed_toolchains,
+        )
+    }
+
+    /// Clean up the target directories and the installed toolchains, once the run is done.
+    ///
+    /// Both are cleaned up, regardless of the outcome of the run. If the run failed, its error
+    /// is returned, and errors of the cleanup are only logged, so they don't hide why it failed.
+    fn finish<T>(&self, reporter: &impl Reporter, outcome: TResult<T>) -> TResult<T> {
+        let cleanup = [
+            self.target_dirs.clean_up(reporter),
+            self.installed_toolchains.clean_up(reporter),
+        ];
+
+        match outcome {
+            Ok(value) => cleanup.into_iter().collect::<TResult<()>>().map(|()| value),
+            Err(err) => {
+                for error in cleanup.into_iter().filter_map(Result::err) {
+                    warn!(%error, "unable to clean up after a failed run");
+                }
+
+                Err(err)
+            }
+        }
+    }
+}
+
+/// The compatibility check of the run, which checks with the given command.
+fn compatibility_check<'reporter, 'run, R: Reporter>(
+    reporter: &'reporter R,
+    run: &'run CheckRun<'run>,
+    provider: &'run dyn ToolchainProvider,
+    run_command: RunCommand,
+) -> ToolchainCheck<'reporter, 'run, R> {
+    let settings = &run.settings;
+
+    ToolchainCheck::new(
+        reporter,
+        settings.ignore_lockfile,
+        settings.no_check_feedback,
+        settings.skip_unavailable_toolchains,
+        settings.environment,
+        run_command,
+    )
+    .with_msrv_lockfile(settings.resolve_msrv_lockfile)
+    .with_isolation(settings.isolated)
+    .with_snapshots(Arc::clone(&run.snapshots))
+    .with_target_dirs(&run.target_dirs)
+    .with_toolchain_provider(provider)
+    .with_cache(compatibility_cache(settings.cache))
+}
+
+/// The cache of compatibility check outcomes, if the user opted in to it.
+fn compatibility_cache(cache: bool) -> Option<CompatibilityCache> {
+    if cache {
+        CompatibilityCache::in_user_cache_dir()
+    } else {
+        None
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! [`check`]: crate::compatibility::IsCompatible
 
+use crate::reporter::event::Diagnosis;
 use crate::rust::Toolchain;
 use rust_releases::semver;
 
@@ -20,6 +21,20 @@ impl Compatibility {
         Self::Incompatible(Incompatible {
             toolchain_spec,
             error_message,
+            diagnosis: None,
+        })
+    }
+
+    /// An incompatible outcome, of which the cause was classified.
+    pub fn new_diagnosed_failure(
+        toolchain_spec: Toolchain,
+        error_message: String,
+        diagnosis: Option<Diagnosis>,
+    ) -> Self {
+        Self::Incompatible(Incompatible {
+            toolchain_spec,
+            error_message,
+            diagnosis,
         })
     }
 
@@ -54,6 +69,7 @@ pub struct Compatible {
 pub struct Incompatible {
     pub(crate) toolchain_spec: Toolchain,
     pub(crate) error_message: String,
+    pub(crate) diagnosis: Option<Diagnosis>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,337 @@
+//! The toolchains which were installed by cargo-msrv.
+//!
+//! Each toolchain which rustup reports to have installed, when asked to do so while setting up a
+//! toolchain for a check, is recorded, in the cache directory of the user. Toolchains which were
+//! installed before, or by anything other than cargo-msrv, are never recorded, so they are never
+//! uninstalled by cargo-msrv: neither once the run is done, when `--cleanup-toolchains` is set,
+//! nor by `cargo msrv toolchains prune`.
+
+use crate::error::{IoError, IoErrorSource, RustupError, RustupListToolchainsError};
+use crate::error::{RustupUninstallError, TResult};
+use crate::external_command::rustup_command::RustupCommand;
+use crate::reporter::event::UninstallToolchain;
+use crate::{CargoMSRVError, Reporter};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::collections::BTreeSet;
+use std::fs;
+use std::sync::Mutex;
+
+/// The names of the toolchains installed by cargo-msrv, as named by rustup, one per line.
+#[derive(Debug)]
+pub struct ToolchainRecord {
+    path: Utf8PathBuf,
+}
+
+impl ToolchainRecord {
+    pub fn new(path: impl Into<Utf8PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The record located in the cache directory of the user, if it can be determined.
+    pub fn in_user_cache_dir() -> Option<Self> {
+        dirs::cache_dir()
+            .and_then(|path| Utf8PathBuf::from_path_buf(path).ok())
+            .map(|path| Self::new(path.join("cargo-msrv").join("installed-toolchains")))
+    }
+
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    /// The recorded toolchains. A record which doesn't exist yet is empty.
+    pub fn load(&self) -> TResult<BTreeSet<String>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(BTreeSet::new()),
+            Err(error) => Err(IoError {
+                error,
+                source: IoErrorSource::ReadFile(self.path.clone()),
+            }
+            .into()),
+        }
+    }
+
+    /// Replace the recorded toolchains.
+    pub fn store(&self, toolchains: &BTreeSet<String>) -> TResult<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::CreateDir(dir.to_path_buf()),
+            })?;
+        }
+
+        let contents = toolchains
+            .iter()
+            .map(|name| format!("{}\n", name))
+            .collect::<String>();
+
+        fs::write(&self.path, contents).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::WriteFile(self.path.clone()),
+        })?;
+
+        Ok(())
+    }
+
+    pub fn add<'a>(&self, toolchains: impl IntoIterator<Item = &'a String>) -> TResult<()> {
+        let mut recorded = self.load()?;
+        recorded.extend(toolchains.into_iter().cloned());
+        self.store(&recorded)
+    }
+
+    pub fn remove(&self, toolchain: &str) -> TResult<()> {
+        let mut recorded = self.load()?;
+        recorded.remove(toolchain);
+        self.store(&recorded)
+    }
+}
+
+/// The toolchains installed during a run, which are uninstalled once the run is done, if the
+/// user opted in to it.
+#[derive(Debug)]
+pub struct InstalledToolchains {
+    record: Option<ToolchainRecord>,
+    cleanup: bool,
+    installed: Mutex<BTreeSet<String>>,
+}
+
+impl InstalledToolchains {
+    /// Installed toolchains, recorded in the cache directory of the user.
+    pub fn new(cleanup: bool) -> Self {
+        Self::with_record(ToolchainRecord::in_user_cache_dir(), cleanup)
+    }
+
+    pub fn with_record(record: Option<ToolchainRecord>, cleanup: bool) -> Self {
+        Self {
+            record,
+            cleanup,
+            installed: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Record a toolchain which rustup reported to have installed, by the name it reported.
+    pub fn record(&self, toolchain: String) {
+        let mut installed = self
+            .installed
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // a missing record only means the toolchain can't be pruned later
+        if let Some(record) = &self.record
+            && let Err(err) = record.add([&toolchain])
+        {
+            info!(%err, "unable to record installed toolchain");
+        }
+
+        installed.insert(toolchain);
+    }
+
+    /// The toolchains installed during the run, so far.
+    pub fn installed(&self) -> BTreeSet<String> {
+        self.installed
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Uninstall each toolchain installed during the run, if the user opted in to it, and report
+    /// it.
+    ///
+    /// A toolchain which can't be uninstalled doesn't stop the others from being uninstalled; the
+    /// errors are returned once each toolchain has been tried.
+    pub fn clean_up(&self, reporter: &impl Reporter) -> TResult<()> {
+        if !self.cleanup {
+            return Ok(());
+        }
+
+        let installed = std::mem::take(
+            &mut *self
+                .installed
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+
+        let errors = installed
+            .into_iter()
+            .filter_map(|toolchain| self.uninstall(reporter, toolchain).err())
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CargoMSRVError::UninstallToolchains(errors))
+        }
+    }
+
+    fn uninstall(&self, reporter: &impl Reporter, toolchain: String) -> TResult<()> {
+        uninstall_toolchain(&toolchain)?;
+
+        if let Some(record) = &self.record {
+            record.remove(&toolchain)?;
+        }
+
+        reporter.report_event(UninstallToolchain::new(toolchain))?;
+
+        Ok(())
+    }
+}
+
+/// A toolchain as listed by `rustup toolchain list`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListedToolchain {
+    pub name: String,
+    /// Whether the toolchain is the default toolchain, or the active toolchain in the current
+    /// directory.
+    pub in_use: bool,
+}
+
+/// List the toolchains installed with rustup.
+pub fn list_installed_toolchains() -> TResult<Vec<ListedToolchain>> {
+    let rustup = RustupCommand::new()
+        .with_args(["list"])
+        .with_stdout()
+        .with_stderr()
+        .toolchain()?;
+
+    if !rustup.exit_status().success() {
+        return Err(CargoMSRVError::RustupError(
+            RustupListToolchainsError {
+                stderr: rustup.stderr().to_string(),
+            }
+            .into(),
+        ));
+    }
+
+    Ok(parse_toolchain_list(rustup.stdout()))
+}
+
+/// Parse the output of `rustup toolchain list`, e.g. `stable-x86_64-unknown-linux-gnu (default)`.
+fn parse_toolchain_list(stdout: &str) -> Vec<ListedToolchain> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("no installed toolchains"))
+        .filter_map(|line| {
+            let (name, annotations) = line.split_once(' ').unwrap_or((line, ""));
+
+            (!name.is_empty()).then(|| ListedToolchain {
+                name: name.to_string(),
+                in_use: annotations.contains("default") || annotations.contains("active"),
+            })
+        })
+        .collect()
+}
+
+/// Uninstall a toolchain with rustup, by the name listed by `rustup toolchain list`.
+pub fn uninstall_toolchain(toolchain: &str) -> TResult<()> {
+    info!(toolchain, "uninstalling toolchain");
+
+    let rustup = RustupCommand::new()
+        .with_stdout()
+        .with_stderr()
+        .with_args(["uninstall", toolchain])
+        .toolchain()?;
+
+    if !rustup.exit_status().success() {
+        return Err(CargoMSRVError::RustupError(RustupError::Uninstall(
+            RustupUninstallError {
+                toolchain: toolchain.to_string(),
+                stderr: rustup.stderr().to_string(),
+            },
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+
+    fn names(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_toolchain_list() {
+        let stdout = "stable-x86_64-unknown-linux-gnu (active, default)\n\
+            1.70.0-x86_64-unknown-linux-gnu\n\
+            nightly-x86_64-unknown-linux-gnu (default)\n";
+
+        assert_eq!(
+            parse_toolchain_list(stdout),
+            vec![
+                ListedToolchain {
+                    name: "stable-x86_64-unknown-linux-gnu".to_string(),
+                    in_use: true,
+                },
+                ListedToolchain {
+                    name: "1.70.0-x86_64-unknown-linux-gnu".to_string(),
+                    in_use: false,
+                },
+                ListedToolchain {
+                    name: "nightly-x86_64-unknown-linux-gnu".to_string(),
+                    in_use: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_empty_toolchain_list() {
+        assert!(parse_toolchain_list("no installed toolchains\n").is_empty());
+    }
+
+    #[test]
+    fn missing_record_is_empty() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(tmp.path().join("installed-toolchains")).unwrap();
+
+        assert!(ToolchainRecord::new(path).load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn adds_and_removes_recorded_toolchains() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(tmp.path().join("dir").join("record")).unwrap();
+        let record = ToolchainRecord::new(path);
+
+        record.add(&names(&["1.70.0-x", "1.71.0-x"])).unwrap();
+        record.add(&names(&["1.71.0-x", "1.72.0-x"])).unwrap();
+        record.remove("1.70.0-x").unwrap();
+
+        assert_eq!(record.load().unwrap(), names(&["1.71.0-x", "1.72.0-x"]));
+    }
+
+    #[test]
+    fn records_installed_toolchains() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(tmp.path().join("record")).unwrap();
+        let installed = InstalledToolchains::with_record(Some(ToolchainRecord::new(&path)), false);
+
+        installed.record("1.70.0-x".to_string());
+        installed.record("1.71.0-x".to_string());
+
+        let expected = names(&["1.70.0-x", "1.71.0-x"]);
+        assert_eq!(installed.installed(), expected);
+        assert_eq!(ToolchainRecord::new(path).load().unwrap(), expected);
+    }
+
+    #[test]
+    fn keeps_installed_toolchains_without_cleanup() {
+        let installed = InstalledToolchains::with_record(None, false);
+        installed.record("1.70.0-x".to_string());
+
+        let reporter = TestReporterWrapper::default();
+        installed.clean_up(reporter.get()).unwrap();
+
+        assert_eq!(installed.installed(), names(&["1.70.0-x"]));
+        assert!(reporter.wait_for_events().is_empty());
+    }
+}
@@ -1,7 +1,8 @@
+pub mod installed_toolchains;
 mod release;
 pub mod release_index;
 pub(crate) mod releases_filter;
-pub(crate) mod setup_toolchain;
+pub mod toolchain_provider;
 
 pub use cargo_msrv_types::Toolchain;
 pub use release::RustRelease;
@@ -26,6 +26,11 @@ impl RustRelease {
         }
     }
 
+    /// The Rust version of the release.
+    pub fn version(&self) -> &rust_releases::semver::Version {
+        self.release.version()
+    }
+
     /// Get the [`Toolchain`] for the given Rust release.
     pub fn to_toolchain_spec(&self) -> Toolchain {
         let version = self.release.version();
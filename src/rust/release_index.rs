@@ -1,27 +1,613 @@
-use crate::error::TResult;
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, RustupListToolchainsError, TResult};
+use crate::external_command::rustup_command::RustupCommand;
 use crate::reporter::Reporter;
-use crate::reporter::event::FetchIndex;
+use crate::reporter::event::{FetchIndex, StaleReleaseIndex};
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_msrv_context::RustReleasesContext;
 use cargo_msrv_context::types::ReleaseSource;
 #[cfg(feature = "rust-releases-dist-source")]
 use rust_releases::RustDist;
-use rust_releases::{Channel, ReleaseIndex, RustChangelog, Source};
+use rust_releases::{Channel, Release, ReleaseIndex, RustChangelog, Source, semver};
+use std::collections::BTreeSet;
+use std::io;
+use std::time::{Duration, SystemTime};
 
+/// How long a cached copy of the index is used, before it's fetched again.
+pub const INDEX_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Obtain the index of Rust releases from the configured release source.
+///
+/// A fetched index is cached on disk. The cached copy is used instead of fetching the index, if
+/// it's younger than [`INDEX_TTL`], or if fetching the index failed. In offline mode, only the
+/// cached copy is used, regardless of its age.
+///
+/// An index from a source which doesn't require network access, like the locally installed
+/// toolchains or a user supplied file, is never cached.
 pub fn fetch_index(
     reporter: &impl Reporter,
-    release_source: ReleaseSource,
+    rust_releases: &RustReleasesContext,
+) -> TResult<ReleaseIndex> {
+    let release_source = &rust_releases.release_source;
+    let offline = rust_releases.offline;
+
+    reporter.run_scoped_event(FetchIndex::new(release_source.clone(), offline), || {
+        let source = release_source.to_string();
+
+        if !release_source.requires_network() {
+            info!(source = source, "reading local index");
+            return fetch_from_source(release_source);
+        }
+
+        let cache = IndexCache::in_user_cache_dir();
+
+        if offline {
+            info!(source = source, "using cached index (offline)");
+
+            let cache = cache.ok_or(CargoMSRVError::NoCacheFolder)?;
+            return cache.load(release_source, None).ok_or_else(|| {
+                CargoMSRVError::NoCachedReleaseIndex {
+                    release_source: release_source.clone(),
+                    path: cache.path(release_source),
+                }
+            });
+        }
+
+        if let Some(index) = cache
+            .as_ref()
+            .and_then(|cache| cache.load(release_source, Some(INDEX_TTL)))
+        {
+            info!(source = source, "using cached index");
+            return Ok(index);
+        }
+
+        fetch_or_use_stale_cache(reporter, release_source, cache.as_ref(), || {
+            fetch_from_source(release_source)
+        })
+    })
+}
+
+/// Fetch the index, and cache it. If fetching fails, the cached copy is used regardless of its
+/// age, and the user is warned that it may be outdated.
+fn fetch_or_use_stale_cache(
+    reporter: &impl Reporter,
+    release_source: &ReleaseSource,
+    cache: Option<&IndexCache>,
+    fetch: impl FnOnce() -> TResult<ReleaseIndex>,
 ) -> TResult<ReleaseIndex> {
-    reporter.run_scoped_event(FetchIndex::new(release_source), || {
-        let source: &'static str = release_source.into();
-        info!(source = source, "fetching index");
+    let source = release_source.to_string();
+
+    info!(source = source, "fetching index");
+
+    let index = match fetch() {
+        Ok(index) => index,
+        Err(err) => {
+            let Some((index, cache)) =
+                cache.and_then(|cache| Some((cache.load(release_source, None)?, cache)))
+            else {
+                return Err(err);
+            };
+
+            let age = age(&cache.path(release_source)).unwrap_or_default();
+            info!(source = source, %err, ?age, "unable to fetch index, using cached index");
+            reporter.report_event(StaleReleaseIndex::new(
+                release_source.clone(),
+                age,
+                err.to_string(),
+            ))?;
+
+            return Ok(index);
+        }
+    };
+
+    if let Some(cache) = cache
+        && let Err(err) = cache.store(release_source, &index)
+    {
+        info!(source = source, %err, "unable to cache index");
+    }
+
+    Ok(index)
+}
+
+/// Whether, and how, the index of Rust releases can be obtained from the configured release
+/// source, as diagnosed by `cargo msrv doctor`.
+#[derive(Debug)]
+pub enum IndexAvailability {
+    /// The index was fetched, or read from a local source
+    Available { releases: usize },
+    /// The cached copy of the index would be used, because the index was not fetched in offline
+    /// mode, or because fetching it failed
+    Cached {
+        releases: usize,
+        age: Duration,
+        fetch_error: Option<CargoMSRVError>,
+    },
+    /// The index can't be obtained
+    Unavailable(CargoMSRVError),
+}
+
+/// Check whether the index of Rust releases can be obtained, like [`fetch_index`] would, but
+/// without using a cached copy which is still fresh, nor storing a fetched index.
+pub fn index_availability(rust_releases: &RustReleasesContext) -> IndexAvailability {
+    let release_source = &rust_releases.release_source;
+
+    let cached = || {
+        let cache = IndexCache::in_user_cache_dir()?;
+        let index = cache.load(release_source, None)?;
+        let age = age(&cache.path(release_source))?;
+
+        Some((index.releases().len(), age))
+    };
+
+    if rust_releases.offline && release_source.requires_network() {
+        return match cached() {
+            Some((releases, age)) => IndexAvailability::Cached {
+                releases,
+                age,
+                fetch_error: None,
+            },
+            None => IndexAvailability::Unavailable(match IndexCache::in_user_cache_dir() {
+                Some(cache) => CargoMSRVError::NoCachedReleaseIndex {
+                    release_source: release_source.clone(),
+                    path: cache.path(release_source),
+                },
+                None => CargoMSRVError::NoCacheFolder,
+            }),
+        };
+    }
+
+    match fetch_from_source(release_source) {
+        Ok(index) => IndexAvailability::Available {
+            releases: index.releases().len(),
+        },
+        Err(err) => match cached().filter(|_| release_source.requires_network()) {
+            Some((releases, age)) => IndexAvailability::Cached {
+                releases,
+                age,
+                fetch_error: Some(err),
+            },
+            None => IndexAvailability::Unavailable(err),
+        },
+    }
+}
+
+fn fetch_from_source(release_source: &ReleaseSource) -> TResult<ReleaseIndex> {
+    let index = match release_source {
+        ReleaseSource::RustChangelog => {
+            RustChangelog::fetch_channel(Channel::Stable)?.build_index()?
+        }
+        #[cfg(feature = "rust-releases-dist-source")]
+        ReleaseSource::RustDist => RustDist::fetch_channel(Channel::Stable)?.build_index()?,
+        ReleaseSource::RustupInstalled => installed_toolchains()?,
+        ReleaseSource::File(path) => read_index_file(path)?,
+    };
+
+    Ok(index)
+}
+
+/// Build an index of the stable toolchains which are installed with rustup.
+fn installed_toolchains() -> TResult<ReleaseIndex> {
+    let rustup = RustupCommand::new()
+        .with_args(["list"])
+        .with_stdout()
+        .with_stderr()
+        .toolchain()?;
+
+    if !rustup.exit_status().success() {
+        return Err(CargoMSRVError::RustupError(
+            RustupListToolchainsError {
+                stderr: rustup.stderr().to_string(),
+            }
+            .into(),
+        ));
+    }
+
+    let versions = rustup
+        .stdout()
+        .lines()
+        .filter_map(parse_toolchain_name)
+        .collect::<BTreeSet<_>>();
+
+    if versions.is_empty() {
+        return Err(CargoMSRVError::NoInstalledToolchains);
+    }
+
+    Ok(versions
+        .into_iter()
+        .rev()
+        .map(Release::new_stable)
+        .collect())
+}
+
+/// Parse the Rust version of a toolchain, as listed by `rustup toolchain list`.
+///
+/// Only toolchains which are named after a full stable version, like `1.70.0-x86_64-unknown-linux-gnu`,
+/// are considered. Channel toolchains like `stable`, partial versions like `1.70`, pre-releases and
+/// custom toolchains are skipped, since the exact Rust version they refer to isn't part of the name.
+fn parse_toolchain_name(line: &str) -> Option<semver::Version> {
+    let name = line.split_whitespace().next()?;
+    let (version, rest) = name.split_once('-').unwrap_or((name, ""));
+
+    if rest.starts_with("beta") || rest.starts_with("nightly") {
+        return None;
+    }
+
+    semver::Version::parse(version).ok()
+}
+
+/// Read an index of Rust releases from a user supplied file.
+///
+/// A file with the `.toml` extension is read as a channel manifest, like `channel-rust-stable.toml`,
+/// which describes a single release. Any other file is read as a plain text list, with one Rust
+/// version per line. Empty lines, and lines starting with `#`, are ignored.
+fn read_index_file(path: &Utf8Path) -> TResult<ReleaseIndex> {
+    let contents = std::fs::read_to_string(path).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
 
-        let index = match release_source {
-            ReleaseSource::RustChangelog => {
-                RustChangelog::fetch_channel(Channel::Stable)?.build_index()?
+    let versions = if path.extension() == Some("toml") {
+        parse_channel_manifest(&contents).map(|version| vec![version])
+    } else {
+        parse_release_list(&contents)
+    };
+
+    let versions = versions
+        .and_then(|versions| {
+            if versions.is_empty() {
+                Err("no Rust versions were found".to_string())
+            } else {
+                Ok(versions.into_iter().collect::<BTreeSet<_>>())
             }
-            #[cfg(feature = "rust-releases-dist-source")]
-            ReleaseSource::RustDist => RustDist::fetch_channel(Channel::Stable)?.build_index()?,
+        })
+        .map_err(|reason| CargoMSRVError::InvalidReleaseIndexFile {
+            path: path.to_path_buf(),
+            reason,
+        })?;
+
+    Ok(versions
+        .into_iter()
+        .rev()
+        .map(Release::new_stable)
+        .collect())
+}
+
+fn parse_release_list(contents: &str) -> Result<Vec<semver::Version>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(number, line)| (number + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            parse_stable_version(line).ok_or_else(|| {
+                format!(
+                    "line {}: '{}' is not a stable Rust version, like '1.70.0'",
+                    number, line
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parse the Rust version of a channel manifest, which is found under `pkg.rust.version`, for
+/// example `version = "1.70.0 (90c541806 2023-05-31)"`.
+fn parse_channel_manifest(contents: &str) -> Result<semver::Version, String> {
+    let document = contents
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|err| format!("unable to parse channel manifest: {}", err.message()))?;
+
+    let version = document
+        .get("pkg")
+        .and_then(|pkg| pkg.get("rust"))
+        .and_then(|rust| rust.get("version"))
+        .and_then(|version| version.as_str())
+        .ok_or_else(|| "channel manifest has no 'pkg.rust.version'".to_string())?;
+
+    version
+        .split_whitespace()
+        .next()
+        .and_then(parse_stable_version)
+        .ok_or_else(|| {
+            format!(
+                "channel manifest version '{}' is not a stable Rust version",
+                version
+            )
+        })
+}
+
+fn parse_stable_version(version: &str) -> Option<semver::Version> {
+    semver::Version::parse(version)
+        .ok()
+        .filter(|version| version.pre.is_empty())
+}
+
+/// A copy of the release index of each release source, stored on disk.
+///
+/// Each index is stored as a plain text file, with one Rust version per line, ordered from the
+/// most recent to the least recent release.
+struct IndexCache {
+    dir: Utf8PathBuf,
+}
+
+impl IndexCache {
+    fn new(dir: impl Into<Utf8PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn in_user_cache_dir() -> Option<Self> {
+        dirs::cache_dir()
+            .and_then(|path| Utf8PathBuf::from_path_buf(path).ok())
+            .map(|path| Self::new(path.join("cargo-msrv").join("index")))
+    }
+
+    fn path(&self, release_source: &ReleaseSource) -> Utf8PathBuf {
+        self.dir.join(release_source.to_string())
+    }
+
+    /// Load the cached index, unless it is older than `max_age`.
+    ///
+    /// A cached index which can't be read, or which is empty, is treated as absent.
+    fn load(
+        &self,
+        release_source: &ReleaseSource,
+        max_age: Option<Duration>,
+    ) -> Option<ReleaseIndex> {
+        let path = self.path(release_source);
+
+        if let Some(max_age) = max_age
+            && age(&path).is_none_or(|age| age > max_age)
+        {
+            return None;
+        }
+
+        let contents = std::fs::read_to_string(&path).ok()?;
+        let releases = contents
+            .lines()
+            .map(|line| semver::Version::parse(line.trim()).map(Release::new_stable))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+
+        (!releases.is_empty()).then(|| releases.into_iter().collect())
+    }
+
+    fn store(&self, release_source: &ReleaseSource, index: &ReleaseIndex) -> io::Result<()> {
+        let contents = index
+            .releases()
+            .iter()
+            .map(|release| format!("{}\n", release.version()))
+            .collect::<String>();
+
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(release_source), contents)
+    }
+}
+
+fn age(path: &Utf8Path) -> Option<Duration> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+
+    // A modification time in the future counts as fresh
+    Some(
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::{Message, TestReporterWrapper};
+    use std::iter::FromIterator;
+
+    fn cache(tmp: &assert_fs::TempDir) -> IndexCache {
+        IndexCache::new(Utf8PathBuf::from_path_buf(tmp.path().join("index")).unwrap())
+    }
+
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 58, 1)),
+            Release::new_stable(semver::Version::new(1, 57, 0)),
+        ])
+    }
+
+    fn versions(index: &ReleaseIndex) -> Vec<&semver::Version> {
+        index.releases().iter().map(Release::version).collect()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&tmp);
+
+        cache
+            .store(&ReleaseSource::RustChangelog, &index())
+            .unwrap();
+
+        let loaded = cache.load(&ReleaseSource::RustChangelog, None).unwrap();
+        assert_eq!(versions(&loaded), versions(&index()));
+    }
+
+    #[test]
+    fn absent() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&tmp);
+
+        assert!(cache.load(&ReleaseSource::RustChangelog, None).is_none());
+    }
+
+    #[test]
+    fn expired() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&tmp);
+
+        cache
+            .store(&ReleaseSource::RustChangelog, &index())
+            .unwrap();
+
+        let old = SystemTime::now() - 2 * INDEX_TTL;
+        std::fs::File::options()
+            .write(true)
+            .open(cache.path(&ReleaseSource::RustChangelog))
+            .and_then(|file| file.set_modified(old))
+            .unwrap();
+
+        assert!(
+            cache
+                .load(&ReleaseSource::RustChangelog, Some(INDEX_TTL))
+                .is_none()
+        );
+
+        // Without a maximum age, e.g. when offline, an expired copy is still used
+        assert!(cache.load(&ReleaseSource::RustChangelog, None).is_some());
+    }
+
+    #[test]
+    fn stale_cache_used_with_warning_when_fetch_fails() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&tmp);
+        let source = ReleaseSource::RustChangelog;
+
+        cache.store(&source, &index()).unwrap();
+
+        let old = SystemTime::now() - 3 * INDEX_TTL;
+        std::fs::File::options()
+            .write(true)
+            .open(cache.path(&source))
+            .and_then(|file| file.set_modified(old))
+            .unwrap();
+
+        let reporter = TestReporterWrapper::default();
+        let index = fetch_or_use_stale_cache(reporter.get(), &source, Some(&cache), || {
+            Err(CargoMSRVError::GenericMessage("offline".to_string()))
+        })
+        .unwrap();
+
+        assert_eq!(versions(&index), versions(&self::index()));
+
+        let events = reporter.wait_for_events();
+        let [event] = events.as_slice() else {
+            panic!("expected a single event, got {:?}", events);
+        };
+        let Message::StaleReleaseIndex(stale) = event.message() else {
+            panic!("expected a stale release index warning, got {:?}", event);
         };
 
-        Ok(index)
-    })
+        assert_eq!(stale.source(), &source);
+        assert_eq!(stale.error(), "offline");
+        assert!(stale.age() >= 3 * INDEX_TTL);
+    }
+
+    #[test]
+    fn fetch_error_without_cache() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&tmp);
+
+        let reporter = TestReporterWrapper::default();
+        let result = fetch_or_use_stale_cache(
+            reporter.get(),
+            &ReleaseSource::RustChangelog,
+            Some(&cache),
+            || Err(CargoMSRVError::GenericMessage("offline".to_string())),
+        );
+
+        assert!(result.is_err());
+        assert!(reporter.wait_for_events().is_empty());
+    }
+
+    #[yare::parameterized(
+        host = { "1.70.0-x86_64-unknown-linux-gnu", Some(semver::Version::new(1, 70, 0)) },
+        default = { "1.56.1-x86_64-pc-windows-msvc (default)", Some(semver::Version::new(1, 56, 1)) },
+        active = { "1.80.1-aarch64-apple-darwin (active, default)", Some(semver::Version::new(1, 80, 1)) },
+        without_target = { "1.60.0", Some(semver::Version::new(1, 60, 0)) },
+        stable = { "stable-x86_64-unknown-linux-gnu", None },
+        nightly = { "nightly-2024-01-01-x86_64-unknown-linux-gnu", None },
+        beta = { "1.81.0-beta.1-x86_64-unknown-linux-gnu", None },
+        partial = { "1.70-x86_64-unknown-linux-gnu", None },
+        custom = { "my-toolchain", None },
+    )]
+    fn toolchain_names(line: &str, expected: Option<semver::Version>) {
+        assert_eq!(parse_toolchain_name(line), expected);
+    }
+
+    #[test]
+    fn release_list() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let path = tmp.path().join("releases.txt");
+        std::fs::write(&path, "# approved compilers\n1.70.0\n\n1.74.1\n1.70.0\n").unwrap();
+
+        let index = read_index_file(&Utf8PathBuf::from_path_buf(path).unwrap()).unwrap();
+
+        assert_eq!(
+            versions(&index),
+            vec![
+                &semver::Version::new(1, 74, 1),
+                &semver::Version::new(1, 70, 0)
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        invalid_version = { "1.70.0\nlatest\n", "line 2: 'latest' is not a stable Rust version, like '1.70.0'" },
+        pre_release = { "1.81.0-beta.1\n", "line 1: '1.81.0-beta.1' is not a stable Rust version, like '1.70.0'" },
+        empty = { "# nothing here\n", "no Rust versions were found" },
+    )]
+    fn invalid_release_list(contents: &str, reason: &str) {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let path = tmp.path().join("releases.txt");
+        std::fs::write(&path, contents).unwrap();
+
+        let err = read_index_file(&Utf8PathBuf::from_path_buf(path).unwrap()).unwrap_err();
+
+        assert!(
+            matches!(err, CargoMSRVError::InvalidReleaseIndexFile { reason: r, .. } if r == reason)
+        );
+    }
+
+    #[test]
+    fn channel_manifest() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let path = tmp.path().join("channel-rust-stable.toml");
+        std::fs::write(
+            &path,
+            r#"manifest-version = "2"
+date = "2023-06-01"
+
+[pkg.rust]
+version = "1.70.0 (90c541806 2023-05-31)"
+"#,
+        )
+        .unwrap();
+
+        let index = read_index_file(&Utf8PathBuf::from_path_buf(path).unwrap()).unwrap();
+
+        assert_eq!(versions(&index), vec![&semver::Version::new(1, 70, 0)]);
+    }
+
+    #[yare::parameterized(
+        no_version = { "manifest-version = \"2\"\n" },
+        beta = { "[pkg.rust]\nversion = \"1.81.0-beta.1 (1a2b3c4d5 2024-07-26)\"\n" },
+        not_toml = { "[pkg.rust\n" },
+    )]
+    fn invalid_channel_manifest(contents: &str) {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let path = tmp.path().join("channel-rust-stable.toml");
+        std::fs::write(&path, contents).unwrap();
+
+        let err = read_index_file(&Utf8PathBuf::from_path_buf(path).unwrap()).unwrap_err();
+
+        assert!(matches!(
+            err,
+            CargoMSRVError::InvalidReleaseIndexFile { .. }
+        ));
+    }
+
+    #[test]
+    fn invalid() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let cache = cache(&tmp);
+
+        std::fs::create_dir_all(&cache.dir).unwrap();
+        std::fs::write(cache.path(&ReleaseSource::RustChangelog), "1.58.1\nnope\n").unwrap();
+
+        assert!(cache.load(&ReleaseSource::RustChangelog, None).is_none());
+    }
 }
@@ -0,0 +1,251 @@
+use crate::error::{IoError, IoErrorSource};
+use crate::reporter::event::Method;
+use crate::rust::Toolchain;
+use crate::rust::toolchain_provider::{ToolchainCommand, ToolchainProvider};
+use crate::{CargoMSRVError, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::env::consts::EXE_SUFFIX;
+use std::ffi::OsString;
+use std::process::{Command, Output};
+
+/// Toolchains which are unpacked in a directory, e.g. by the standalone installers, where each
+/// toolchain is located at `<dir>/<version>`, with its binaries in `<dir>/<version>/bin`.
+///
+/// Toolchains can't be installed into the directory, so a toolchain which isn't present is
+/// unavailable. Components can't be added either, so they are assumed to be present.
+#[derive(Debug)]
+pub struct DirectoryToolchainProvider {
+    dir: Utf8PathBuf,
+}
+
+impl DirectoryToolchainProvider {
+    pub fn new(dir: impl Into<Utf8PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn toolchain_dir(&self, toolchain: &Toolchain) -> Utf8PathBuf {
+        self.dir.join(toolchain.version().to_string())
+    }
+
+    fn bin_dir(&self, toolchain: &Toolchain) -> Utf8PathBuf {
+        self.toolchain_dir(toolchain).join("bin")
+    }
+
+    /// The program of the command, as located in the toolchain if present there, e.g.
+    /// `<dir>/<version>/bin/cargo`, or else as given, e.g. for a custom check command.
+    fn program(&self, toolchain: &Toolchain, program: &str) -> String {
+        let in_toolchain = self.bin_dir(toolchain).join(executable(program));
+
+        if in_toolchain.is_file() {
+            in_toolchain.into_string()
+        } else {
+            program.to_string()
+        }
+    }
+}
+
+impl ToolchainProvider for DirectoryToolchainProvider {
+    fn install(&self, toolchain: &Toolchain) -> TResult<()> {
+        if self.locate(toolchain)?.is_none() {
+            return Err(CargoMSRVError::ToolchainNotInDirectory {
+                version: toolchain.version().clone(),
+                dir: self.dir.clone(),
+            });
+        }
+
+        let toolchain_dir = self.toolchain_dir(toolchain);
+
+        if !toolchain_dir
+            .join("lib")
+            .join("rustlib")
+            .join(toolchain.target())
+            .is_dir()
+        {
+            return Err(CargoMSRVError::TargetNotInToolchainDirectory {
+                target: toolchain.target().to_string(),
+                toolchain_dir,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn locate(&self, toolchain: &Toolchain) -> TResult<Option<Utf8PathBuf>> {
+        let bin_dir = self.bin_dir(toolchain);
+
+        let located = ["cargo", "rustc"]
+            .into_iter()
+            .all(|program| bin_dir.join(executable(program)).is_file());
+
+        Ok(located.then_some(bin_dir))
+    }
+
+    fn run(&self, toolchain: &Toolchain, command: &ToolchainCommand) -> TResult<Output> {
+        let Some((program, args)) = command.args.split_first() else {
+            return Err(CargoMSRVError::UnableToRunCheck {
+                command: String::new(),
+                cwd: command.dir.to_path_buf(),
+            });
+        };
+
+        let bin_dir = self.bin_dir(toolchain);
+        let program = self.program(toolchain, program);
+
+        let mut cmd = Command::new(&program);
+        cmd.args(args)
+            .current_dir(command.dir)
+            .env("PATH", path_with(&bin_dir)?)
+            .env("RUSTC", bin_dir.join(executable("rustc")))
+            // the toolchain may otherwise be overridden, if a rustup proxy is found on the path
+            .env_remove("RUSTUP_TOOLCHAIN");
+
+        if let Some(target_dir) = command.target_dir {
+            cmd.env("CARGO_TARGET_DIR", target_dir);
+        }
+
+        debug!(?cmd, "running command with toolchain from directory");
+
+        cmd.output().map_err(|error| {
+            IoError {
+                error,
+                source: IoErrorSource::SpawnProcess(OsString::from(program)),
+            }
+            .into()
+        })
+    }
+
+    fn method(&self, toolchain: &Toolchain, command: &ToolchainCommand) -> Method {
+        let args = command
+            .args
+            .split_first()
+            .map(|(program, args)| {
+                std::iter::once(self.program(toolchain, program))
+                    .chain(args.iter().cloned())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        Method::direct_run(args, command.dir)
+    }
+
+    fn origin(&self) -> String {
+        let dir = self
+            .dir
+            .canonicalize_utf8()
+            .unwrap_or_else(|_| self.dir.clone());
+
+        format!("directory {}", dir)
+    }
+}
+
+fn executable(program: &str) -> String {
+    format!("{}{}", program, EXE_SUFFIX)
+}
+
+/// The `PATH`, with the given directory in front, so the programs of the toolchain take
+/// precedence.
+fn path_with(dir: &Utf8Path) -> TResult<OsString> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let paths =
+        std::iter::once(dir.as_std_path().to_path_buf()).chain(std::env::split_paths(&path));
+
+    std::env::join_paths(paths).map_err(|err| CargoMSRVError::GenericMessage(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+    use std::fs;
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    fn toolchains_dir() -> (assert_fs::TempDir, Utf8PathBuf) {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let dir = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+
+        let bin = dir.join("1.70.0").join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join(executable("cargo")), "").unwrap();
+        fs::write(bin.join(executable("rustc")), "").unwrap();
+
+        (tmp, dir)
+    }
+
+    #[test]
+    fn locates_toolchain_by_version() {
+        let (_tmp, dir) = toolchains_dir();
+        let provider = DirectoryToolchainProvider::new(&dir);
+
+        assert_eq!(
+            provider.locate(&toolchain(70)).unwrap(),
+            Some(dir.join("1.70.0").join("bin"))
+        );
+        assert_eq!(provider.locate(&toolchain(71)).unwrap(), None);
+    }
+
+    #[test]
+    fn installs_only_present_toolchain_with_target() {
+        let (_tmp, dir) = toolchains_dir();
+        let provider = DirectoryToolchainProvider::new(&dir);
+
+        assert!(matches!(
+            provider.install(&toolchain(71)),
+            Err(CargoMSRVError::ToolchainNotInDirectory { .. })
+        ));
+        assert!(matches!(
+            provider.install(&toolchain(70)),
+            Err(CargoMSRVError::TargetNotInToolchainDirectory { .. })
+        ));
+
+        fs::create_dir_all(dir.join("1.70.0").join("lib").join("rustlib").join("x")).unwrap();
+        assert!(provider.install(&toolchain(70)).is_ok());
+    }
+
+    #[test]
+    fn reports_program_of_toolchain() {
+        let (_tmp, dir) = toolchains_dir();
+        let provider = DirectoryToolchainProvider::new(&dir);
+        let args = ["cargo", "check"].map(String::from);
+        let command = ToolchainCommand {
+            args: &args,
+            dir: Utf8Path::new("crate"),
+            target_dir: None,
+        };
+
+        let cargo = dir.join("1.70.0").join("bin").join(executable("cargo"));
+        assert_eq!(
+            provider.method(&toolchain(70), &command),
+            Method::direct_run([cargo.as_str(), "check"], "crate")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_program_of_toolchain() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_tmp, dir) = toolchains_dir();
+        let cargo = dir.join("1.70.0").join("bin").join("cargo");
+        fs::write(&cargo, "#!/bin/sh\necho \"$RUSTC $CARGO_TARGET_DIR $*\"\n").unwrap();
+        fs::set_permissions(&cargo, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let provider = DirectoryToolchainProvider::new(&dir);
+        let args = ["cargo", "check"].map(String::from);
+        let command = ToolchainCommand {
+            args: &args,
+            dir: &dir,
+            target_dir: Some(Utf8Path::new("target")),
+        };
+
+        let output = provider.run(&toolchain(70), &command).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            format!("{}/1.70.0/bin/rustc target check", dir)
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! The providers of the Rust toolchains which are checked for compatibility.
+//!
+//! By default, toolchains are installed and run with rustup. Toolchains which come from
+//! elsewhere, like Nix, distro packages or unpacked standalone installers, can be provided from a
+//! directory instead.
+
+use crate::TResult;
+use crate::reporter::event::Method;
+use crate::rust::Toolchain;
+use camino::{Utf8Path, Utf8PathBuf};
+use std::process::Output;
+
+mod directory;
+mod rustup;
+
+pub use directory::DirectoryToolchainProvider;
+pub use rustup::RustupToolchainProvider;
+
+/// Provides the Rust toolchains which are checked for compatibility.
+///
+/// Checks may run concurrently, so a provider may be used from multiple threads.
+pub trait ToolchainProvider: Sync {
+    /// Make the toolchain, including its target and components, available to run, e.g. by
+    /// installing it.
+    fn install(&self, toolchain: &Toolchain) -> TResult<()>;
+
+    /// The directory with the binaries of the toolchain, like `cargo` and `rustc`, if the
+    /// toolchain is available.
+    fn locate(&self, toolchain: &Toolchain) -> TResult<Option<Utf8PathBuf>>;
+
+    /// Run a command, like `cargo check`, with the toolchain, in the given directory.
+    ///
+    /// The first argument is the program to run. The output is returned as is, since a command
+    /// which fails is a sign of incompatibility, rather than an error.
+    fn run(&self, toolchain: &Toolchain, command: &ToolchainCommand) -> TResult<Output>;
+
+    /// How a command is run with the toolchain, as reported to the user.
+    fn method(&self, toolchain: &Toolchain, command: &ToolchainCommand) -> Method;
+
+    /// Where the toolchains come from, like rustup, or a given directory. Toolchains of the same
+    /// version, but from a different origin, may not be the same toolchain.
+    fn origin(&self) -> String;
+}
+
+/// A command to run with a toolchain.
+#[derive(Debug)]
+pub struct ToolchainCommand<'a> {
+    /// The program to run, followed by its arguments, e.g. `cargo check`
+    pub args: &'a [String],
+    /// The working directory of the program
+    pub dir: &'a Utf8Path,
+    /// The Cargo target directory, if not the default one
+    pub target_dir: Option<&'a Utf8Path>,
+}
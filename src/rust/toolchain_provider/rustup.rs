@@ -0,0 +1,320 @@
+use crate::error::{
+    RustupAddComponentError, RustupAddTargetError, RustupError, RustupInstallError,
+};
+use crate::external_command::rustup_command::RustupCommand;
+use crate::reporter::event::Method;
+use crate::rust::Toolchain;
+use crate::rust::installed_toolchains::InstalledToolchains;
+use crate::rust::toolchain_provider::{ToolchainCommand, ToolchainProvider};
+use crate::{CargoMSRVError, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::process::Output;
+
+/// Toolchains which are installed, and run, with rustup.
+#[derive(Debug, Default)]
+pub struct RustupToolchainProvider<'a> {
+    installed_toolchains: Option<&'a InstalledToolchains>,
+    installed_only: bool,
+}
+
+impl<'a> RustupToolchainProvider<'a> {
+    pub const fn new() -> Self {
+        Self {
+            installed_toolchains: None,
+            installed_only: false,
+        }
+    }
+
+    /// Only use toolchains which are already installed, and never install, nor add a target to,
+    /// a toolchain, so rustup doesn't access the network. A toolchain which isn't installed is
+    /// unavailable. Components are assumed to be present.
+    pub fn installed_only(mut self, installed_only: bool) -> Self {
+        self.installed_only = installed_only;
+        self
+    }
+
+    /// Record each toolchain which is installed, so it can be uninstalled later.
+    pub fn with_installed_toolchains(
+        mut self,
+        installed_toolchains: &'a InstalledToolchains,
+    ) -> Self {
+        self.installed_toolchains = Some(installed_toolchains);
+        self
+    }
+}
+
+impl ToolchainProvider for RustupToolchainProvider<'_> {
+    #[instrument(skip(self, toolchain))]
+    fn install(&self, toolchain: &Toolchain) -> TResult<()> {
+        if self.installed_only {
+            return self.require_installed(toolchain);
+        }
+
+        let installed = install_toolchain(toolchain)?;
+
+        // recorded before the target and components are added, so a toolchain is still
+        // uninstalled when adding them fails
+        if let (Some(installed_toolchains), Some(name)) = (self.installed_toolchains, installed) {
+            installed_toolchains.record(name);
+        }
+
+        add_target(toolchain)?;
+
+        if !toolchain.components().is_empty() {
+            add_components(toolchain)?;
+        }
+
+        Ok(())
+    }
+
+    fn locate(&self, toolchain: &Toolchain) -> TResult<Option<Utf8PathBuf>> {
+        let rustup = RustupCommand::new()
+            .with_stdout()
+            .with_args(["--toolchain", &toolchain.version().to_string(), "rustc"])
+            .which()?;
+
+        if !rustup.exit_status().success() {
+            return Ok(None);
+        }
+
+        Ok(Utf8Path::new(rustup.stdout().trim())
+            .parent()
+            .map(Utf8Path::to_path_buf))
+    }
+
+    fn run(&self, toolchain: &Toolchain, command: &ToolchainCommand) -> TResult<Output> {
+        let mut rustup = RustupCommand::new()
+            .with_args(rustup_run_args(toolchain, command))
+            .with_dir(command.dir)
+            .with_stdout()
+            .with_stderr();
+
+        if let Some(target_dir) = command.target_dir {
+            rustup = rustup.with_env("CARGO_TARGET_DIR", target_dir);
+        }
+
+        Ok(rustup.run()?.into_output())
+    }
+
+    fn method(&self, toolchain: &Toolchain, command: &ToolchainCommand) -> Method {
+        Method::rustup_run(rustup_run_args(toolchain, command), command.dir)
+    }
+
+    fn origin(&self) -> String {
+        "rustup".to_string()
+    }
+}
+
+impl RustupToolchainProvider<'_> {
+    /// Fail if the toolchain, or its target, isn't installed.
+    fn require_installed(&self, toolchain: &Toolchain) -> TResult<()> {
+        let Some(bin_dir) = self.locate(toolchain)? else {
+            return Err(CargoMSRVError::ToolchainNotInstalledWithRustup {
+                version: toolchain.version().clone(),
+            });
+        };
+
+        // the sysroot of the toolchain, i.e. the parent of its `bin` directory
+        let toolchain_dir = bin_dir.parent().unwrap_or(&bin_dir).to_path_buf();
+
+        if !toolchain_dir
+            .join("lib")
+            .join("rustlib")
+            .join(toolchain.target())
+            .is_dir()
+        {
+            return Err(CargoMSRVError::TargetNotInToolchainDirectory {
+                target: toolchain.target().to_string(),
+                toolchain_dir,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The arguments of `rustup run`, i.e. the version of the toolchain, followed by the command.
+fn rustup_run_args(toolchain: &Toolchain, command: &ToolchainCommand) -> Vec<String> {
+    std::iter::once(toolchain.version().to_string())
+        .chain(command.args.iter().cloned())
+        .collect()
+}
+
+/// Install the toolchain, if it isn't installed yet. Returns the name of the toolchain, if rustup
+/// reported to have installed it.
+#[instrument(skip(toolchain))]
+fn install_toolchain(toolchain: &Toolchain) -> TResult<Option<String>> {
+    info!(toolchain = toolchain.spec(), "installing host toolchain");
+
+    let rustup = RustupCommand::new()
+        .with_stdout()
+        .with_stderr()
+        .with_args([
+            "--no-self-update",
+            "--profile",
+            "minimal",
+            &format!("{}", toolchain.version()),
+        ])
+        .install()?;
+
+    let status = rustup.exit_status();
+
+    if !status.success() {
+        error!(
+            toolchain = toolchain.spec(),
+            stdout = rustup.stdout(),
+            stderr = rustup.stderr(),
+            "rustup failed to install toolchain"
+        );
+
+        return Err(CargoMSRVError::RustupError(RustupError::Install(
+            RustupInstallError {
+                toolchain_spec: toolchain.spec().to_string(),
+                stderr: rustup.stderr().to_string(),
+            },
+        )));
+    }
+
+    Ok(installed_toolchain_name(rustup.stdout(), toolchain))
+}
+
+/// The name of the toolchain, if rustup reported to have installed it, e.g.
+/// `1.70.0-x86_64-unknown-linux-gnu installed - rustc 1.70.0 (90c541806 2023-05-31)`. A toolchain
+/// which was already installed is reported as `unchanged` instead.
+fn installed_toolchain_name(stdout: &str, toolchain: &Toolchain) -> Option<String> {
+    let version = toolchain.version().to_string();
+
+    stdout
+        .lines()
+        .filter_map(|line| line.trim().split_once(" installed - "))
+        .map(|(name, _)| name)
+        .find(|name| {
+            *name == version
+                || name
+                    .strip_prefix(version.as_str())
+                    .is_some_and(|host| host.starts_with('-'))
+        })
+        .map(String::from)
+}
+
+#[instrument(skip(toolchain))]
+fn add_target(toolchain: &Toolchain) -> TResult<()> {
+    info!(
+        toolchain = toolchain.spec(),
+        target = toolchain.target(),
+        "adding target to toolchain"
+    );
+
+    let rustup = RustupCommand::new()
+        .with_stdout()
+        .with_stderr()
+        .with_args([
+            "add",
+            "--toolchain",
+            &format!("{}", toolchain.version()),
+            toolchain.target(),
+        ])
+        .target()?;
+
+    let status = rustup.exit_status();
+
+    if !status.success() {
+        error!(
+            toolchain = toolchain.spec(),
+            stdout = rustup.stdout(),
+            stderr = rustup.stderr(),
+            "rustup failed to add target to toolchain"
+        );
+
+        return Err(CargoMSRVError::RustupError(RustupError::AddTarget(
+            RustupAddTargetError {
+                targets: toolchain.target().to_string(),
+                toolchain_spec: toolchain.spec().to_string(),
+                stderr: rustup.stderr().to_string(),
+            },
+        )));
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(toolchain))]
+fn add_components(toolchain: &Toolchain) -> TResult<()> {
+    info!(
+        toolchain = toolchain.spec(),
+        target = toolchain.target(),
+        components = toolchain.components().join(","),
+        "adding components to toolchain"
+    );
+
+    let base_arguments = [
+        "add",
+        "--toolchain",
+        &format!("{}", toolchain.version()),
+        "--target",
+        toolchain.target(),
+    ];
+
+    let rustup = RustupCommand::new()
+        .with_stdout()
+        .with_stderr()
+        .with_args(base_arguments.iter().chain(toolchain.components().iter()))
+        .component()?;
+
+    let status = rustup.exit_status();
+
+    if !status.success() {
+        error!(
+            toolchain = toolchain.spec(),
+            stdout = rustup.stdout(),
+            stderr = rustup.stderr(),
+            "rustup failed to add component(s) to toolchain"
+        );
+
+        return Err(CargoMSRVError::RustupError(RustupError::AddComponent(
+            RustupAddComponentError {
+                components: toolchain.components().join(", "),
+                toolchain_spec: toolchain.spec().to_string(),
+                stderr: rustup.stderr().to_string(),
+            },
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    #[yare::parameterized(
+        installed = {
+            "\n  1.70.0-x86_64-unknown-linux-gnu installed - rustc 1.70.0 (90c541806 2023-05-31)\n\n",
+            Some("1.70.0-x86_64-unknown-linux-gnu")
+        },
+        unchanged = {
+            "\n  1.70.0-x86_64-unknown-linux-gnu unchanged - rustc 1.70.0 (90c541806 2023-05-31)\n\n",
+            None
+        },
+        other_toolchain = {
+            "\n  1.71.0-x86_64-unknown-linux-gnu installed - rustc 1.71.0 (8ede3aae2 2023-07-12)\n\n",
+            None
+        },
+        other_patch_version = {
+            "\n  1.70.01-x86_64-unknown-linux-gnu installed - rustc 1.70.1\n\n",
+            None
+        },
+        empty = { "", None },
+    )]
+    fn name_of_installed_toolchain(stdout: &str, expected: Option<&str>) {
+        assert_eq!(
+            installed_toolchain_name(stdout, &toolchain(70)),
+            expected.map(String::from)
+        );
+    }
+}
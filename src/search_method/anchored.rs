@@ -0,0 +1,272 @@
+use crate::TResult;
+use crate::compatibility::IsCompatible;
+use crate::context::SearchMethod;
+use crate::error::NoToolchainsToTryError;
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::outcome::Compatibility;
+use crate::reporter::Reporter;
+use crate::reporter::event::{FindMsrv, Progress};
+use crate::rust::RustRelease;
+use crate::search_method::{Bisect, FindMinimalSupportedRustVersion};
+use cargo_msrv_types::BareVersion;
+use std::cell::Cell;
+
+/// A search which starts from the currently declared MSRV, the anchor.
+///
+/// First the anchor and its predecessor are checked, which confirms the anchor as the MSRV when
+/// the anchor is compatible, and its predecessor is not. Otherwise, the search gallops away from
+/// the anchor, in steps of 1, 2, 4, 8, ... releases, until the MSRV is enclosed, and then bisects
+/// the enclosed releases.
+///
+/// When no MSRV is declared, or the declared MSRV is not part of the search space, a regular
+/// bisection is used instead.
+pub struct Anchored<'runner, R: IsCompatible> {
+    runner: &'runner R,
+    anchor: Option<BareVersion>,
+}
+
+impl<'runner, R: IsCompatible> Anchored<'runner, R> {
+    pub fn new(runner: &'runner R, anchor: Option<BareVersion>) -> Self {
+        Self { runner, anchor }
+    }
+
+    /// The index of the anchor in the search space, which is ordered from most to least recent.
+    fn anchor_index(&self, search_space: &[RustRelease]) -> Option<usize> {
+        let anchor = self.anchor.as_ref()?;
+        let versions = search_space.iter().map(|release| release.version());
+        let version = anchor.try_to_semver(versions).ok()?;
+
+        search_space
+            .iter()
+            .position(|release| release.version() == version)
+    }
+}
+
+impl<R: IsCompatible> FindMinimalSupportedRustVersion for Anchored<'_, R> {
+    fn find_toolchain(
+        &self,
+        search_space: &[RustRelease],
+        reporter: &impl Reporter,
+    ) -> TResult<MinimumSupportedRustVersion> {
+        info!(?search_space, anchor = ?self.anchor);
+
+        if search_space.is_empty() {
+            return Err(NoToolchainsToTryError::new_empty().into());
+        }
+
+        let Some(anchor) = self.anchor_index(search_space) else {
+            info!(anchor = ?self.anchor, "anchor not in search space, bisecting instead");
+            return Bisect::new(self.runner).find_toolchain(search_space, reporter);
+        };
+
+        reporter.run_scoped_event(FindMsrv::new(SearchMethod::Anchored), || {
+            let total = search_space.len() as u64;
+            let iteration = Cell::new(0_u64);
+
+            let is_compatible = |i: usize| -> TResult<bool> {
+                iteration.set(iteration.get() + 1);
+                reporter.report_event(Progress::new(i as u64, total, iteration.get()))?;
+
+                let toolchain = search_space[i].to_toolchain_spec();
+                let outcome = self.runner.is_compatible(&toolchain)?;
+
+                Ok(matches!(outcome, Compatibility::Compatible(_)))
+            };
+
+            let msrv = if is_compatible(anchor)? {
+                gallop_to_least_recent(anchor, search_space.len(), is_compatible)?
+            } else {
+                gallop_to_most_recent(anchor, is_compatible)?
+            };
+
+            Ok(MinimumSupportedRustVersion::from_option(
+                msrv.map(|i| &search_space[i]),
+            ))
+        })
+    }
+}
+
+/// Starting from a compatible release, gallop towards the least recent release until an
+/// incompatible release is found, and bisect the releases in between.
+fn gallop_to_least_recent(
+    compatible: usize,
+    len: usize,
+    is_compatible: impl Fn(usize) -> TResult<bool>,
+) -> TResult<Option<usize>> {
+    let mut compatible = compatible;
+    let mut step = 1;
+
+    while compatible + 1 < len {
+        let next = (compatible + step).min(len - 1);
+
+        if is_compatible(next)? {
+            compatible = next;
+            step *= 2;
+        } else {
+            return bisect(compatible, next, is_compatible).map(Some);
+        }
+    }
+
+    // The least recent release is compatible
+    Ok(Some(compatible))
+}
+
+/// Starting from an incompatible release, gallop towards the most recent release until a
+/// compatible release is found, and bisect the releases in between.
+fn gallop_to_most_recent(
+    incompatible: usize,
+    is_compatible: impl Fn(usize) -> TResult<bool>,
+) -> TResult<Option<usize>> {
+    let mut incompatible = incompatible;
+    let mut step = 1;
+
+    while incompatible > 0 {
+        let next = incompatible.saturating_sub(step);
+
+        if is_compatible(next)? {
+            return bisect(next, incompatible, is_compatible).map(Some);
+        } else {
+            incompatible = next;
+            step *= 2;
+        }
+    }
+
+    // Even the most recent release is incompatible
+    Ok(None)
+}
+
+/// Find the least recent compatible release between a compatible and a less recent,
+/// incompatible release.
+fn bisect(
+    compatible: usize,
+    incompatible: usize,
+    is_compatible: impl Fn(usize) -> TResult<bool>,
+) -> TResult<usize> {
+    let (mut compatible, mut incompatible) = (compatible, incompatible);
+
+    while incompatible - compatible > 1 {
+        let middle = compatible + (incompatible - compatible) / 2;
+
+        if is_compatible(middle)? {
+            compatible = middle;
+        } else {
+            incompatible = middle;
+        }
+    }
+
+    Ok(compatible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compatibility::TestRunner;
+    use crate::reporter::event::Message;
+    use crate::reporter::{Event, TestReporterWrapper};
+    use crate::rust::Toolchain;
+    use crate::search_method::Linear;
+    use crate::semver;
+    use rust_releases::Release;
+
+    fn search_space_of(minors: std::ops::RangeInclusive<u64>) -> Vec<RustRelease> {
+        minors
+            .rev()
+            .map(|minor| {
+                RustRelease::new(
+                    Release::new_stable(semver::Version::new(1, minor, 0)),
+                    "x",
+                    &[],
+                )
+            })
+            .collect()
+    }
+
+    // Rust 1.<oldest_supported> up to and including Rust 1.40 are compatible
+    fn runner(oldest_supported: u64) -> TestRunner {
+        let supported = (oldest_supported..=40)
+            .map(|minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+
+        TestRunner::with_ok("x", supported.iter())
+    }
+
+    fn checks(events: Vec<Event>) -> usize {
+        events
+            .iter()
+            .filter(|event| matches!(event.message(), Message::Progress(_)))
+            .count()
+    }
+
+    #[yare::parameterized(
+        confirmed = { 20, 20 },
+        lowered_by_one = { 20, 19 },
+        lowered_far = { 35, 3 },
+        lowered_to_least_recent = { 20, 1 },
+        raised_by_one = { 20, 21 },
+        raised_far = { 3, 35 },
+        raised_to_most_recent = { 20, 40 },
+        none_compatible = { 20, 41 },
+        anchor_is_least_recent = { 1, 1 },
+        anchor_is_most_recent = { 40, 40 },
+    )]
+    fn finds_same_msrv_as_linear(anchor_minor: u64, oldest_supported: u64) {
+        let runner = runner(oldest_supported);
+        let search_space = search_space_of(1..=40);
+        let anchor = BareVersion::TwoComponents(1, anchor_minor);
+
+        let linear = Linear::new(&runner)
+            .find_toolchain(&search_space, TestReporterWrapper::default().get())
+            .unwrap();
+        let anchored = Anchored::new(&runner, Some(anchor))
+            .find_toolchain(&search_space, TestReporterWrapper::default().get())
+            .unwrap();
+
+        assert_eq!(anchored, linear);
+    }
+
+    #[test]
+    fn confirms_declared_msrv_with_two_checks() {
+        let runner = runner(20);
+        let search_space = search_space_of(1..=40);
+        let reporter = TestReporterWrapper::default();
+
+        let actual = Anchored::new(&runner, Some(BareVersion::TwoComponents(1, 20)))
+            .find_toolchain(&search_space, reporter.get())
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            MinimumSupportedRustVersion::Toolchain {
+                toolchain: Toolchain::new(semver::Version::new(1, 20, 0), "x", &[]),
+            }
+        );
+        assert_eq!(checks(reporter.wait_for_events()), 2);
+    }
+
+    #[yare::parameterized(
+        undeclared = { None },
+        outside_search_space = { Some(BareVersion::TwoComponents(1, 60)) },
+    )]
+    fn bisects_without_anchor(anchor: Option<BareVersion>) {
+        let runner = runner(20);
+        let search_space = search_space_of(1..=40);
+        let reporter = TestReporterWrapper::default();
+
+        let actual = Anchored::new(&runner, anchor)
+            .find_toolchain(&search_space, reporter.get())
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            MinimumSupportedRustVersion::Toolchain {
+                toolchain: Toolchain::new(semver::Version::new(1, 20, 0), "x", &[]),
+            }
+        );
+
+        let events = reporter.wait_for_events();
+        assert!(events.iter().any(|event| matches!(
+            event.message(),
+            Message::FindMsrv(method) if *method == FindMsrv::new(SearchMethod::Bisect)
+        )));
+    }
+}
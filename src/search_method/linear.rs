@@ -8,14 +8,33 @@ use crate::reporter::Reporter;
 use crate::reporter::event::{FindMsrv, Progress};
 use crate::rust::RustRelease;
 use crate::search_method::FindMinimalSupportedRustVersion;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct Linear<'runner, R: IsCompatible> {
     runner: &'runner R,
+    jobs: NonZeroUsize,
 }
 
 impl<'runner, R: IsCompatible> Linear<'runner, R> {
     pub fn new(runner: &'runner R) -> Self {
-        Self { runner }
+        Self {
+            runner,
+            jobs: NonZeroUsize::MIN,
+        }
+    }
+
+    /// Run up to `jobs` compatibility checks concurrently.
+    ///
+    /// The concurrent search yields the same MSRV as the sequential search: each release which
+    /// is more recent than the first incompatible release is still checked.
+    ///
+    /// The runner must not share state between concurrent checks, like the lockfile of the crate,
+    /// which is why more than one job implies isolated checks.
+    pub fn with_jobs(mut self, jobs: NonZeroUsize) -> Self {
+        self.jobs = jobs;
+        self
     }
 
     fn run_check(
@@ -41,29 +60,111 @@ impl<R: IsCompatible> FindMinimalSupportedRustVersion for Linear<'_, R> {
         }
 
         reporter.run_scoped_event(FindMsrv::new(SearchMethod::Linear), || {
-            let mut last_compatible_index = None;
-            let total = search_space.len() as u64;
+            let last_compatible_index = if self.jobs.get() > 1 {
+                self.find_concurrently(search_space, reporter)?
+            } else {
+                self.find_sequentially(search_space, reporter)?
+            };
+
+            let msrv = last_compatible_index.map(|i| &search_space[i]);
+
+            Ok(MinimumSupportedRustVersion::from_option(msrv))
+        })
+    }
+}
+
+impl<R: IsCompatible> Linear<'_, R> {
+    fn find_sequentially(
+        &self,
+        search_space: &[RustRelease],
+        reporter: &impl Reporter,
+    ) -> TResult<Option<usize>> {
+        let mut last_compatible_index = None;
+        let total = search_space.len() as u64;
+
+        for (i, release) in search_space.iter().enumerate() {
+            let current = i as u64;
+            reporter.report_event(Progress::new(current, total, current + 1))?;
 
-            for (i, release) in search_space.iter().enumerate() {
-                let current = i as u64;
-                reporter.report_event(Progress::new(current, total, current + 1))?;
+            let outcome = Self::run_check(self.runner, release, reporter)?;
 
-                let outcome = Self::run_check(self.runner, release, reporter)?;
+            match outcome {
+                Compatibility::Incompatible(_outcome) => {
+                    break;
+                }
+                Compatibility::Compatible(_outcome) => {}
+            }
+
+            last_compatible_index = Some(i);
+        }
 
-                match outcome {
-                    Compatibility::Incompatible(_outcome) => {
-                        break;
-                    }
-                    Compatibility::Compatible(_outcome) => {}
+        Ok(last_compatible_index)
+    }
+
+    /// Each job takes the next most recent release which hasn't been checked yet, until a
+    /// release is found which ends the search: an incompatible release, or a release for which
+    /// the check failed. Releases which are less recent than that release are not checked.
+    fn find_concurrently(
+        &self,
+        search_space: &[RustRelease],
+        reporter: &impl Reporter,
+    ) -> TResult<Option<usize>> {
+        let total = search_space.len();
+        let next = AtomicUsize::new(0);
+        let end = AtomicUsize::new(total);
+        let outcomes = Mutex::new((0..total).map(|_| None).collect::<Vec<_>>());
+
+        let job = || -> TResult<()> {
+            let mut iteration = 0;
+
+            loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+
+                if i >= end.load(Ordering::SeqCst) {
+                    return Ok(());
                 }
 
-                last_compatible_index = Some(i);
+                iteration += 1;
+                reporter.report_event(Progress::new(i as u64, total as u64, iteration))?;
+
+                let toolchain = search_space[i].to_toolchain_spec();
+                let outcome = self.runner.is_compatible(&toolchain);
+
+                if !matches!(outcome, Ok(Compatibility::Compatible(_))) {
+                    end.fetch_min(i, Ordering::SeqCst);
+                }
+
+                outcomes.lock().expect("a job panicked")[i] = Some(outcome);
             }
+        };
 
-            let msrv = last_compatible_index.map(|i| &search_space[i]);
+        let jobs = self.jobs.get().min(total);
+
+        std::thread::scope(|scope| {
+            let handles = (0..jobs).map(|_| scope.spawn(job)).collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .try_for_each(|handle| handle.join().expect("a job panicked"))
+        })?;
+
+        // Interpret the outcomes in the same order as the sequential search.
+        let mut last_compatible_index = None;
+
+        for (i, outcome) in outcomes
+            .into_inner()
+            .expect("a job panicked")
+            .into_iter()
+            .enumerate()
+        {
+            match outcome {
+                Some(Ok(Compatibility::Compatible(_))) => last_compatible_index = Some(i),
+                Some(Ok(Compatibility::Incompatible(_))) | None => break,
+                Some(Err(err)) => return Err(err),
+            }
+        }
 
-            Ok(MinimumSupportedRustVersion::from_option(msrv))
-        })
+        Ok(last_compatible_index)
     }
 }
 
@@ -218,4 +319,77 @@ mod tests {
         let expected = MinimumSupportedRustVersion::NoCompatibleToolchain;
         assert_eq!(actual, expected);
     }
+
+    fn search_space_of(minors: std::ops::RangeInclusive<u64>) -> Vec<RustRelease> {
+        let releases = minors
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0)))
+            .collect::<Vec<_>>();
+
+        to_rust_releases(&releases)
+    }
+
+    #[yare::parameterized(
+        none_supported = { &[] },
+        all_supported = { &[40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50] },
+        most_recent_only = { &[50] },
+        tail = { &[44, 45, 46, 47, 48, 49, 50] },
+        not_backwards_compatible = { &[40, 41, 42, 48, 49, 50] },
+        least_recent_only = { &[40] },
+    )]
+    fn concurrent_search_finds_same_msrv(supported_minors: &[u64]) {
+        let supported = supported_minors
+            .iter()
+            .map(|minor| semver::Version::new(1, *minor, 0))
+            .collect::<Vec<_>>();
+        let runner = TestRunner::with_ok("x", supported.iter());
+        let search_space = search_space_of(40..=50);
+
+        let sequential = Linear::new(&runner)
+            .find_toolchain(&search_space, TestReporterWrapper::default().get())
+            .unwrap();
+
+        for jobs in [2, 3, 4, 16] {
+            let concurrent = Linear::new(&runner)
+                .with_jobs(NonZeroUsize::new(jobs).unwrap())
+                .find_toolchain(&search_space, TestReporterWrapper::default().get())
+                .unwrap();
+
+            assert_eq!(concurrent, sequential, "with {} jobs", jobs);
+        }
+    }
+
+    #[test]
+    fn concurrent_search_uses_separate_jobs() {
+        use std::collections::HashSet;
+        use std::thread::ThreadId;
+
+        struct JobRecorder(Mutex<HashSet<ThreadId>>);
+
+        impl IsCompatible for JobRecorder {
+            fn is_compatible(&self, toolchain: &Toolchain) -> TResult<Compatibility> {
+                self.0.lock().unwrap().insert(std::thread::current().id());
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                Ok(Compatibility::new_success(toolchain.clone()))
+            }
+        }
+
+        let runner = JobRecorder(Mutex::new(HashSet::new()));
+        let search_space = search_space_of(40..=50);
+
+        let actual = Linear::new(&runner)
+            .with_jobs(NonZeroUsize::new(3).unwrap())
+            .find_toolchain(&search_space, TestReporterWrapper::default().get())
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            MinimumSupportedRustVersion::Toolchain {
+                toolchain: Toolchain::new(semver::Version::new(1, 40, 0), "x", &[]),
+            }
+        );
+
+        let jobs = runner.0.into_inner().unwrap();
+        assert!((2..=3).contains(&jobs.len()));
+    }
 }
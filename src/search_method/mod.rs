@@ -1,10 +1,13 @@
-pub use {bisect::Bisect, linear::Linear};
+pub use {anchored::Anchored, bisect::Bisect, linear::Linear, multi_bisect::MultiBisect};
 
 use crate::TResult;
 use crate::msrv::MinimumSupportedRustVersion;
 use crate::reporter::Reporter;
 use crate::rust::RustRelease;
 
+/// Find the MSRV by starting from the declared MSRV. When the declared MSRV is still accurate, or
+/// is close to the actual MSRV, only a few checks are needed.
+pub mod anchored;
 /// Use a bisection method to find the MSRV. By using a binary search, we halve our search space each
 /// step, making this an efficient search function.
 pub mod bisect;
@@ -12,6 +15,9 @@ pub mod bisect;
 /// not very efficient, but is useful as a baseline, or if you're certain the MSRV is very close to
 /// the head.
 pub mod linear;
+/// Find the MSRV by checking multiple split points of the search space concurrently. With `k`
+/// concurrent checks, each step divides the search space into `k + 1` parts, instead of halving it.
+pub mod multi_bisect;
 
 pub trait FindMinimalSupportedRustVersion {
     /// Method to find the minimum capable toolchain.
@@ -0,0 +1,260 @@
+use crate::TResult;
+use crate::compatibility::IsCompatible;
+use crate::context::SearchMethod;
+use crate::error::NoToolchainsToTryError;
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::outcome::Compatibility;
+use crate::reporter::Reporter;
+use crate::reporter::event::{FindMsrv, Progress};
+use crate::rust::RustRelease;
+use crate::search_method::FindMinimalSupportedRustVersion;
+use std::num::NonZeroUsize;
+
+/// A k-ary search, which checks up to `jobs` split points of the remaining search space
+/// concurrently, in each iteration.
+///
+/// With a single job, this is a regular binary search. With `k` jobs, the search space shrinks
+/// by a factor of `k + 1` in each iteration, instead of by half.
+pub struct MultiBisect<'runner, R: IsCompatible> {
+    runner: &'runner R,
+    jobs: NonZeroUsize,
+}
+
+impl<'runner, R: IsCompatible> MultiBisect<'runner, R> {
+    pub fn new(runner: &'runner R) -> Self {
+        Self {
+            runner,
+            jobs: NonZeroUsize::MIN,
+        }
+    }
+
+    /// Check up to `jobs` split points concurrently.
+    pub fn with_jobs(mut self, jobs: NonZeroUsize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Check the releases at the given indices of the search space, one job per index.
+    fn run_checks(
+        &self,
+        search_space: &[RustRelease],
+        indices: &[usize],
+    ) -> Vec<TResult<Compatibility>> {
+        std::thread::scope(|scope| {
+            let handles = indices
+                .iter()
+                .map(|&i| {
+                    let toolchain = search_space[i].to_toolchain_spec();
+                    scope.spawn(move || self.runner.is_compatible(&toolchain))
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("a job panicked"))
+                .collect()
+        })
+    }
+}
+
+impl<R: IsCompatible> FindMinimalSupportedRustVersion for MultiBisect<'_, R> {
+    fn find_toolchain(
+        &self,
+        search_space: &[RustRelease],
+        reporter: &impl Reporter,
+    ) -> TResult<MinimumSupportedRustVersion> {
+        info!(?search_space);
+
+        if search_space.is_empty() {
+            return Err(NoToolchainsToTryError::new_empty().into());
+        }
+
+        reporter.run_scoped_event(FindMsrv::new(SearchMethod::MultiBisect), || {
+            let total = search_space.len() as u64;
+            let mut iteration = 0_u64;
+
+            // The releases before `compatible_until` are compatible, and the release at
+            // `incompatible_from` (if any) and those after it are incompatible. The search
+            // converges when the releases in between have all been checked.
+            let mut compatible_until = 0;
+            let mut incompatible_from = search_space.len();
+
+            while compatible_until < incompatible_from {
+                let indices = split_points(compatible_until, incompatible_from, self.jobs.get());
+
+                info!(compatible_until, incompatible_from, ?indices);
+
+                for &i in &indices {
+                    iteration += 1;
+                    reporter.report_event(Progress::new(i as u64, total, iteration))?;
+                }
+
+                let outcomes = self.run_checks(search_space, &indices);
+
+                for (&i, outcome) in indices.iter().zip(outcomes) {
+                    match outcome? {
+                        Compatibility::Compatible(_) => {}
+                        Compatibility::Incompatible(_) => {
+                            incompatible_from = incompatible_from.min(i);
+                        }
+                    }
+                }
+
+                // Only a compatible release which precedes all incompatible releases moves the
+                // compatible bound, so the bounds never cross.
+                compatible_until = indices
+                    .iter()
+                    .filter(|&&i| i < incompatible_from)
+                    .map(|i| i + 1)
+                    .fold(compatible_until, usize::max);
+            }
+
+            let msrv = compatible_until.checked_sub(1).map(|i| &search_space[i]);
+
+            Ok(MinimumSupportedRustVersion::from_option(msrv))
+        })
+    }
+}
+
+/// Pick up to `k` indices which split the range `start..end` into parts of (about) equal size.
+///
+/// If the range doesn't contain more than `k` indices, all of them are picked.
+fn split_points(start: usize, end: usize, k: usize) -> Vec<usize> {
+    let len = end - start;
+
+    if len <= k {
+        return (start..end).collect();
+    }
+
+    (1..=k).map(|j| start + j * len / (k + 1)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compatibility::TestRunner;
+    use crate::reporter::TestReporterWrapper;
+    use crate::rust::Toolchain;
+    use crate::search_method::{Bisect, Linear};
+    use crate::semver;
+    use rust_releases::Release;
+
+    fn search_space_of(minors: std::ops::RangeInclusive<u64>) -> Vec<RustRelease> {
+        minors
+            .rev()
+            .map(|minor| {
+                RustRelease::new(
+                    Release::new_stable(semver::Version::new(1, minor, 0)),
+                    "x",
+                    &[],
+                )
+            })
+            .collect()
+    }
+
+    fn msrv(minor: u64) -> MinimumSupportedRustVersion {
+        MinimumSupportedRustVersion::Toolchain {
+            toolchain: Toolchain::new(semver::Version::new(1, minor, 0), "x", &[]),
+        }
+    }
+
+    #[yare::parameterized(
+        k1 = { 0, 10, 1, &[5] },
+        k3 = { 0, 100, 3, &[25, 50, 75] },
+        k3_offset = { 10, 20, 3, &[12, 15, 17] },
+        all_remaining = { 4, 7, 3, &[4, 5, 6] },
+        single = { 4, 5, 8, &[4] },
+    )]
+    fn splits(start: usize, end: usize, k: usize, expected: &[usize]) {
+        assert_eq!(split_points(start, end, k), expected);
+    }
+
+    // Rust 1.<oldest_supported> up to and including Rust 1.40 are compatible
+    #[yare::parameterized(
+        none_supported = { 41 },
+        all_supported = { 1 },
+        most_recent_only = { 40 },
+        msrv_near_head = { 38 },
+        msrv_in_the_middle = { 17 },
+        msrv_near_tail = { 2 },
+    )]
+    fn finds_same_msrv_as_other_methods(oldest_supported: u64) {
+        let supported = (oldest_supported..=40)
+            .map(|minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+        let runner = TestRunner::with_ok("x", supported.iter());
+        let search_space = search_space_of(1..=40);
+
+        let linear = Linear::new(&runner)
+            .find_toolchain(&search_space, TestReporterWrapper::default().get())
+            .unwrap();
+        let bisect = Bisect::new(&runner)
+            .find_toolchain(&search_space, TestReporterWrapper::default().get())
+            .unwrap();
+        assert_eq!(linear, bisect);
+
+        for jobs in [1, 2, 3, 4, 7, 64] {
+            let multi_bisect = MultiBisect::new(&runner)
+                .with_jobs(NonZeroUsize::new(jobs).unwrap())
+                .find_toolchain(&search_space, TestReporterWrapper::default().get())
+                .unwrap();
+
+            assert_eq!(multi_bisect, linear, "with {} jobs", jobs);
+        }
+    }
+
+    #[test]
+    fn single_release() {
+        let runner = TestRunner::with_ok("x", &[semver::Version::new(1, 40, 0)]);
+        let search_space = search_space_of(40..=40);
+
+        let actual = MultiBisect::new(&runner)
+            .with_jobs(NonZeroUsize::new(4).unwrap())
+            .find_toolchain(&search_space, TestReporterWrapper::default().get())
+            .unwrap();
+
+        assert_eq!(actual, msrv(40));
+    }
+
+    #[test]
+    fn empty_search_space() {
+        let runner = TestRunner::with_ok("x", &[]);
+
+        let actual = MultiBisect::new(&runner)
+            .with_jobs(NonZeroUsize::new(4).unwrap())
+            .find_toolchain(&[], TestReporterWrapper::default().get());
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn fewer_iterations_with_more_jobs() {
+        use crate::reporter::Event;
+        use crate::reporter::event::Message;
+
+        let supported = (17..=40)
+            .map(|minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+        let runner = TestRunner::with_ok("x", supported.iter());
+        let search_space = search_space_of(1..=40);
+
+        // Each batch of checks is reported as a number of consecutive progress events
+        let batches = |jobs: usize| {
+            let reporter = TestReporterWrapper::default();
+            let actual = MultiBisect::new(&runner)
+                .with_jobs(NonZeroUsize::new(jobs).unwrap())
+                .find_toolchain(&search_space, reporter.get())
+                .unwrap();
+            assert_eq!(actual, msrv(17));
+
+            reporter
+                .wait_for_events()
+                .iter()
+                .filter(|event: &&Event| matches!(event.message(), Message::Progress(_)))
+                .count()
+                .div_ceil(jobs)
+        };
+
+        assert!(batches(4) < batches(1));
+    }
+}
@@ -0,0 +1,38 @@
+use crate::SubCommand;
+use crate::compatibility::CompatibilityCache;
+use crate::context::CacheContext;
+use crate::context::cache::CacheAction;
+use crate::error::TResult;
+use crate::reporter::Reporter;
+use crate::reporter::event::CacheResult;
+
+/// Manage the cache of compatibility check outcomes.
+pub struct Cache {
+    cache: CompatibilityCache,
+}
+
+impl Cache {
+    pub fn new(cache: CompatibilityCache) -> Self {
+        Self { cache }
+    }
+}
+
+impl SubCommand for Cache {
+    type Context = CacheContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        match ctx.action {
+            CacheAction::Clear => {
+                let removed = self.cache.clear()?;
+
+                reporter.report_event(CacheResult::cleared(
+                    self.cache.dir().to_path_buf(),
+                    removed,
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+//! Checks of the environment in which cargo-msrv runs, which would otherwise only fail halfway
+//! through a run.
+
+use crate::SubCommand;
+use crate::context::DoctorContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::external_command::rustup_command::RustupCommand;
+use crate::reporter::Reporter;
+use crate::reporter::event::{DoctorCheck, DoctorResult, DoctorStatus};
+use crate::rust::release_index::{INDEX_TTL, IndexAvailability, index_availability};
+use camino::Utf8Path;
+use cargo_metadata::{Metadata, MetadataCommand};
+use cargo_msrv_context::default_target::default_target;
+use rust_releases::semver;
+use std::fs::OpenOptions;
+use std::time::Duration;
+use toml_edit::DocumentMut;
+
+/// The oldest Rust release of which Cargo can read each version of the lockfile format.
+const LOCKFILE_VERSIONS: [(i64, semver::Version); 2] = [
+    (3, semver::Version::new(1, 53, 0)),
+    (4, semver::Version::new(1, 78, 0)),
+];
+
+/// Diagnose the environment, and report the outcome of each check.
+#[derive(Default)]
+pub struct Doctor;
+
+impl SubCommand for Doctor {
+    type Context = DoctorContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let manifest = ctx.environment.manifest();
+        let metadata = MetadataCommand::new()
+            .manifest_path(&manifest)
+            .no_deps()
+            .exec();
+
+        let checks = vec![
+            rustup(),
+            host_triple(),
+            installed_toolchains(),
+            release_index(ctx),
+            manifest_writability(&manifest),
+            lockfile(ctx, metadata.as_ref().ok()),
+            workspace(&metadata),
+        ];
+
+        let failed = checks
+            .iter()
+            .filter(|check| check.status == DoctorStatus::Fail)
+            .map(|check| check.name)
+            .collect::<Vec<_>>();
+
+        reporter.report_event(DoctorResult::new(checks))?;
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(CargoMSRVError::DoctorChecksFailed(failed))
+        }
+    }
+}
+
+fn rustup() -> DoctorCheck {
+    const NAME: &str = "rustup";
+
+    let output = match RustupCommand::new().with_stdout().with_stderr().version() {
+        Ok(output) => output,
+        Err(err) => {
+            return DoctorCheck::fail(
+                NAME,
+                format!(
+                    "unable to run rustup ({}), which is used to install and run toolchains",
+                    err
+                ),
+            );
+        }
+    };
+
+    if !output.exit_status().success() {
+        return DoctorCheck::fail(
+            NAME,
+            format!("`rustup --version` failed: {}", output.stderr().trim()),
+        );
+    }
+
+    match rustup_version(output.stdout()) {
+        Some(version) => DoctorCheck::pass(NAME, version),
+        None => DoctorCheck::warn(
+            NAME,
+            format!(
+                "unexpected output of `rustup --version`: '{}'",
+                output.stdout().trim()
+            ),
+        ),
+    }
+}
+
+/// The first line of `rustup --version`, e.g. `rustup 1.27.1 (54dd3d00f 2024-04-24)`.
+fn rustup_version(stdout: &str) -> Option<&str> {
+    stdout
+        .lines()
+        .next()
+        .map(str::trim)
+        .filter(|line| line.starts_with("rustup "))
+}
+
+fn host_triple() -> DoctorCheck {
+    const NAME: &str = "default-target";
+
+    match default_target() {
+        Ok(target) if is_target_triple(&target) => DoctorCheck::pass(NAME, target),
+        Ok(target) => DoctorCheck::fail(
+            NAME,
+            format!(
+                "'{}', parsed from the output of `rustup show`, is not a target triple; use --target to set the target",
+                target
+            ),
+        ),
+        Err(err) => DoctorCheck::fail(NAME, format!("{}; use --target to set the target", err)),
+    }
+}
+
+fn is_target_triple(target: &str) -> bool {
+    target.split('-').count() >= 2
+        && target
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn installed_toolchains() -> DoctorCheck {
+    const NAME: &str = "toolchains";
+
+    let output = RustupCommand::new()
+        .with_args(["list"])
+        .with_stdout()
+        .with_stderr()
+        .toolchain();
+
+    match output {
+        Ok(output) if output.exit_status().success() => match count_toolchains(output.stdout()) {
+            0 => DoctorCheck::warn(
+                NAME,
+                "no toolchains are installed; each checked toolchain is installed first",
+            ),
+            count => DoctorCheck::pass(NAME, format!("{} toolchain(s) installed", count)),
+        },
+        Ok(output) => DoctorCheck::fail(
+            NAME,
+            format!("`rustup toolchain list` failed: {}", output.stderr().trim()),
+        ),
+        Err(err) => DoctorCheck::fail(NAME, format!("unable to list toolchains: {}", err)),
+    }
+}
+
+/// The number of toolchains listed by `rustup toolchain list`.
+fn count_toolchains(stdout: &str) -> usize {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("no installed toolchains"))
+        .count()
+}
+
+fn release_index(ctx: &DoctorContext) -> DoctorCheck {
+    const NAME: &str = "release-index";
+
+    let source = &ctx.rust_releases.release_source;
+
+    match index_availability(&ctx.rust_releases) {
+        IndexAvailability::Available { releases } => DoctorCheck::pass(
+            NAME,
+            format!("{} releases available from {}", releases, source),
+        ),
+        IndexAvailability::Cached {
+            releases,
+            age,
+            fetch_error: None,
+        } => {
+            let message = format!(
+                "using the cached index of {}, with {} releases, fetched {} ago",
+                source,
+                releases,
+                describe_age(age)
+            );
+
+            if age > INDEX_TTL {
+                DoctorCheck::warn(NAME, format!("{}, which may be outdated", message))
+            } else {
+                DoctorCheck::pass(NAME, message)
+            }
+        }
+        IndexAvailability::Cached {
+            releases,
+            age,
+            fetch_error: Some(err),
+        } => DoctorCheck::warn(
+            NAME,
+            format!(
+                "unable to fetch the index from {} ({}); the cached index, with {} releases, fetched {} ago, is used instead",
+                source,
+                err,
+                releases,
+                describe_age(age)
+            ),
+        ),
+        IndexAvailability::Unavailable(err) => DoctorCheck::fail(
+            NAME,
+            format!("unable to obtain the index from {}: {}", source, err),
+        ),
+    }
+}
+
+fn describe_age(age: Duration) -> String {
+    const HOUR: u64 = 60 * 60;
+    const DAY: u64 = 24 * HOUR;
+
+    match age.as_secs() {
+        secs if secs >= DAY => format!("{} day(s)", secs / DAY),
+        secs if secs >= HOUR => format!("{} hour(s)", secs / HOUR),
+        _ => "less than an hour".to_string(),
+    }
+}
+
+fn manifest_writability(manifest: &Utf8Path) -> DoctorCheck {
+    const NAME: &str = "manifest";
+
+    if !manifest.is_file() {
+        return DoctorCheck::fail(NAME, format!("no Cargo manifest found at '{}'", manifest));
+    }
+
+    // Opening the manifest for appending doesn't modify it
+    match OpenOptions::new().append(true).open(manifest) {
+        Ok(_) => DoctorCheck::pass(NAME, format!("'{}' is writable", manifest)),
+        Err(err) => DoctorCheck::warn(
+            NAME,
+            format!(
+                "'{}' is not writable ({}), so `cargo msrv set` and `--write-msrv` will fail",
+                manifest, err
+            ),
+        ),
+    }
+}
+
+fn lockfile(ctx: &DoctorContext, metadata: Option<&Metadata>) -> DoctorCheck {
+    const NAME: &str = "lockfile";
+
+    let root = metadata.map_or_else(|| ctx.environment.root(), |m| m.workspace_root.as_path());
+    let path = root.join("Cargo.lock");
+
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return DoctorCheck::pass(
+            NAME,
+            format!(
+                "no lockfile found at '{}'; dependencies are resolved by each check",
+                path
+            ),
+        );
+    };
+
+    // The lowest `rust-version` in the workspace, which must be able to read the lockfile
+    let msrv = metadata.and_then(|metadata| {
+        metadata
+            .workspace_packages()
+            .into_iter()
+            .filter_map(|package| package.rust_version.clone())
+            .min()
+    });
+
+    lockfile_version_check(&contents, msrv.as_ref())
+        .unwrap_or_else(|message| DoctorCheck::fail(NAME, format!("'{}' {}", path, message)))
+}
+
+fn lockfile_version_check(
+    contents: &str,
+    msrv: Option<&semver::Version>,
+) -> Result<DoctorCheck, String> {
+    const NAME: &str = "lockfile";
+
+    let document = contents
+        .parse::<DocumentMut>()
+        .map_err(|err| format!("can't be parsed: {}", err.message()))?;
+
+    // Lockfiles of version 1 and 2 have no `version` key
+    let Some(version) = document
+        .get("version")
+        .and_then(|version| version.as_integer())
+    else {
+        return Ok(DoctorCheck::pass(NAME, "lockfile version 1 or 2"));
+    };
+
+    let Some((_, readable_since)) = LOCKFILE_VERSIONS.into_iter().find(|(v, _)| *v == version)
+    else {
+        return Ok(DoctorCheck::warn(
+            NAME,
+            format!("unknown lockfile version {}", version),
+        ));
+    };
+
+    let check = match msrv {
+        Some(msrv) if *msrv < readable_since => DoctorCheck::warn(
+            NAME,
+            format!(
+                "lockfile version {} can only be read by Rust {} and later, but the lowest rust-version of the workspace is {}; use --ignore-lockfile, or --resolve-msrv-lockfile, to check older toolchains",
+                version, readable_since, msrv
+            ),
+        ),
+        _ => DoctorCheck::pass(
+            NAME,
+            format!(
+                "lockfile version {}, which can be read by Rust {} and later",
+                version, readable_since
+            ),
+        ),
+    };
+
+    Ok(check)
+}
+
+fn workspace(metadata: &Result<Metadata, cargo_metadata::Error>) -> DoctorCheck {
+    const NAME: &str = "workspace";
+
+    match metadata {
+        Ok(metadata) => {
+            let members = metadata.workspace_members.len();
+            let kind = if metadata.root_package().is_some() {
+                "workspace"
+            } else {
+                "virtual workspace"
+            };
+
+            DoctorCheck::pass(
+                NAME,
+                format!(
+                    "{} at '{}', with {} member(s)",
+                    kind, metadata.workspace_root, members
+                ),
+            )
+        }
+        Err(err) => DoctorCheck::fail(NAME, format!("unable to read the workspace: {}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        version = { "rustup 1.27.1 (54dd3d00f 2024-04-24)\n", Some("rustup 1.27.1 (54dd3d00f 2024-04-24)") },
+        empty = { "", None },
+        unexpected = { "command not found\n", None },
+    )]
+    fn parses_rustup_version(stdout: &str, expected: Option<&str>) {
+        assert_eq!(rustup_version(stdout), expected);
+    }
+
+    #[yare::parameterized(
+        linux = { "x86_64-unknown-linux-gnu", true },
+        wasi = { "wasm32-wasip1", true },
+        word = { "host:", false },
+        empty = { "", false },
+    )]
+    fn recognizes_target_triple(target: &str, expected: bool) {
+        assert_eq!(is_target_triple(target), expected);
+    }
+
+    #[yare::parameterized(
+        none = { "no installed toolchains\n", 0 },
+        some = { "stable-x86_64-unknown-linux-gnu (default)\n1.70.0-x86_64-unknown-linux-gnu\n", 2 },
+    )]
+    fn counts_toolchains(stdout: &str, expected: usize) {
+        assert_eq!(count_toolchains(stdout), expected);
+    }
+
+    #[yare::parameterized(
+        unversioned = { "[[package]]\nname = \"a\"\n", None, DoctorStatus::Pass },
+        v3 = { "version = 3\n", Some(semver::Version::new(1, 60, 0)), DoctorStatus::Pass },
+        v4_without_msrv = { "version = 4\n", None, DoctorStatus::Pass },
+        v4_readable_by_msrv = { "version = 4\n", Some(semver::Version::new(1, 78, 0)), DoctorStatus::Pass },
+        v4_unreadable_by_msrv = { "version = 4\n", Some(semver::Version::new(1, 70, 0)), DoctorStatus::Warn },
+        unknown = { "version = 9\n", None, DoctorStatus::Warn },
+    )]
+    fn checks_lockfile_version(
+        contents: &str,
+        msrv: Option<semver::Version>,
+        expected: DoctorStatus,
+    ) {
+        let check = lockfile_version_check(contents, msrv.as_ref()).unwrap();
+
+        assert_eq!(check.status, expected);
+    }
+
+    #[test]
+    fn rejects_invalid_lockfile() {
+        assert!(lockfile_version_check("version = ", None).is_err());
+    }
+
+    #[test]
+    fn missing_manifest_fails() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let manifest = camino::Utf8PathBuf::from_path_buf(tmp.path().join("Cargo.toml")).unwrap();
+
+        assert_eq!(manifest_writability(&manifest).status, DoctorStatus::Fail);
+
+        std::fs::write(&manifest, "").unwrap();
+        assert_eq!(manifest_writability(&manifest).status, DoctorStatus::Pass);
+    }
+
+    #[yare::parameterized(
+        minutes = { 60, "less than an hour" },
+        hours = { 3 * 60 * 60, "3 hour(s)" },
+        days = { 2 * 24 * 60 * 60 + 1, "2 day(s)" },
+    )]
+    fn describes_age(secs: u64, expected: &str) {
+        assert_eq!(describe_age(Duration::from_secs(secs)), expected);
+    }
+}
@@ -0,0 +1,393 @@
+//! Blame the dependency which limits the MSRV.
+//!
+//! The Rust release which precedes the found MSRV is the most recent incompatible release.
+//! If its check failed because of a dependency, either because the dependency declares a
+//! greater `rust-version`, or because it failed to build, that dependency limits the MSRV.
+
+use crate::compatibility::IsCompatible;
+use crate::context::FindContext;
+use crate::dependency_graph::DependencyGraph;
+use crate::dependency_graph::resolver::{CargoMetadataResolver, DependencyResolver};
+use crate::error::TResult;
+use crate::outcome::{Compatibility, Incompatible};
+use crate::reporter::Reporter;
+use crate::reporter::event::{IncompatibilityCause, LimitingDependency, LimitingReason};
+use crate::rust::Toolchain;
+use crate::semver;
+use cargo_metadata::Package;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::Bfs;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Records the incompatible outcomes of the checks run by the wrapped runner, so the cause of
+/// an incompatibility can be inspected after the search.
+pub(super) struct RecordIncompatible<'runner, R> {
+    runner: &'runner R,
+    incompatible: Mutex<Vec<Incompatible>>,
+}
+
+impl<'runner, R: IsCompatible> RecordIncompatible<'runner, R> {
+    pub fn new(runner: &'runner R) -> Self {
+        Self {
+            runner,
+            incompatible: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The recorded outcome of the check of the given Rust version, if it was incompatible.
+    pub fn incompatible(&self, version: &semver::Version) -> Option<Incompatible> {
+        self.incompatible
+            .lock()
+            .expect("unable to access the recorded outcomes")
+            .iter()
+            .find(|outcome| outcome.toolchain_spec.version() == version)
+            .cloned()
+    }
+
+    fn record(&self, outcome: TResult<Compatibility>) -> TResult<Compatibility> {
+        if let Ok(Compatibility::Incompatible(incompatible)) = &outcome {
+            self.incompatible
+                .lock()
+                .expect("unable to access the recorded outcomes")
+                .push(incompatible.clone());
+        }
+
+        outcome
+    }
+}
+
+impl<R: IsCompatible> IsCompatible for RecordIncompatible<'_, R> {
+    fn before(&self, toolchain: &Toolchain) -> TResult<()> {
+        self.runner.before(toolchain)
+    }
+
+    fn is_compatible(&self, toolchain: &Toolchain) -> TResult<Compatibility> {
+        self.record(self.runner.is_compatible(toolchain))
+    }
+
+    fn after(&self, toolchain: &Toolchain) -> TResult<()> {
+        self.runner.after(toolchain)
+    }
+}
+
+/// Report the dependency which made the given, most recent incompatible, Rust release
+/// incompatible, if a dependency is to blame.
+///
+/// Blaming a dependency is best effort: when the dependency graph can't be resolved, nothing
+/// is reported.
+pub(super) fn blame_dependency(
+    ctx: &FindContext,
+    reporter: &impl Reporter,
+    incompatible: &Incompatible,
+    package: Option<&str>,
+) -> TResult<()> {
+    let Some(suspect) = Suspect::from_outcome(incompatible) else {
+        return Ok(());
+    };
+
+    let resolver = CargoMetadataResolver::from_manifest_path(&ctx.environment.manifest());
+    let graph = match resolver.resolve() {
+        Ok(graph) => graph,
+        Err(err) => {
+            info!(%err, "unable to resolve the dependency graph to blame a dependency");
+            return Ok(());
+        }
+    };
+
+    let incompatible_version = incompatible.toolchain_spec.version();
+
+    if let Some(blamed) = blame(&graph, &suspect, incompatible_version) {
+        let event = match package {
+            Some(package) => blamed.with_package(package),
+            None => blamed,
+        };
+
+        reporter.report_event(event)?;
+    }
+
+    Ok(())
+}
+
+/// The dependency which is suspected to cause an incompatibility, as named by Cargo.
+#[derive(Debug, Eq, PartialEq)]
+enum Suspect {
+    /// A dependency which requires a newer Rust version, if named.
+    RustVersion {
+        name: Option<String>,
+        version: Option<semver::Version>,
+    },
+    /// A crate which failed to build.
+    BuildFailure { name: String },
+}
+
+impl Suspect {
+    fn from_outcome(incompatible: &Incompatible) -> Option<Self> {
+        let stderr = incompatible.error_message.as_str();
+        let requires_rust_version = incompatible.diagnosis.as_ref().is_some_and(|diagnosis| {
+            diagnosis.cause() == IncompatibilityCause::DependencyRustVersion
+        });
+
+        if requires_rust_version {
+            let (name, version) = rust_version_requirement(stderr).unzip();
+            let version = version.and_then(|version| semver::Version::parse(version).ok());
+
+            return Some(Self::RustVersion {
+                name: name.map(String::from),
+                version,
+            });
+        }
+
+        backticked_after(stderr, "could not compile `").map(|name| Self::BuildFailure {
+            name: name.to_string(),
+        })
+    }
+}
+
+/// The name and version of the package which requires a newer Rust version, as reported by
+/// Cargo.
+fn rust_version_requirement(stderr: &str) -> Option<(&str, &str)> {
+    // Cargo 1.56 up to 1.78: package `dep v1.2.3` cannot be built because it requires rustc ...
+    if let Some(package) = backticked_after(stderr, "package `")
+        && let Some((name, version)) = package.split_once(" v")
+    {
+        return Some((name, version));
+    }
+
+    // More recent versions: rustc 1.60.0 is not supported by the following package:
+    //   dep@1.2.3 requires rustc 1.70
+    stderr
+        .lines()
+        .skip_while(|line| !line.contains("is not supported by the following package"))
+        .skip(1)
+        .find_map(|line| {
+            let (package, _) = line.trim().split_once(" requires rustc")?;
+            package.split_once('@')
+        })
+}
+
+/// The text between the given prefix, which ends with a backtick, and the next backtick.
+fn backticked_after<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(prefix)?;
+    rest.split_once('`').map(|(quoted, _)| quoted)
+}
+
+/// Find the suspected dependency in the dependency graph, and the path to it from the root crate.
+fn blame(
+    graph: &DependencyGraph,
+    suspect: &Suspect,
+    incompatible_version: &semver::Version,
+) -> Option<LimitingDependency> {
+    let root = NodeIndex::new(*graph.index().get(graph.root_crate())?);
+    let packages = graph.packages();
+
+    // Visit the dependencies breadth first, so the path to each dependency is a shortest path
+    let mut parents = HashMap::new();
+    let mut visited = Vec::new();
+    let mut bfs = Bfs::new(packages, root);
+
+    while let Some(node) = bfs.next(packages) {
+        for child in packages.neighbors(node) {
+            parents.entry(child).or_insert(node);
+        }
+
+        // The root crate itself is never to blame
+        if node != root {
+            visited.push(node);
+        }
+    }
+
+    let is_named = |package: &Package, name: &str| package.name.as_str() == name;
+
+    let (node, reason) = match suspect {
+        Suspect::RustVersion {
+            name: Some(name),
+            version,
+        } => visited
+            .iter()
+            .find(|&&node| {
+                let package = &packages[node];
+                is_named(package, name) && version.as_ref().is_none_or(|v| &package.version == v)
+            })
+            .map(|&node| (node, LimitingReason::RustVersion)),
+        // When Cargo didn't name the dependency, blame the nearest dependency which declares a
+        // greater `rust-version`
+        Suspect::RustVersion { name: None, .. } => visited
+            .iter()
+            .find(|&&node| {
+                packages[node]
+                    .rust_version
+                    .as_ref()
+                    .is_some_and(|rust_version| rust_version > incompatible_version)
+            })
+            .map(|&node| (node, LimitingReason::RustVersion)),
+        Suspect::BuildFailure { name } => visited
+            .iter()
+            .find(|&&node| is_named(&packages[node], name))
+            .map(|&node| (node, LimitingReason::BuildFailure)),
+    }?;
+
+    let mut path = vec![node];
+    while let Some(&parent) = parents.get(path.last()?) {
+        path.push(parent);
+
+        if parent == root {
+            break;
+        }
+    }
+
+    let path = path
+        .iter()
+        .rev()
+        .map(|&node| packages[node].name.to_string())
+        .collect();
+
+    let package = &packages[node];
+    let rust_version = package.rust_version.as_ref().map(ToString::to_string);
+
+    Some(
+        LimitingDependency::new(
+            package.name.as_str(),
+            package.version.clone(),
+            path,
+            reason,
+            incompatible_version.clone(),
+        )
+        .with_rust_version(rust_version),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Diagnosis;
+
+    fn package(name: &str, version: &str, rust_version: Option<&str>) -> Package {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "version": version,
+            "id": format!("{}@{}", name, version),
+            "dependencies": [],
+            "targets": [],
+            "features": {},
+            "manifest_path": format!("/{}/Cargo.toml", name),
+            "rust_version": rust_version,
+        }))
+        .unwrap()
+    }
+
+    // root → a (1.60) → c (1.70)
+    // root → b → d
+    fn graph() -> DependencyGraph {
+        let packages = [
+            package("root", "0.1.0", None),
+            package("a", "1.0.0", Some("1.60")),
+            package("b", "2.0.0", None),
+            package("c", "3.0.0", Some("1.70")),
+            package("d", "4.0.0", None),
+        ];
+
+        let mut graph = DependencyGraph::with_capacity(packages[0].id.clone(), packages.len());
+        for package in packages {
+            graph.add_package(package);
+        }
+
+        let index = |name: &str| {
+            graph
+                .index()
+                .iter()
+                .find(|(id, _)| id.repr.starts_with(&format!("{}@", name)))
+                .map(|(_, &index)| index)
+                .unwrap()
+        };
+
+        let edges = [("root", "a"), ("root", "b"), ("a", "c"), ("b", "d")]
+            .map(|(from, to)| (index(from), index(to)));
+        for (from, to) in edges {
+            graph.add_dependency(from, to);
+        }
+
+        graph
+    }
+
+    fn incompatible(error_message: &str, cause: Option<IncompatibilityCause>) -> Incompatible {
+        Incompatible {
+            toolchain_spec: Toolchain::new(semver::Version::new(1, 69, 0), "x", &[]),
+            error_message: error_message.to_string(),
+            diagnosis: cause.map(|cause| Diagnosis::new(cause, "")),
+        }
+    }
+
+    #[yare::parameterized(
+        old_cargo = {
+            "error: package `c v3.0.0` cannot be built because it requires rustc 1.70 or newer, while the currently active rustc version is 1.69.0\n",
+            Some(("c", "3.0.0"))
+        },
+        recent_cargo = {
+            "error: rustc 1.69.0 is not supported by the following package:\n  c@3.0.0 requires rustc 1.70\n",
+            Some(("c", "3.0.0"))
+        },
+        unnamed = { "error: something else\n", None },
+    )]
+    fn parses_rust_version_requirement(stderr: &str, expected: Option<(&str, &str)>) {
+        assert_eq!(rust_version_requirement(stderr), expected);
+    }
+
+    #[test]
+    fn blames_dependency_which_requires_newer_rust_version() {
+        let outcome = incompatible(
+            "error: package `c v3.0.0` cannot be built because it requires rustc 1.70 or newer, while the currently active rustc version is 1.69.0\n",
+            Some(IncompatibilityCause::DependencyRustVersion),
+        );
+        let suspect = Suspect::from_outcome(&outcome).unwrap();
+
+        let blamed = blame(&graph(), &suspect, outcome.toolchain_spec.version()).unwrap();
+
+        assert_eq!(blamed.dependency(), "c");
+        assert_eq!(blamed.version(), &semver::Version::new(3, 0, 0));
+        assert_eq!(blamed.path(), ["root", "a", "c"]);
+        assert_eq!(blamed.reason(), LimitingReason::RustVersion);
+        assert_eq!(blamed.rust_version(), Some("1.70.0"));
+    }
+
+    #[test]
+    fn blames_nearest_dependency_with_greater_rust_version_if_unnamed() {
+        let outcome = incompatible("", Some(IncompatibilityCause::DependencyRustVersion));
+        let suspect = Suspect::from_outcome(&outcome).unwrap();
+
+        let blamed = blame(&graph(), &suspect, outcome.toolchain_spec.version()).unwrap();
+
+        assert_eq!(blamed.dependency(), "c");
+        assert_eq!(blamed.path(), ["root", "a", "c"]);
+    }
+
+    #[test]
+    fn blames_dependency_which_fails_to_build() {
+        let outcome = incompatible(
+            "error[E0658]: use of unstable library feature\nerror: could not compile `d` (lib) due to 1 previous error\n",
+            Some(IncompatibilityCause::UnstableFeature),
+        );
+        let suspect = Suspect::from_outcome(&outcome).unwrap();
+
+        let blamed = blame(&graph(), &suspect, outcome.toolchain_spec.version()).unwrap();
+
+        assert_eq!(blamed.dependency(), "d");
+        assert_eq!(blamed.path(), ["root", "b", "d"]);
+        assert_eq!(blamed.reason(), LimitingReason::BuildFailure);
+    }
+
+    #[test]
+    fn root_crate_is_not_blamed() {
+        let outcome = incompatible("error: could not compile `root`\n", None);
+        let suspect = Suspect::from_outcome(&outcome).unwrap();
+
+        assert!(blame(&graph(), &suspect, outcome.toolchain_spec.version()).is_none());
+    }
+
+    #[test]
+    fn no_suspect() {
+        let outcome = incompatible("error: unrelated\n", None);
+
+        assert!(Suspect::from_outcome(&outcome).is_none());
+    }
+}
@@ -0,0 +1,280 @@
+use rust_releases::ReleaseIndex;
+
+use super::workspace::package_context;
+use super::{search, write_found_msrv};
+use crate::compatibility::{
+    IsCompatible, PackageRunCommandProvider, RunCommand, RunCommandProvider,
+};
+use crate::context::{FeatureSetsContext, FindContext};
+use crate::error::{CargoMSRVError, TResult};
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::reporter::Reporter;
+use crate::reporter::event::{FeatureSetMsrv, FindFeatureSetsSummary};
+use crate::writer::toolchain_file::write_toolchain_file;
+use crate::{SubCommand, semver};
+
+/// Find the MSRV of each combination of the features of a package.
+///
+/// Each feature set is checked without the default features, using a runner which is provided
+/// by the `runner_factory` for the compatibility check command of the feature set.
+pub struct FindFeatureSets<'index, F> {
+    release_index: &'index ReleaseIndex,
+    runner_factory: F,
+}
+
+impl<'index, C, F> FindFeatureSets<'index, F>
+where
+    C: IsCompatible,
+    F: Fn(RunCommand) -> C,
+{
+    pub fn new(release_index: &'index ReleaseIndex, runner_factory: F) -> Self {
+        Self {
+            release_index,
+            runner_factory,
+        }
+    }
+}
+
+impl<C, F> SubCommand for FindFeatureSets<'_, F>
+where
+    C: IsCompatible,
+    F: Fn(RunCommand) -> C,
+{
+    type Context = FindContext;
+    type Output = semver::Version;
+
+    /// Returns the MSRV of the package, which is the greatest MSRV of all feature sets.
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        find_feature_sets_msrv(ctx, reporter, self.release_index, &self.runner_factory)
+    }
+}
+
+fn find_feature_sets_msrv<C: IsCompatible>(
+    ctx: &FindContext,
+    reporter: &impl Reporter,
+    release_index: &ReleaseIndex,
+    runner_factory: &impl Fn(RunCommand) -> C,
+) -> TResult<semver::Version> {
+    let settings = ctx.feature_sets.clone().unwrap_or_default();
+
+    let package = match ctx.environment.workspace_packages.selected_packages() {
+        [package] => package,
+        packages => {
+            return Err(CargoMSRVError::FeatureSetsRequireSinglePackage {
+                selected: packages.len(),
+            });
+        }
+    };
+
+    // An explicitly selected package is checked from the workspace root, like a workspace search
+    let selected = ctx
+        .environment
+        .workspace_packages
+        .is_explicit_selection()
+        .then(|| package.name.as_str());
+
+    let base_ctx = match selected {
+        Some(_) => package_context(ctx, package),
+        None => ctx.clone(),
+    };
+
+    let features = package
+        .features
+        .keys()
+        .filter(|feature| feature.as_str() != "default")
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+
+    for feature_set in feature_sets(&features, &settings) {
+        let set_ctx = feature_set_context(&base_ctx, &feature_set);
+        let runner = runner_factory(run_command(&set_ctx, selected));
+
+        info!(features = ?feature_set, "searching for feature set MSRV");
+
+        let msrv = match search(&set_ctx, reporter, release_index, &runner, selected)? {
+            MinimumSupportedRustVersion::Toolchain { toolchain } => {
+                Some(toolchain.version().clone())
+            }
+            MinimumSupportedRustVersion::NoCompatibleToolchain => None,
+        };
+
+        results.push(FeatureSetMsrv::new(feature_set, msrv));
+    }
+
+    let summary = FindFeatureSetsSummary::new(results);
+    let msrv = summary.msrv().cloned();
+    let undetermined = summary
+        .feature_sets()
+        .iter()
+        .find(|set| set.msrv.is_none())
+        .map(|set| set.features.clone());
+
+    reporter.report_event(summary)?;
+
+    match (msrv, undetermined) {
+        (Some(version), _) => {
+            if ctx.write_toolchain_file {
+                write_toolchain_file(reporter, &version, ctx.environment.root())?;
+            }
+
+            if ctx.write_msrv {
+                write_found_msrv(&base_ctx, reporter, release_index, &version)?;
+            }
+
+            Ok(version)
+        }
+        (None, features) => {
+            info!(
+                ?features,
+                "no minimal-compatible toolchain found for feature set"
+            );
+
+            let command = features
+                .map(|features| {
+                    run_command(&feature_set_context(&base_ctx, &features), selected)
+                        .components()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+
+            Err(CargoMSRVError::UnableToFindAnyGoodVersion { command })
+        }
+    }
+}
+
+/// The feature sets to search, ordered from small to large, starting with the empty feature set.
+///
+/// Feature sets are combinations of the given features, which are not excluded, and contain at
+/// most `max_depth` features.
+fn feature_sets(features: &[String], settings: &FeatureSetsContext) -> Vec<Vec<String>> {
+    let features = features
+        .iter()
+        .filter(|feature| !settings.exclude.contains(feature))
+        .collect::<Vec<_>>();
+
+    let max_depth = settings
+        .max_depth
+        .map_or(features.len(), |depth| depth.get().min(features.len()));
+
+    let mut sets = vec![Vec::new()];
+
+    for depth in 1..=max_depth {
+        combinations(&features, depth, 0, &mut Vec::new(), &mut sets);
+    }
+
+    sets
+}
+
+/// Push each combination of `k` of the features, starting at index `from`, to `sets`.
+fn combinations(
+    features: &[&String],
+    k: usize,
+    from: usize,
+    current: &mut Vec<String>,
+    sets: &mut Vec<Vec<String>>,
+) {
+    if current.len() == k {
+        sets.push(current.clone());
+        return;
+    }
+
+    for i in from..features.len() {
+        current.push(features[i].clone());
+        combinations(features, k, i + 1, current, sets);
+        current.pop();
+    }
+}
+
+/// The context for a single feature set, which is checked without the default features.
+fn feature_set_context(ctx: &FindContext, features: &[String]) -> FindContext {
+    let mut set_ctx = ctx.clone();
+
+    set_ctx.check_cmd.cargo_features = (!features.is_empty()).then(|| features.to_vec());
+    set_ctx.check_cmd.cargo_all_features = false;
+    set_ctx.check_cmd.cargo_no_default_features = true;
+
+    set_ctx
+}
+
+fn run_command(ctx: &FindContext, package: Option<&str>) -> RunCommand {
+    match package {
+        Some(package) => ctx.provide_package_run_command(package),
+        None => ctx.provide_run_command(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    fn features(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn powerset() {
+        let sets = feature_sets(&features(&["a", "b", "c"]), &FeatureSetsContext::default());
+
+        assert_eq!(
+            sets,
+            vec![
+                features(&[]),
+                features(&["a"]),
+                features(&["b"]),
+                features(&["c"]),
+                features(&["a", "b"]),
+                features(&["a", "c"]),
+                features(&["b", "c"]),
+                features(&["a", "b", "c"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn powerset_with_max_depth() {
+        let settings = FeatureSetsContext {
+            max_depth: NonZeroUsize::new(1),
+            exclude: vec![],
+        };
+        let sets = feature_sets(&features(&["a", "b", "c"]), &settings);
+
+        assert_eq!(
+            sets,
+            vec![
+                features(&[]),
+                features(&["a"]),
+                features(&["b"]),
+                features(&["c"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn powerset_with_exclusions() {
+        let settings = FeatureSetsContext {
+            max_depth: None,
+            exclude: features(&["b", "unknown"]),
+        };
+        let sets = feature_sets(&features(&["a", "b", "c"]), &settings);
+
+        assert_eq!(
+            sets,
+            vec![
+                features(&[]),
+                features(&["a"]),
+                features(&["c"]),
+                features(&["a", "c"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn powerset_without_features() {
+        let sets = feature_sets(&[], &FeatureSetsContext::default());
+
+        assert_eq!(sets, vec![features(&[])]);
+    }
+}
@@ -6,12 +6,17 @@ use crate::error::{CargoMSRVError, NoToolchainsToTryError, TResult};
 use crate::msrv::MinimumSupportedRustVersion;
 use crate::reporter::Reporter;
 use crate::reporter::event::FindResult;
-use crate::rust::RustRelease;
 use crate::rust::releases_filter::ReleasesFilter;
-use crate::search_method::{Bisect, FindMinimalSupportedRustVersion, Linear};
+use crate::rust::{RustRelease, Toolchain};
+use crate::search_method::{
+    Anchored, Bisect, FindMinimalSupportedRustVersion, Linear, MultiBisect,
+};
+use crate::sub_command::find::blame::{RecordIncompatible, blame_dependency};
+use crate::sub_command::find::monotonicity::verify_monotonicity;
 use crate::writer::toolchain_file::write_toolchain_file;
 use crate::writer::write_msrv::write_msrv;
 use crate::{SubCommand, semver};
+use cargo_msrv_context::context::verify::RustVersion;
 use cargo_msrv_types::BareVersion;
 
 pub struct Find<'index, C: IsCompatible> {
@@ -43,7 +48,7 @@ fn find_msrv(
     release_index: &ReleaseIndex,
     runner: &impl IsCompatible,
 ) -> TResult<semver::Version> {
-    let search_result = search(ctx, reporter, release_index, runner)?;
+    let search_result = search(ctx, reporter, release_index, runner, None)?;
 
     match &search_result {
         MinimumSupportedRustVersion::NoCompatibleToolchain => {
@@ -68,16 +73,7 @@ fn find_msrv(
             }
 
             if ctx.write_msrv {
-                let environment_ctx = ctx.environment.clone();
-                let rust_releases_ctx = ctx.rust_releases.clone();
-
-                write_msrv(
-                    reporter,
-                    BareVersion::two_component_from_semver(toolchain.version()),
-                    Some(release_index), // Reuse the already obtained index
-                    environment_ctx,
-                    rust_releases_ctx,
-                )?;
+                write_found_msrv(ctx, reporter, release_index, version)?;
             }
 
             Ok(version.clone())
@@ -85,11 +81,30 @@ fn find_msrv(
     }
 }
 
+fn write_found_msrv(
+    ctx: &FindContext,
+    reporter: &impl Reporter,
+    release_index: &ReleaseIndex,
+    version: &semver::Version,
+) -> TResult<()> {
+    let environment_ctx = ctx.environment.clone();
+    let rust_releases_ctx = ctx.rust_releases.clone();
+
+    write_msrv(
+        reporter,
+        BareVersion::two_component_from_semver(version),
+        Some(release_index), // Reuse the already obtained index
+        environment_ctx,
+        rust_releases_ctx,
+    )
+}
+
 fn search(
     ctx: &FindContext,
     reporter: &impl Reporter,
     index: &ReleaseIndex,
     runner: &impl IsCompatible,
+    package: Option<&str>,
 ) -> TResult<MinimumSupportedRustVersion> {
     let releases = index.releases();
 
@@ -104,7 +119,7 @@ fn search(
     );
 
     let included_releases = releases_filter.filter(releases);
-    run_with_search_method(ctx, &included_releases, reporter, runner)
+    run_with_search_method(ctx, &included_releases, reporter, runner, package)
 }
 
 fn run_with_search_method(
@@ -112,26 +127,66 @@ fn run_with_search_method(
     included_releases: &[Release],
     reporter: &impl Reporter,
     runner: &impl IsCompatible,
+    package: Option<&str>,
 ) -> TResult<MinimumSupportedRustVersion> {
     let search_method = ctx.search_method;
     info!(?search_method);
 
-    // Run a linear or binary search depending on the configuration
+    // The outcomes are recorded, so the cause of the most recent incompatibility can be blamed
+    let runner = &RecordIncompatible::new(runner);
+
+    // Run a linear, binary, k-ary or anchored search depending on the configuration
     match search_method {
-        SearchMethod::Linear => {
-            run_searcher(&Linear::new(runner), included_releases, ctx, reporter)
-        }
-        SearchMethod::Bisect => {
-            run_searcher(&Bisect::new(runner), included_releases, ctx, reporter)
-        }
+        SearchMethod::Linear => run_searcher(
+            &Linear::new(runner).with_jobs(ctx.jobs),
+            included_releases,
+            runner,
+            ctx,
+            reporter,
+            package,
+        ),
+        SearchMethod::Bisect => run_searcher(
+            &Bisect::new(runner),
+            included_releases,
+            runner,
+            ctx,
+            reporter,
+            package,
+        ),
+        SearchMethod::MultiBisect => run_searcher(
+            &MultiBisect::new(runner).with_jobs(ctx.jobs),
+            included_releases,
+            runner,
+            ctx,
+            reporter,
+            package,
+        ),
+        SearchMethod::Anchored => run_searcher(
+            &Anchored::new(runner, declared_msrv(ctx)),
+            included_releases,
+            runner,
+            ctx,
+            reporter,
+            package,
+        ),
     }
 }
 
+/// The MSRV declared in the Cargo manifest, if any, from which the anchored search starts.
+fn declared_msrv(ctx: &FindContext) -> Option<BareVersion> {
+    RustVersion::try_from_environment(&ctx.environment)
+        .inspect_err(|error| info!(%error, "no declared MSRV to anchor the search"))
+        .ok()
+        .map(|rust_version| rust_version.version().clone())
+}
+
 fn run_searcher(
     method: &impl FindMinimalSupportedRustVersion,
     releases: &[Release],
+    runner: &RecordIncompatible<'_, impl IsCompatible>,
     ctx: &FindContext,
     reporter: &impl Reporter,
+    package: Option<&str>,
 ) -> TResult<MinimumSupportedRustVersion> {
     let searchable_releases = releases
         .iter()
@@ -151,16 +206,49 @@ fn run_searcher(
             _ => err,
         })?;
 
-    report_outcome(&minimum_capable, releases, ctx, reporter)?;
+    if let (Some(samples), MinimumSupportedRustVersion::Toolchain { toolchain }) =
+        (ctx.verify_monotonicity, &minimum_capable)
+    {
+        verify_monotonicity(
+            runner,
+            &searchable_releases,
+            toolchain,
+            samples,
+            reporter,
+            package,
+        )?;
+    }
+
+    if let MinimumSupportedRustVersion::Toolchain { toolchain } = &minimum_capable
+        && let Some(incompatible) = preceding_release(&searchable_releases, toolchain)
+            .and_then(|release| runner.incompatible(release.version()))
+    {
+        blame_dependency(ctx, reporter, &incompatible, package)?;
+    }
+
+    report_outcome(&minimum_capable, releases, ctx, reporter, package)?;
 
     Ok(minimum_capable)
 }
 
+/// The release which precedes the release of the given toolchain in the search space.
+fn preceding_release<'r>(
+    search_space: &'r [RustRelease],
+    toolchain: &Toolchain,
+) -> Option<&'r RustRelease> {
+    let position = search_space
+        .iter()
+        .position(|release| release.version() == toolchain.version())?;
+
+    search_space.get(position + 1)
+}
+
 fn report_outcome(
     minimum_capable: &MinimumSupportedRustVersion,
     releases: &[Release],
     ctx: &FindContext,
     reporter: &impl Reporter,
+    package: Option<&str>,
 ) -> TResult<()> {
     let (min, max) = min_max_releases(releases)?;
 
@@ -179,27 +267,41 @@ fn report_outcome(
     let target = ctx.toolchain.target;
     let search_method = ctx.search_method;
 
-    match minimum_capable {
-        MinimumSupportedRustVersion::Toolchain { toolchain } => {
-            let version = toolchain.version();
-
-            reporter.report_event(FindResult::new_msrv(
-                version.clone(),
-                target,
-                minimum_considered,
-                maximum_considered,
-                search_method,
-            ))?;
-        }
-        MinimumSupportedRustVersion::NoCompatibleToolchain => {
-            reporter.report_event(FindResult::none(
-                target,
-                minimum_considered,
-                maximum_considered,
-                search_method,
-            ))?;
-        }
-    }
+    let result = match minimum_capable {
+        MinimumSupportedRustVersion::Toolchain { toolchain } => FindResult::new_msrv(
+            toolchain.version().clone(),
+            target,
+            minimum_considered,
+            maximum_considered,
+            search_method,
+        ),
+        MinimumSupportedRustVersion::NoCompatibleToolchain => FindResult::none(
+            target,
+            minimum_considered,
+            maximum_considered,
+            search_method,
+        ),
+    };
+
+    let result = match package {
+        Some(package) => result.with_package(package),
+        None => result,
+    };
+
+    // When searching multiple targets, each result is attributed to its target
+    let result = if ctx.additional_targets.is_empty() {
+        result
+    } else {
+        result.with_attributed_target()
+    };
+
+    // When searching multiple feature sets, each result is attributed to its feature set
+    let result = match &ctx.feature_sets {
+        Some(_) => result.with_features(ctx.check_cmd.cargo_features.clone().unwrap_or_default()),
+        None => result,
+    };
+
+    reporter.report_event(result)?;
 
     Ok(())
 }
@@ -217,5 +319,15 @@ fn min_max_releases(rust_releases: &[Release]) -> TResult<(BareVersion, BareVers
     Ok((min.into(), max.into()))
 }
 
+mod blame;
+mod feature_sets;
+mod monotonicity;
+mod targets;
+mod workspace;
+
+pub use feature_sets::FindFeatureSets;
+pub use targets::FindTargets;
+pub use workspace::FindWorkspace;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,171 @@
+use crate::TResult;
+use crate::compatibility::IsCompatible;
+use crate::outcome::Compatibility;
+use crate::reporter::Reporter;
+use crate::reporter::event::NonMonotonicCompatibility;
+use crate::rust::{RustRelease, Toolchain};
+use std::num::NonZeroUsize;
+
+/// Check a sample of the releases which are more recent than the MSRV, and report the
+/// incompatible ones, if any.
+///
+/// The search space must be ordered from most to least recent.
+pub(super) fn verify_monotonicity(
+    runner: &impl IsCompatible,
+    search_space: &[RustRelease],
+    msrv: &Toolchain,
+    samples: NonZeroUsize,
+    reporter: &impl Reporter,
+    package: Option<&str>,
+) -> TResult<()> {
+    let Some(msrv_index) = search_space
+        .iter()
+        .position(|release| release.version() == msrv.version())
+    else {
+        return Ok(());
+    };
+
+    let mut holes = Vec::new();
+
+    for i in sample(msrv_index, samples.get()) {
+        let toolchain = search_space[i].to_toolchain_spec();
+
+        if let Compatibility::Incompatible(_) = runner.is_compatible(&toolchain)? {
+            holes.push(toolchain.version().clone());
+        }
+    }
+
+    info!(%msrv, ?holes, "verified monotonicity");
+
+    if holes.is_empty() {
+        return Ok(());
+    }
+
+    holes.sort();
+
+    let event = NonMonotonicCompatibility::new(msrv.version().clone(), holes);
+    let event = match package {
+        Some(package) => event.with_package(package),
+        None => event,
+    };
+
+    reporter.report_event(event)?;
+
+    Ok(())
+}
+
+/// Pick up to `k` indices from `0..len`, spread evenly.
+///
+/// The release right after the MSRV (`len - 1`) is always picked, and, given `k > 1`, the most
+/// recent release (`0`) as well.
+fn sample(len: usize, k: usize) -> Vec<usize> {
+    if len <= k {
+        return (0..len).collect();
+    }
+
+    if k == 1 {
+        return vec![len - 1];
+    }
+
+    (0..k).map(|j| j * (len - 1) / (k - 1)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compatibility::TestRunner;
+    use crate::reporter::event::Message;
+    use crate::reporter::{Event, TestReporterWrapper};
+    use crate::semver;
+    use rust_releases::Release;
+
+    fn search_space_of(minors: std::ops::RangeInclusive<u64>) -> Vec<RustRelease> {
+        minors
+            .rev()
+            .map(|minor| {
+                RustRelease::new(
+                    Release::new_stable(semver::Version::new(1, minor, 0)),
+                    "x",
+                    &[],
+                )
+            })
+            .collect()
+    }
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    fn warnings(events: Vec<Event>) -> Vec<NonMonotonicCompatibility> {
+        events
+            .into_iter()
+            .filter_map(|event| match event.message() {
+                Message::NonMonotonicCompatibility(it) => Some(it.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[yare::parameterized(
+        all = { 3, 4, &[0, 1, 2] },
+        single = { 10, 1, &[9] },
+        spread = { 10, 4, &[0, 3, 6, 9] },
+        endpoints = { 10, 2, &[0, 9] },
+        none = { 0, 4, &[] },
+    )]
+    fn samples(len: usize, k: usize, expected: &[usize]) {
+        assert_eq!(sample(len, k), expected);
+    }
+
+    #[test]
+    fn reports_holes() {
+        // Rust 1.35 regressed
+        let compatible = (30..=40)
+            .filter(|&minor| minor != 35)
+            .map(|minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+        let runner = TestRunner::with_ok("x", compatible.iter());
+        let search_space = search_space_of(20..=40);
+        let reporter = TestReporterWrapper::default();
+
+        verify_monotonicity(
+            &runner,
+            &search_space,
+            &toolchain(30),
+            NonZeroUsize::new(10).unwrap(),
+            reporter.get(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            warnings(reporter.wait_for_events()),
+            vec![NonMonotonicCompatibility::new(
+                semver::Version::new(1, 30, 0),
+                vec![semver::Version::new(1, 35, 0)],
+            )]
+        );
+    }
+
+    #[test]
+    fn no_warning_if_monotonic() {
+        let compatible = (30..=40)
+            .map(|minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+        let runner = TestRunner::with_ok("x", compatible.iter());
+        let search_space = search_space_of(20..=40);
+        let reporter = TestReporterWrapper::default();
+
+        verify_monotonicity(
+            &runner,
+            &search_space,
+            &toolchain(30),
+            NonZeroUsize::new(4).unwrap(),
+            reporter.get(),
+            Some("a"),
+        )
+        .unwrap();
+
+        assert!(warnings(reporter.wait_for_events()).is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use rust_releases::ReleaseIndex;
+
+use super::{search, write_found_msrv};
+use crate::compatibility::{IsCompatible, RunCommand, RunCommandProvider};
+use crate::context::FindContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::reporter::Reporter;
+use crate::reporter::event::{FindTargetsSummary, TargetMsrv};
+use crate::writer::toolchain_file::write_toolchain_file;
+use crate::{SubCommand, semver};
+
+/// Find the MSRV of a crate for each of multiple targets.
+///
+/// Each target is searched separately, using a runner which is provided by the `runner_factory`
+/// for the compatibility check command of the target. Since rustup installs a toolchain once,
+/// and adds each target to it, toolchains are shared by the targets.
+pub struct FindTargets<'index, F> {
+    release_index: &'index ReleaseIndex,
+    runner_factory: F,
+}
+
+impl<'index, C, F> FindTargets<'index, F>
+where
+    C: IsCompatible,
+    F: Fn(RunCommand) -> C,
+{
+    pub fn new(release_index: &'index ReleaseIndex, runner_factory: F) -> Self {
+        Self {
+            release_index,
+            runner_factory,
+        }
+    }
+}
+
+impl<C, F> SubCommand for FindTargets<'_, F>
+where
+    C: IsCompatible,
+    F: Fn(RunCommand) -> C,
+{
+    type Context = FindContext;
+    type Output = semver::Version;
+
+    /// Returns the MSRV of the crate, which is the greatest MSRV of all targets.
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        find_targets_msrv(ctx, reporter, self.release_index, &self.runner_factory)
+    }
+}
+
+fn find_targets_msrv<C: IsCompatible>(
+    ctx: &FindContext,
+    reporter: &impl Reporter,
+    release_index: &ReleaseIndex,
+    runner_factory: &impl Fn(RunCommand) -> C,
+) -> TResult<semver::Version> {
+    let targets = std::iter::once(ctx.toolchain.target)
+        .chain(ctx.additional_targets.iter().copied())
+        .collect::<Vec<_>>();
+    let mut results = Vec::new();
+
+    for &target in &targets {
+        let target_ctx = target_context(ctx, target);
+        let runner = runner_factory(target_ctx.provide_run_command());
+
+        info!(target, "searching for target MSRV");
+
+        let msrv = match search(&target_ctx, reporter, release_index, &runner, None)? {
+            MinimumSupportedRustVersion::Toolchain { toolchain } => {
+                Some(toolchain.version().clone())
+            }
+            MinimumSupportedRustVersion::NoCompatibleToolchain => None,
+        };
+
+        results.push(TargetMsrv::new(target, msrv));
+    }
+
+    let summary = FindTargetsSummary::new(results);
+    let msrv = summary.msrv().cloned();
+    let undetermined = targets
+        .iter()
+        .zip(summary.targets())
+        .find(|(_, result)| result.msrv.is_none())
+        .map(|(&target, _)| target);
+
+    reporter.report_event(summary)?;
+
+    match (msrv, undetermined) {
+        (Some(version), _) => {
+            if ctx.write_toolchain_file {
+                write_toolchain_file(reporter, &version, ctx.environment.root())?;
+            }
+
+            if ctx.write_msrv {
+                write_found_msrv(ctx, reporter, release_index, &version)?;
+            }
+
+            Ok(version)
+        }
+        (None, target) => {
+            info!(?target, "no minimal-compatible toolchain found for target");
+
+            let command = target
+                .map(|target| {
+                    target_context(ctx, target)
+                        .provide_run_command()
+                        .components()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+
+            Err(CargoMSRVError::UnableToFindAnyGoodVersion { command })
+        }
+    }
+}
+
+/// The context for a single target.
+fn target_context(ctx: &FindContext, target: &'static str) -> FindContext {
+    let mut target_ctx = ctx.clone();
+    target_ctx.toolchain.target = target;
+    target_ctx
+}
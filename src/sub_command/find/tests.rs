@@ -1,16 +1,18 @@
 use super::*;
 use crate::compatibility::TestRunner;
 use crate::context::{
-    CheckCommandContext, EnvironmentContext, RustReleasesContext, ToolchainContext,
-    WorkspacePackages,
+    CheckCommandContext, EnvironmentContext, RustReleasesContext, TargetDirCleanup,
+    ToolchainContext, ToolchainSource, WorkspacePackages,
 };
 use crate::reporter::Event;
 use crate::reporter::TestReporterWrapper;
-use camino::Utf8PathBuf;
+use crate::reporter::event::NonMonotonicCompatibility;
+use camino::{Utf8Path, Utf8PathBuf};
 use cargo_msrv_context::types::ReleaseSource;
 use cargo_msrv_types::BareVersion;
 use rust_releases::semver;
 use std::iter::FromIterator;
+use std::num::NonZeroUsize;
 
 #[test]
 fn bisect_find_only_last() {
@@ -149,6 +151,58 @@ fn bisect_none_compatible() {
     phenomenon::contains_at_least_ordered(events, expected).assert_this();
 }
 
+#[test]
+fn bisect_reports_non_monotonic_compatibility() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+        Release::new_stable(semver::Version::new(1, 54, 0)),
+        Release::new_stable(semver::Version::new(1, 53, 0)),
+        Release::new_stable(semver::Version::new(1, 52, 0)),
+    ]);
+
+    let reporter = TestReporterWrapper::default();
+    // Rust 1.55.0 regressed, but the search never checks it
+    let runner = TestRunner::with_ok(
+        "x",
+        &[
+            semver::Version::new(1, 56, 0),
+            semver::Version::new(1, 54, 0),
+            semver::Version::new(1, 53, 0),
+        ],
+    );
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.search_method = SearchMethod::Bisect;
+    ctx.verify_monotonicity = NonZeroUsize::new(4);
+    // necessary currently, otherwise our own cargo manifest edition is used (ugh),
+    // which now is 2021, i.e. >= 1.56, and that breaks the tests
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 52, 0));
+
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 53, 0));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![
+        NonMonotonicCompatibility::new(
+            semver::Version::new(1, 53, 0),
+            vec![semver::Version::new(1, 55, 0)],
+        )
+        .into(),
+        FindResult::new_msrv(
+            semver::Version::new(1, 53, 0),
+            "x",
+            BareVersion::ThreeComponents(1, 52, 0),
+            BareVersion::ThreeComponents(1, 56, 0),
+            SearchMethod::Bisect,
+        )
+        .into(),
+    ];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
 // These test cases cover the case that the minimum is set to be a strictly more recent
 // Rust release compared to the maximum set.
 // https://github.com/foresterre/cargo-msrv/issues/369
@@ -253,11 +307,513 @@ mod issue_369_min_more_recent_than_max {
     }
 }
 
+#[cfg(test)]
+mod workspace {
+    use super::*;
+    use crate::FindWorkspace;
+    use crate::context::PackageSelection;
+    use crate::reporter::event::{FindWorkspaceSummary, PackageMsrv};
+    use assert_fs::prelude::*;
+
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 58, 1)),
+            Release::new_stable(semver::Version::new(1, 57, 0)),
+            Release::new_stable(semver::Version::new(1, 56, 1)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+        ])
+    }
+
+    // Creates a virtual workspace with the members `a` and `b`, and selects both packages
+    fn create_workspace_context(tmp: &assert_fs::TempDir) -> FindContext {
+        tmp.child("Cargo.toml")
+            .write_str("[workspace]\nmembers = [\"a\", \"b\"]\n")
+            .unwrap();
+
+        for name in ["a", "b"] {
+            tmp.child(name)
+                .child("Cargo.toml")
+                .write_str(&format!(
+                    "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"
+                ))
+                .unwrap();
+            tmp.child(name).child("src/lib.rs").touch().unwrap();
+        }
+
+        let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        let mut ctx = create_test_context();
+        ctx.environment = EnvironmentContext {
+            root_crate_path: root,
+            workspace_packages: WorkspacePackages::from_selection(
+                metadata.packages,
+                PackageSelection::Workspace,
+            ),
+        };
+        ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 55, 0));
+        ctx
+    }
+
+    fn runner_factory(package: &str) -> TestRunner {
+        match package {
+            "a" => TestRunner::with_ok(
+                "x",
+                &[
+                    semver::Version::new(1, 58, 1),
+                    semver::Version::new(1, 57, 0),
+                    semver::Version::new(1, 56, 1),
+                ],
+            ),
+            "b" => TestRunner::with_ok("x", &[semver::Version::new(1, 58, 1)]),
+            _ => TestRunner::with_ok("x", &[]),
+        }
+    }
+
+    #[yare::parameterized(
+        bisect = { SearchMethod::Bisect },
+        linear = { SearchMethod::Linear },
+        multi_bisect = { SearchMethod::MultiBisect },
+        anchored = { SearchMethod::Anchored },
+    )]
+    fn msrv_per_package(search_method: SearchMethod) {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let mut ctx = create_workspace_context(&tmp);
+        ctx.search_method = search_method;
+
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let found = FindWorkspace::new(&index, runner_factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+        assert_eq!(found, semver::Version::new(1, 58, 1));
+
+        let events = reporter.wait_for_events();
+        let min = BareVersion::ThreeComponents(1, 55, 0);
+        let max = BareVersion::ThreeComponents(1, 58, 1);
+        let expected: Vec<Event> = vec![
+            FindResult::new_msrv(
+                semver::Version::new(1, 56, 1),
+                "x",
+                min.clone(),
+                max.clone(),
+                search_method,
+            )
+            .with_package("a")
+            .into(),
+            FindResult::new_msrv(semver::Version::new(1, 58, 1), "x", min, max, search_method)
+                .with_package("b")
+                .into(),
+            FindWorkspaceSummary::new(vec![
+                PackageMsrv::new("a", Some(semver::Version::new(1, 56, 1))),
+                PackageMsrv::new("b", Some(semver::Version::new(1, 58, 1))),
+            ])
+            .into(),
+        ];
+
+        phenomenon::contains_at_least_ordered(events, expected).assert_this();
+    }
+
+    #[test]
+    fn package_without_msrv() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let ctx = create_workspace_context(&tmp);
+
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let factory = |package: &str| match package {
+            "a" => runner_factory(package),
+            _ => TestRunner::with_ok("x", &[]),
+        };
+
+        let err = FindWorkspace::new(&index, factory)
+            .run(&ctx, reporter.get())
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            CargoMSRVError::UnableToFindAnyGoodVersion { ref command } if command.contains("--package b")
+        ));
+
+        let events = reporter.wait_for_events();
+        let expected: Vec<Event> = vec![
+            FindWorkspaceSummary::new(vec![
+                PackageMsrv::new("a", Some(semver::Version::new(1, 56, 1))),
+                PackageMsrv::new("b", None),
+            ])
+            .into(),
+        ];
+
+        phenomenon::contains_at_least_ordered(events, expected).assert_this();
+    }
+
+    #[test]
+    fn write_msrv_per_package() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let mut ctx = create_workspace_context(&tmp);
+        ctx.write_msrv = true;
+
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        FindWorkspace::new(&index, runner_factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+
+        let a = std::fs::read_to_string(tmp.child("a").child("Cargo.toml").path()).unwrap();
+        let b = std::fs::read_to_string(tmp.child("b").child("Cargo.toml").path()).unwrap();
+
+        assert!(a.contains("rust-version = \"1.56\""));
+        assert!(b.contains("rust-version = \"1.58\""));
+    }
+
+    // The `workspace-inheritance` fixture, in which `b` inherits its MSRV from the workspace,
+    // and in which `a` is made to inherit it too
+    fn create_inheriting_workspace_context(tmp: &assert_fs::TempDir) -> FindContext {
+        let fixture = Utf8Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("workspace-inheritance");
+        tmp.copy_from(&fixture, &["**"]).unwrap();
+
+        let a = tmp.child("a").child("Cargo.toml");
+        let manifest = std::fs::read_to_string(a.path()).unwrap();
+        a.write_str(&manifest.replace(
+            "rust-version = \"1.64.0\"",
+            "rust-version = { workspace = true }",
+        ))
+        .unwrap();
+
+        let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        let mut ctx = create_test_context();
+        ctx.environment = EnvironmentContext {
+            root_crate_path: root,
+            workspace_packages: WorkspacePackages::from_selection(
+                metadata.packages,
+                PackageSelection::Workspace,
+            ),
+        };
+        ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 55, 0));
+        ctx.write_msrv = true;
+        ctx
+    }
+
+    #[test]
+    fn write_msrv_inherited_from_workspace() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let ctx = create_inheriting_workspace_context(&tmp);
+
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        // `a` has the greatest MSRV, but is checked before `b`, which inherits its MSRV too
+        let runner_factory = |package: &str| match package {
+            "a" => TestRunner::with_ok("x", &[semver::Version::new(1, 58, 1)]),
+            _ => TestRunner::with_ok(
+                "x",
+                &[
+                    semver::Version::new(1, 58, 1),
+                    semver::Version::new(1, 57, 0),
+                ],
+            ),
+        };
+
+        FindWorkspace::new(&index, runner_factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+
+        let read = |path: &str| std::fs::read_to_string(tmp.child(path).path()).unwrap();
+        let workspace: toml_edit::DocumentMut = read("Cargo.toml").parse().unwrap();
+
+        assert_eq!(
+            workspace["workspace"]["package"]["rust-version"].as_str(),
+            Some("1.58")
+        );
+        assert!(read("a/Cargo.toml").contains("rust-version = { workspace = true }"));
+        assert!(read("b/Cargo.toml").contains("rust-version = { workspace = true }"));
+        assert!(read("c/Cargo.toml").contains("rust-version = \"1.57\""));
+    }
+}
+
+#[cfg(test)]
+mod feature_sets {
+    use super::*;
+    use crate::FindFeatureSets;
+    use crate::compatibility::RunCommand;
+    use crate::context::FeatureSetsContext;
+    use crate::reporter::event::{FeatureSetMsrv, FindFeatureSetsSummary};
+    use assert_fs::prelude::*;
+
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 58, 1)),
+            Release::new_stable(semver::Version::new(1, 57, 0)),
+            Release::new_stable(semver::Version::new(1, 56, 1)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+        ])
+    }
+
+    // Creates a crate with the features `fast` and `std`, and selects it by default
+    fn create_feature_sets_context(tmp: &assert_fs::TempDir) -> FindContext {
+        tmp.child("Cargo.toml")
+            .write_str(
+                "[package]\nname = \"a\"\nversion = \"0.1.0\"\n\n\
+                [features]\ndefault = [\"std\"]\nfast = []\nstd = []\n",
+            )
+            .unwrap();
+        tmp.child("src/lib.rs").touch().unwrap();
+
+        let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).unwrap();
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        let mut ctx = create_test_context();
+        ctx.feature_sets = Some(FeatureSetsContext::default());
+        ctx.environment = EnvironmentContext {
+            root_crate_path: root,
+            workspace_packages: WorkspacePackages::from_vec(metadata.packages),
+        };
+        ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 55, 0));
+        ctx
+    }
+
+    // The `fast` feature raises the MSRV to 1.58.1
+    fn runner_factory(run_command: RunCommand) -> TestRunner {
+        if run_command.components().iter().any(|c| c.contains("fast")) {
+            TestRunner::with_ok("x", &[semver::Version::new(1, 58, 1)])
+        } else {
+            TestRunner::with_ok(
+                "x",
+                &[
+                    semver::Version::new(1, 58, 1),
+                    semver::Version::new(1, 57, 0),
+                    semver::Version::new(1, 56, 1),
+                ],
+            )
+        }
+    }
+
+    fn features(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn msrv_per_feature_set() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let ctx = create_feature_sets_context(&tmp);
+
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let found = FindFeatureSets::new(&index, runner_factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+        assert_eq!(found, semver::Version::new(1, 58, 1));
+
+        let events = reporter.wait_for_events();
+        let min = BareVersion::ThreeComponents(1, 55, 0);
+        let max = BareVersion::ThreeComponents(1, 58, 1);
+        let result = |set: &[&str], minor, patch| -> Event {
+            FindResult::new_msrv(
+                semver::Version::new(1, minor, patch),
+                "x",
+                min.clone(),
+                max.clone(),
+                SearchMethod::Bisect,
+            )
+            .with_features(features(set))
+            .into()
+        };
+        let summary = FindFeatureSetsSummary::new(vec![
+            FeatureSetMsrv::new(features(&[]), Some(semver::Version::new(1, 56, 1))),
+            FeatureSetMsrv::new(features(&["fast"]), Some(semver::Version::new(1, 58, 1))),
+            FeatureSetMsrv::new(features(&["std"]), Some(semver::Version::new(1, 56, 1))),
+            FeatureSetMsrv::new(
+                features(&["fast", "std"]),
+                Some(semver::Version::new(1, 58, 1)),
+            ),
+        ]);
+        assert_eq!(summary.raised_by(), Some(features(&["fast"]).as_slice()));
+
+        let expected: Vec<Event> = vec![
+            result(&[], 56, 1),
+            result(&["fast"], 58, 1),
+            result(&["std"], 56, 1),
+            result(&["fast", "std"], 58, 1),
+            summary.into(),
+        ];
+
+        phenomenon::contains_at_least_ordered(events, expected).assert_this();
+    }
+
+    #[test]
+    fn excluded_features() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let mut ctx = create_feature_sets_context(&tmp);
+        ctx.feature_sets = Some(FeatureSetsContext {
+            max_depth: None,
+            exclude: features(&["fast"]),
+        });
+
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let found = FindFeatureSets::new(&index, runner_factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+        assert_eq!(found, semver::Version::new(1, 56, 1));
+    }
+
+    #[test]
+    fn requires_single_package() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        let mut ctx = create_feature_sets_context(&tmp);
+        ctx.environment.workspace_packages = WorkspacePackages::default();
+
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let result = FindFeatureSets::new(&index, runner_factory).run(&ctx, reporter.get());
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::FeatureSetsRequireSinglePackage { selected: 0 })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod targets {
+    use super::*;
+    use crate::FindTargets;
+    use crate::compatibility::RunCommand;
+    use crate::reporter::event::{FindTargetsSummary, TargetMsrv};
+
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 58, 1)),
+            Release::new_stable(semver::Version::new(1, 57, 0)),
+            Release::new_stable(semver::Version::new(1, 56, 1)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+        ])
+    }
+
+    fn create_targets_context() -> FindContext {
+        let mut ctx = create_test_context();
+        ctx.additional_targets = vec!["y", "z"];
+        // necessary currently, otherwise our own cargo manifest edition is used
+        ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 55, 0));
+        ctx
+    }
+
+    // The target `y` raises the MSRV to 1.58.1, and no Rust version supports target `none`
+    fn runner_factory(run_command: RunCommand) -> TestRunner {
+        let has_target = |target: &str| {
+            run_command
+                .components()
+                .windows(2)
+                .any(|args| args[0] == "--target" && args[1] == target)
+        };
+
+        if has_target("y") {
+            TestRunner::with_ok("y", &[semver::Version::new(1, 58, 1)])
+        } else if has_target("none") {
+            TestRunner::with_ok("none", &[])
+        } else {
+            TestRunner::with_ok(
+                "x",
+                &[
+                    semver::Version::new(1, 58, 1),
+                    semver::Version::new(1, 57, 0),
+                ],
+            )
+        }
+    }
+
+    #[test]
+    fn msrv_per_target() {
+        let ctx = create_targets_context();
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let found = FindTargets::new(&index, runner_factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+        assert_eq!(found, semver::Version::new(1, 58, 1));
+
+        let events = reporter.wait_for_events();
+        let result = |target, minor, patch| -> Event {
+            FindResult::new_msrv(
+                semver::Version::new(1, minor, patch),
+                target,
+                BareVersion::ThreeComponents(1, 55, 0),
+                BareVersion::ThreeComponents(1, 58, 1),
+                SearchMethod::Bisect,
+            )
+            .with_attributed_target()
+            .into()
+        };
+        let summary = FindTargetsSummary::new(vec![
+            TargetMsrv::new("x", Some(semver::Version::new(1, 57, 0))),
+            TargetMsrv::new("y", Some(semver::Version::new(1, 58, 1))),
+            TargetMsrv::new("z", Some(semver::Version::new(1, 57, 0))),
+        ]);
+        assert_eq!(summary.raised_by(), ["y"]);
+
+        let expected: Vec<Event> = vec![
+            result("x", 57, 0),
+            result("y", 58, 1),
+            result("z", 57, 0),
+            summary.into(),
+        ];
+
+        phenomenon::contains_at_least_ordered(events, expected).assert_this();
+    }
+
+    #[test]
+    fn unsupported_target() {
+        let mut ctx = create_targets_context();
+        ctx.additional_targets = vec!["none"];
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let result = FindTargets::new(&index, runner_factory).run(&ctx, reporter.get());
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::UnableToFindAnyGoodVersion { command }) if command.contains("--target none")
+        ));
+    }
+}
+
 fn create_test_context() -> FindContext {
     FindContext {
+        jobs: NonZeroUsize::MIN,
+        verify_monotonicity: None,
+        feature_sets: None,
         search_method: SearchMethod::Bisect,
         write_toolchain_file: false,
         ignore_lockfile: false,
+        resolve_msrv_lockfile: false,
+        isolated: false,
+        target_dir_cleanup: TargetDirCleanup::Keep,
+        cleanup_toolchains: false,
+        cache: false,
         skip_unavailable_toolchains: true,
         no_check_feedback: false,
         write_msrv: false,
@@ -266,11 +822,14 @@ fn create_test_context() -> FindContext {
             maximum_rust_version: None,
             consider_patch_releases: false,
             release_source: ReleaseSource::RustChangelog,
+            offline: false,
         },
         toolchain: ToolchainContext {
             target: "x",
             components: &[],
+            source: ToolchainSource::Rustup,
         },
+        additional_targets: vec![],
         check_cmd: CheckCommandContext {
             cargo_features: None,
             cargo_all_features: false,
@@ -0,0 +1,177 @@
+use rust_releases::ReleaseIndex;
+
+use super::{search, write_found_msrv};
+use crate::compatibility::{IsCompatible, PackageRunCommandProvider};
+use crate::context::{EnvironmentContext, FindContext, PackageSelection, WorkspacePackages};
+use crate::error::{CargoMSRVError, TResult};
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::reporter::Reporter;
+use crate::reporter::event::{FindWorkspaceSummary, PackageMsrv};
+use crate::sub_command::set::inherits_workspace_msrv;
+use crate::writer::toolchain_file::write_toolchain_file;
+use crate::{SubCommand, semver};
+
+/// Find the MSRV of each selected package of a Cargo workspace.
+///
+/// Each package is checked in isolation (i.e. using `cargo check --package <name>`), using a
+/// runner which is provided by the `runner_factory` for the package with the given name.
+pub struct FindWorkspace<'index, F> {
+    release_index: &'index ReleaseIndex,
+    runner_factory: F,
+}
+
+impl<'index, C, F> FindWorkspace<'index, F>
+where
+    C: IsCompatible,
+    F: Fn(&str) -> C,
+{
+    pub fn new(release_index: &'index ReleaseIndex, runner_factory: F) -> Self {
+        Self {
+            release_index,
+            runner_factory,
+        }
+    }
+}
+
+impl<C, F> SubCommand for FindWorkspace<'_, F>
+where
+    C: IsCompatible,
+    F: Fn(&str) -> C,
+{
+    type Context = FindContext;
+    type Output = semver::Version;
+
+    /// Returns the MSRV of the workspace, which is the greatest MSRV of all selected packages.
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        find_workspace_msrv(ctx, reporter, self.release_index, &self.runner_factory)
+    }
+}
+
+fn find_workspace_msrv<C: IsCompatible>(
+    ctx: &FindContext,
+    reporter: &impl Reporter,
+    release_index: &ReleaseIndex,
+    runner_factory: &impl Fn(&str) -> C,
+) -> TResult<semver::Version> {
+    let packages = ctx.environment.workspace_packages.selected_packages();
+    let mut results = Vec::with_capacity(packages.len());
+    // The greatest MSRV of the packages which inherit their MSRV from the workspace
+    let mut inherited_msrv: Option<(FindContext, semver::Version)> = None;
+
+    for package in packages {
+        let name = package.name.as_str();
+        let package_ctx = package_context(ctx, package);
+        let runner = runner_factory(name);
+
+        info!(package = name, "searching for package MSRV");
+
+        let msrv = match search(&package_ctx, reporter, release_index, &runner, Some(name))? {
+            MinimumSupportedRustVersion::Toolchain { toolchain } => {
+                let version = toolchain.version().clone();
+
+                if ctx.write_msrv {
+                    if inherits_workspace_msrv(&package.manifest_path)? {
+                        if inherited_msrv
+                            .as_ref()
+                            .is_none_or(|(_, msrv)| *msrv < version)
+                        {
+                            inherited_msrv =
+                                Some((inherited_msrv_context(ctx, package), version.clone()));
+                        }
+                    } else {
+                        write_found_msrv(&package_ctx, reporter, release_index, &version)?;
+                    }
+                }
+
+                Some(version)
+            }
+            MinimumSupportedRustVersion::NoCompatibleToolchain => None,
+        };
+
+        results.push(PackageMsrv::new(name, msrv));
+    }
+
+    // Written once, since each of these packages would otherwise overwrite the inherited MSRV
+    if let Some((inherited_ctx, version)) = inherited_msrv {
+        write_found_msrv(&inherited_ctx, reporter, release_index, &version)?;
+    }
+
+    let summary = FindWorkspaceSummary::new(results);
+    let workspace_msrv = summary.workspace_msrv().cloned();
+    let undetermined = summary
+        .packages()
+        .iter()
+        .find(|package| package.msrv.is_none())
+        .map(|package| package.name.clone());
+
+    reporter.report_event(summary)?;
+
+    match (workspace_msrv, undetermined) {
+        (Some(version), _) => {
+            if ctx.write_toolchain_file {
+                write_toolchain_file(reporter, &version, ctx.environment.root())?;
+            }
+
+            Ok(version)
+        }
+        (None, package) => {
+            info!(
+                ?package,
+                "no minimal-compatible toolchain found for package"
+            );
+
+            let command = package
+                .map(|name| {
+                    ctx.provide_package_run_command(&name)
+                        .components()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+
+            Err(CargoMSRVError::UnableToFindAnyGoodVersion { command })
+        }
+    }
+}
+
+/// The context for a single package, which is rooted at the directory of the package manifest.
+///
+/// Determines where the minimum considered Rust version is read from, and where the MSRV is
+/// written to. Since the package is selected explicitly, the MSRV is written to the package
+/// manifest, even if the package currently inherits it from the workspace.
+pub(super) fn package_context(ctx: &FindContext, package: &cargo_metadata::Package) -> FindContext {
+    let root_crate_path = package
+        .manifest_path
+        .parent()
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| ctx.environment.root_crate_path.clone());
+
+    FindContext {
+        environment: EnvironmentContext {
+            root_crate_path,
+            workspace_packages: WorkspacePackages::from_selection(
+                vec![package.clone()],
+                PackageSelection::Packages,
+            ),
+        },
+        ..ctx.clone()
+    }
+}
+
+/// The context for the MSRV which the given package inherits from the workspace.
+///
+/// Since the package isn't selected explicitly, the MSRV is written to the `workspace.package`
+/// table of the workspace manifest, from which the package inherits it.
+fn inherited_msrv_context(ctx: &FindContext, package: &cargo_metadata::Package) -> FindContext {
+    let package_ctx = package_context(ctx, package);
+
+    FindContext {
+        environment: EnvironmentContext {
+            workspace_packages: WorkspacePackages::from_selection(
+                vec![package.clone()],
+                PackageSelection::Default,
+            ),
+            ..package_ctx.environment
+        },
+        ..package_ctx
+    }
+}
@@ -1,9 +1,11 @@
 use crate::SubCommand;
 use crate::context::ListContext;
+use crate::dependency_graph::downgrades::suggest_downgrades;
+use crate::dependency_graph::registry_index::RegistryIndexCache;
 use crate::dependency_graph::resolver::{CargoMetadataResolver, DependencyResolver};
 use crate::error::TResult;
 use crate::reporter::Reporter;
-use crate::reporter::event::ListResult;
+use crate::reporter::event::{DowngradeSuggestions, ListResult};
 
 #[derive(Default)]
 pub struct List;
@@ -22,7 +24,15 @@ fn list_msrv(ctx: &ListContext, reporter: &impl Reporter) -> TResult<()> {
     let graph = resolver.resolve()?;
     let variant = ctx.variant;
 
-    reporter.report_event(ListResult::new(variant, graph))?;
+    if let Some(target_msrv) = &ctx.suggest_downgrades {
+        let registry = RegistryIndexCache::from_cargo_home();
+        let dependencies = suggest_downgrades(&graph, target_msrv, |name| registry.versions(name));
+
+        reporter.report_event(ListResult::new(variant, graph))?;
+        reporter.report_event(DowngradeSuggestions::new(target_msrv.clone(), dependencies))?;
+    } else {
+        reporter.report_event(ListResult::new(variant, graph))?;
+    }
 
     Ok(())
 }
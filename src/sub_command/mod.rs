@@ -1,3 +1,17 @@
+/// Manage the cache of compatibility check outcomes.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv cache clear`
+pub use cache::Cache;
+
+/// Diagnose the environment in which cargo-msrv runs.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv doctor`
+pub use doctor::Doctor;
+
 /// Find the MSRV of a Rust package.
 ///
 /// # Example (CLI)
@@ -5,6 +19,27 @@
 /// `cargo msrv`
 pub use find::Find;
 
+/// Find the MSRV of each selected package of a Cargo workspace.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv find --workspace`
+pub use find::FindWorkspace;
+
+/// Find the MSRV of each combination of the features of a package.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv find --feature-powerset`
+pub use find::FindFeatureSets;
+
+/// Find the MSRV of a crate for each of multiple targets.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv find --target x86_64-unknown-linux-gnu --target wasm32-unknown-unknown`
+pub use find::FindTargets;
+
 /// List the MSRV's of libraries you depend on.
 ///
 /// # Example (CLI)
@@ -12,6 +47,13 @@ pub use find::Find;
 /// `cargo msrv list`
 pub use list::List;
 
+/// Uninstall the toolchains which were installed by cargo-msrv.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv toolchains prune`
+pub use toolchains::Toolchains;
+
 /// Check whether the MSRV of a crate is valid as an MSRV.
 ///
 /// # Use case
@@ -24,6 +66,20 @@ pub use list::List;
 /// `cargo msrv verify`
 pub use verify::Verify;
 
+/// Check whether the MSRV of each selected package of a Cargo workspace is valid.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv verify --workspace`
+pub use verify::VerifyWorkspace;
+
+/// Print the configuration of a project, merged with the options given on the command line.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv find --print-config`
+pub use print_config::PrintConfig;
+
 /// Write a given MSRV to a Cargo manifest
 ///
 /// # Example (CLI)
@@ -41,10 +97,14 @@ pub use show::Show;
 use crate::TResult;
 use crate::reporter::Reporter;
 
+pub mod cache;
+pub mod doctor;
 pub mod find;
 pub mod list;
+pub mod print_config;
 pub mod set;
 pub mod show;
+pub mod toolchains;
 pub mod verify;
 
 /// A sub-command of `cargo-msrv`.
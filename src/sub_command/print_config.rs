@@ -0,0 +1,19 @@
+use crate::SubCommand;
+use crate::context::PrintConfigContext;
+use crate::error::TResult;
+use crate::reporter::Reporter;
+use crate::reporter::event::ConfigResult;
+
+#[derive(Default)]
+pub struct PrintConfig;
+
+impl SubCommand for PrintConfig {
+    type Context = PrintConfigContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        reporter.report_event(ConfigResult::new(ctx.config.clone(), ctx.layers.clone()))?;
+
+        Ok(())
+    }
+}
@@ -3,15 +3,16 @@ use std::io::Write;
 use rust_releases::{Release, ReleaseIndex, semver};
 use toml_edit::{DocumentMut, Item, Value, table, value};
 
-use crate::context::SetContext;
+use crate::context::{PackageSelection, SetContext};
 use crate::error::{InvalidMsrvSetError, IoError, IoErrorSource, SetMsrvError};
-use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::manifest::{CargoManifestParser, TomlParser, inherits_rust_version};
 use crate::reporter::Reporter;
 use crate::reporter::event::{
     AuxiliaryOutput, AuxiliaryOutputItem, Destination, MsrvKind, SetResult,
     UnableToConfirmValidReleaseVersion,
 };
 use crate::{CargoMSRVError, SubCommand, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
 use cargo_msrv_types::BareVersion;
 
 const RUST_VERSION_SUPPORTED_SINCE: semver::Version = semver::Version::new(1, 56, 0);
@@ -68,29 +69,121 @@ fn matches_release(msrv: &BareVersion, release: &Release) -> bool {
 }
 
 fn set_msrv(ctx: &SetContext, reporter: &impl Reporter, msrv: &BareVersion) -> TResult<()> {
+    let workspace_packages = &ctx.environment.workspace_packages;
+
+    // When specific packages were selected, the MSRV is written to the manifest of each of these
+    // packages, even if they currently inherit their MSRV from the workspace.
+    if workspace_packages.selection() == PackageSelection::Packages {
+        for package in workspace_packages.selected_packages() {
+            update_manifest(reporter, &package.manifest_path, msrv, set_or_override_msrv)?;
+        }
+
+        return Ok(());
+    }
+
     let cargo_toml = ctx.environment.manifest();
+    let manifest = read_manifest(&cargo_toml)?;
+
+    match msrv_location(&manifest) {
+        MsrvLocation::Package => update_manifest(reporter, &cargo_toml, msrv, set_or_override_msrv),
+        MsrvLocation::Workspace => update_manifest(reporter, &cargo_toml, msrv, set_workspace_msrv),
+        MsrvLocation::InheritedFromWorkspace => {
+            let workspace_toml = find_workspace_manifest(&cargo_toml, &manifest)?;
+
+            update_manifest(reporter, &workspace_toml, msrv, set_workspace_msrv)
+        }
+    }
+}
+
+/// Where the MSRV of a Cargo manifest is to be written to.
+#[derive(Debug, Eq, PartialEq)]
+enum MsrvLocation {
+    /// The `[package]` table of the manifest
+    Package,
+    /// The `[workspace.package]` table of a virtual manifest
+    Workspace,
+    /// The `[workspace.package]` table of the workspace manifest, since the package specifies
+    /// `rust-version.workspace = true`
+    InheritedFromWorkspace,
+}
+
+fn msrv_location(manifest: &DocumentMut) -> MsrvLocation {
+    let package = manifest.as_table().get("package");
+
+    match package {
+        Some(_) if inherits_rust_version(manifest) => MsrvLocation::InheritedFromWorkspace,
+        Some(_) => MsrvLocation::Package,
+        None if manifest.as_table().contains_key("workspace") => MsrvLocation::Workspace,
+        None => MsrvLocation::Package,
+    }
+}
 
+/// Find the manifest of the workspace a package belongs to, by searching the parent
+/// directories of the package, like Cargo does.
+fn find_workspace_manifest(cargo_toml: &Utf8Path, manifest: &DocumentMut) -> TResult<Utf8PathBuf> {
+    if manifest.as_table().contains_key("workspace") {
+        return Ok(cargo_toml.to_path_buf());
+    }
+
+    for dir in cargo_toml.ancestors().skip(2) {
+        let candidate = dir.join("Cargo.toml");
+
+        if !candidate.is_file() {
+            continue;
+        }
+
+        if read_manifest(&candidate)?
+            .as_table()
+            .contains_key("workspace")
+        {
+            return Ok(candidate);
+        }
+    }
+
+    Err(CargoMSRVError::SetMsrv(
+        SetMsrvError::WorkspaceManifestNotFound(cargo_toml.to_path_buf()),
+    ))
+}
+
+/// Whether the package of the given Cargo manifest inherits its MSRV from the workspace, i.e.
+/// specifies `rust-version.workspace = true`.
+pub(crate) fn inherits_workspace_msrv(cargo_toml: &Utf8Path) -> TResult<bool> {
+    Ok(inherits_rust_version(&read_manifest(cargo_toml)?))
+}
+
+fn read_manifest(cargo_toml: &Utf8Path) -> TResult<DocumentMut> {
     // Read the Cargo manifest to a String
-    let contents = std::fs::read_to_string(&cargo_toml).map_err(|error| IoError {
+    let contents = std::fs::read_to_string(cargo_toml).map_err(|error| IoError {
         error,
-        source: IoErrorSource::ReadFile(cargo_toml.clone()),
+        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
     })?;
 
     // Parse the Cargo manifest contents, in particular the MSRV value
-    let mut manifest = CargoManifestParser.parse::<DocumentMut>(&contents)?;
-    check_workspace(&manifest)?;
+    let manifest = CargoManifestParser.parse::<DocumentMut>(&contents)?;
+
+    Ok(manifest)
+}
+
+/// Read the Cargo manifest at `cargo_toml`, modify it using `update`, and write it back.
+fn update_manifest(
+    reporter: &impl Reporter,
+    cargo_toml: &Utf8Path,
+    msrv: &BareVersion,
+    update: impl FnOnce(&mut DocumentMut, &BareVersion) -> TResult<()>,
+) -> TResult<()> {
+    let mut manifest = read_manifest(cargo_toml)?;
 
     // Set the MSRV
-    set_or_override_msrv(&mut manifest, msrv)?;
+    update(&mut manifest, msrv)?;
 
     // Open the Cargo manifest file with write permissions and truncate the current its contents
     let mut file = std::fs::OpenOptions::new()
         .write(true)
         .truncate(true)
-        .open(&cargo_toml)
+        .open(cargo_toml)
         .map_err(|error| IoError {
             error,
-            source: IoErrorSource::OpenFile(cargo_toml.clone()),
+            source: IoErrorSource::OpenFile(cargo_toml.to_path_buf()),
         })?;
 
     // Write the new manifest contents with the newly set MSRV value
@@ -100,26 +193,16 @@ fn set_msrv(ctx: &SetContext, reporter: &impl Reporter, msrv: &BareVersion) -> T
     })?;
 
     reporter.report_event(AuxiliaryOutput::new(
-        Destination::file(cargo_toml.clone()),
+        Destination::file(cargo_toml.to_path_buf()),
         AuxiliaryOutputItem::msrv(MsrvKind::RustVersion),
     ))?;
 
     // Report that the MSRV was set
-    reporter.report_event(SetResult::new(msrv.clone(), cargo_toml))?;
+    reporter.report_event(SetResult::new(msrv.clone(), cargo_toml.to_path_buf()))?;
 
     Ok(())
 }
 
-fn check_workspace(manifest: &DocumentMut) -> TResult<()> {
-    if manifest.as_table().get("package").is_none()
-        && manifest.as_table().get("workspace").is_some()
-    {
-        Err(CargoMSRVError::WorkspaceFound)
-    } else {
-        Ok(())
-    }
-}
-
 /// Override MSRV if it is already set, otherwise, simply set it
 fn set_or_override_msrv(manifest: &mut DocumentMut, msrv: &BareVersion) -> TResult<()> {
     // NB: As a consequence of scrubbing the current MSRV, if the MSRV is the only value in the
@@ -136,6 +219,42 @@ fn set_or_override_msrv(manifest: &mut DocumentMut, msrv: &BareVersion) -> TResu
     insert_new_msrv(manifest, msrv)
 }
 
+/// Set the MSRV which can be inherited by workspace members, i.e. `workspace.package.rust-version`.
+///
+/// Unlike the MSRV of a package, it is always written as `rust-version`, since workspace inheritance
+/// requires Cargo 1.64 or later anyway.
+fn set_workspace_msrv(manifest: &mut DocumentMut, msrv: &BareVersion) -> TResult<()> {
+    let workspace = &mut manifest["workspace"];
+
+    if workspace.is_none() {
+        *workspace = table();
+    }
+
+    let package = &mut workspace["package"];
+
+    match package {
+        Item::None => {
+            // Explicitly create the table, otherwise it would default to an inline table instead
+            *package = table();
+            package["rust-version"] = value(msrv.to_string());
+        }
+        Item::Value(Value::InlineTable(table)) => {
+            table.insert("rust-version", msrv.to_string().into());
+        }
+        Item::Table(table) => {
+            // Indexing, unlike inserting, preserves the formatting of an existing key
+            table["rust-version"] = value(msrv.to_string());
+        }
+        _ => {
+            return Err(CargoMSRVError::SetMsrv(
+                SetMsrvError::WorkspacePackageNotATable,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 fn insert_new_msrv(manifest: &mut DocumentMut, msrv: &BareVersion) -> TResult<()> {
     fn insert_rust_version(manifest: &mut DocumentMut, msrv: &BareVersion) -> TResult<()> {
         manifest["package"]["rust-version"] = value(msrv.to_string());
@@ -920,6 +1039,165 @@ edition = "2021"
     }
 }
 
+#[cfg(test)]
+mod msrv_location_tests {
+    use toml_edit::DocumentMut;
+
+    use crate::manifest::{CargoManifestParser, TomlParser};
+    use crate::sub_command::set::{MsrvLocation, msrv_location};
+
+    #[yare::parameterized(
+        package = { "[package]\nname = \"a\"\n", MsrvLocation::Package },
+        package_with_rust_version = { "[package]\nrust-version = \"1.56\"\n", MsrvLocation::Package },
+        package_in_workspace_root = { "[package]\nrust-version = \"1.56\"\n\n[workspace]\n", MsrvLocation::Package },
+        empty = { "", MsrvLocation::Package },
+        virtual_workspace = { "[workspace]\nmembers = [\"a\"]\n", MsrvLocation::Workspace },
+        inherited_dotted = { "[package]\nrust-version.workspace = true\n", MsrvLocation::InheritedFromWorkspace },
+        inherited_inline = { "[package]\nrust-version = { workspace = true }\n", MsrvLocation::InheritedFromWorkspace },
+        not_inherited = { "[package]\nrust-version = { workspace = false }\n", MsrvLocation::Package },
+    )]
+    fn location(input: &str, expected: MsrvLocation) {
+        let manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+
+        assert_eq!(msrv_location(&manifest), expected);
+    }
+}
+
+#[cfg(test)]
+mod set_workspace_msrv_tests {
+    use toml_edit::DocumentMut;
+
+    use crate::manifest::{CargoManifestParser, TomlParser};
+    use crate::sub_command::set::set_workspace_msrv;
+    use cargo_msrv_types::BareVersion;
+
+    #[test]
+    fn without_workspace_package_table() {
+        let input = r#"[workspace]
+members = ["a", "b"]
+"#;
+
+        let mut manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+        set_workspace_msrv(&mut manifest, &BareVersion::TwoComponents(1, 64)).unwrap();
+
+        let expected = r#"[workspace]
+members = ["a", "b"]
+
+[workspace.package]
+rust-version = "1.64"
+"#;
+
+        assert_eq!(manifest.to_string(), expected);
+    }
+
+    #[test]
+    fn with_existing_rust_version() {
+        let input = r#"[workspace]
+members = ["a", "b", "c"]
+
+[workspace.package]
+# The MSRV of the workspace
+rust-version = "1.66.0"
+edition = "2021"
+"#;
+
+        let mut manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+        set_workspace_msrv(&mut manifest, &BareVersion::ThreeComponents(1, 70, 0)).unwrap();
+
+        let expected = r#"[workspace]
+members = ["a", "b", "c"]
+
+[workspace.package]
+# The MSRV of the workspace
+rust-version = "1.70.0"
+edition = "2021"
+"#;
+
+        assert_eq!(manifest.to_string(), expected);
+    }
+
+    #[test]
+    fn with_inline_table() {
+        let input = r#"[workspace]
+package = { edition = "2021" }
+"#;
+
+        let mut manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+        set_workspace_msrv(&mut manifest, &BareVersion::TwoComponents(1, 64)).unwrap();
+
+        assert_eq!(
+            manifest["workspace"]["package"]["rust-version"]
+                .as_str()
+                .unwrap(),
+            "1.64"
+        );
+        assert!(manifest["workspace"]["package"].is_inline_table());
+    }
+
+    #[test]
+    fn not_a_table() {
+        let input = r#"[workspace]
+package = 1
+"#;
+
+        let mut manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+        let result = set_workspace_msrv(&mut manifest, &BareVersion::TwoComponents(1, 64));
+
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod find_workspace_manifest_tests {
+    use assert_fs::prelude::*;
+    use camino::Utf8Path;
+    use toml_edit::DocumentMut;
+
+    use crate::error::{CargoMSRVError, SetMsrvError};
+    use crate::manifest::{CargoManifestParser, TomlParser};
+    use crate::sub_command::set::find_workspace_manifest;
+
+    #[test]
+    fn in_parent_directory() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        tmp.child("Cargo.toml")
+            .write_str("[workspace]\nmembers = [\"crates/a\"]\n")
+            .unwrap();
+        tmp.child("crates/a/Cargo.toml")
+            .write_str("[package]\nrust-version.workspace = true\n")
+            .unwrap();
+
+        let root = Utf8Path::from_path(tmp.path()).unwrap();
+        let member = root.join("crates/a/Cargo.toml");
+        let manifest = CargoManifestParser
+            .parse::<DocumentMut>(&std::fs::read_to_string(&member).unwrap())
+            .unwrap();
+
+        let found = find_workspace_manifest(&member, &manifest).unwrap();
+        assert_eq!(found, root.join("Cargo.toml"));
+    }
+
+    #[test]
+    fn not_found() {
+        let tmp = assert_fs::TempDir::new().unwrap();
+        tmp.child("a/Cargo.toml")
+            .write_str("[package]\nrust-version.workspace = true\n")
+            .unwrap();
+
+        let root = Utf8Path::from_path(tmp.path()).unwrap();
+        let member = root.join("a/Cargo.toml");
+        let manifest = CargoManifestParser
+            .parse::<DocumentMut>(&std::fs::read_to_string(&member).unwrap())
+            .unwrap();
+
+        let err = find_workspace_manifest(&member, &manifest).unwrap_err();
+        assert!(matches!(
+            err,
+            CargoMSRVError::SetMsrv(SetMsrvError::WorkspaceManifestNotFound(_))
+        ));
+    }
+}
+
 #[cfg(test)]
 mod valid_release_tests {
     use std::iter::FromIterator;
@@ -1,14 +1,14 @@
 use camino::Utf8PathBuf;
-use cargo_metadata::MetadataCommand;
+use cargo_metadata::{Metadata, MetadataCommand};
 use std::convert::TryFrom;
 
 use crate::context::ShowContext;
 use crate::error::TResult;
 
 use crate::SubCommand;
-use crate::manifest::CargoManifest;
+use crate::manifest::{CargoManifest, package_msrv};
 use crate::reporter::Reporter;
-use crate::reporter::event::ShowResult;
+use crate::reporter::event::{ShowPackage, ShowResult};
 
 #[derive(Default)]
 pub struct Show;
@@ -23,11 +23,17 @@ impl SubCommand for Show {
 }
 
 fn show_msrv(ctx: &ShowContext, reporter: &impl Reporter) -> TResult<()> {
-    // TODO: Add support for workspaces, but take care to also still support raw `rustup run`.
-
     let cargo_toml = ctx.environment.manifest();
 
     let metadata = MetadataCommand::new().manifest_path(&cargo_toml).exec()?;
+
+    // A virtual workspace has no root package, so we show the MSRV of each of its members instead.
+    if ctx.environment.workspace_packages.is_explicit_selection()
+        || metadata.root_package().is_none()
+    {
+        return show_workspace_msrv(ctx, reporter, &metadata);
+    }
+
     let manifest = CargoManifest::try_from(metadata)?;
 
     let msrv = manifest
@@ -39,6 +45,40 @@ fn show_msrv(ctx: &ShowContext, reporter: &impl Reporter) -> TResult<()> {
     Ok(())
 }
 
+/// Show the declared MSRV of each selected workspace package, and where it was specified.
+///
+/// Packages which do not declare an MSRV are listed as well.
+fn show_workspace_msrv(
+    ctx: &ShowContext,
+    reporter: &impl Reporter,
+    metadata: &Metadata,
+) -> TResult<()> {
+    let selected = ctx.environment.workspace_packages.selected_packages();
+
+    let packages = if selected.is_empty() {
+        metadata.workspace_default_packages()
+    } else {
+        selected.iter().collect()
+    };
+
+    let packages = packages
+        .into_iter()
+        .map(|package| {
+            let msrv = package_msrv(package)?;
+
+            Ok(ShowPackage::new(
+                package.name.as_str(),
+                msrv,
+                package.manifest_path.clone(),
+            ))
+        })
+        .collect::<TResult<Vec<_>>>()?;
+
+    reporter.report_event(ShowResult::workspace(packages))?;
+
+    Ok(())
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("MSRV was not specified in Cargo manifest at '{0}'")]
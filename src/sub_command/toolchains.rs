@@ -0,0 +1,117 @@
+use crate::SubCommand;
+use crate::context::ToolchainsContext;
+use crate::context::toolchains::ToolchainsAction;
+use crate::error::TResult;
+use crate::reporter::Reporter;
+use crate::reporter::event::{ToolchainsResult, UninstallToolchain};
+use crate::rust::installed_toolchains::{
+    ListedToolchain, ToolchainRecord, list_installed_toolchains, uninstall_toolchain,
+};
+use std::collections::BTreeSet;
+
+/// Manage the toolchains which were installed by cargo-msrv.
+pub struct Toolchains {
+    record: ToolchainRecord,
+}
+
+impl Toolchains {
+    pub fn new(record: ToolchainRecord) -> Self {
+        Self { record }
+    }
+}
+
+impl SubCommand for Toolchains {
+    type Context = ToolchainsContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        match ctx.action {
+            ToolchainsAction::Prune => {
+                let removed = self.prune(reporter)?;
+                reporter.report_event(ToolchainsResult::pruned(removed))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Toolchains {
+    /// Uninstall each recorded toolchain, unless it is in use, and forget about it.
+    fn prune(&self, reporter: &impl Reporter) -> TResult<Vec<String>> {
+        let recorded = self.record.load()?;
+        let installed = list_installed_toolchains()?;
+
+        let Plan { uninstall, keep } = plan_prune(&recorded, &installed);
+
+        // toolchains which are in use, are kept on the record, so they can be pruned later
+        self.record.store(&keep)?;
+
+        let mut removed = Vec::with_capacity(uninstall.len());
+
+        for toolchain in uninstall {
+            uninstall_toolchain(&toolchain)?;
+            reporter.report_event(UninstallToolchain::new(toolchain.clone()))?;
+            removed.push(toolchain);
+        }
+
+        Ok(removed)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Plan {
+    uninstall: Vec<String>,
+    keep: BTreeSet<String>,
+}
+
+/// Recorded toolchains which are no longer installed are forgotten, while the ones which are the
+/// default or active toolchain are kept.
+fn plan_prune(recorded: &BTreeSet<String>, installed: &[ListedToolchain]) -> Plan {
+    let mut uninstall = Vec::new();
+    let mut keep = BTreeSet::new();
+
+    for toolchain in installed.iter().filter(|t| recorded.contains(&t.name)) {
+        if toolchain.in_use {
+            keep.insert(toolchain.name.clone());
+        } else {
+            uninstall.push(toolchain.name.clone());
+        }
+    }
+
+    Plan { uninstall, keep }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listed(name: &str, in_use: bool) -> ListedToolchain {
+        ListedToolchain {
+            name: name.to_string(),
+            in_use,
+        }
+    }
+
+    #[test]
+    fn prunes_only_recorded_toolchains_not_in_use() {
+        let recorded = ["1.70.0-x", "1.71.0-x", "1.72.0-x"]
+            .map(String::from)
+            .into_iter()
+            .collect();
+        let installed = [
+            listed("stable-x", true),
+            listed("1.69.0-x", false),
+            listed("1.70.0-x", false),
+            listed("1.71.0-x", true),
+        ];
+
+        assert_eq!(
+            plan_prune(&recorded, &installed),
+            Plan {
+                uninstall: vec!["1.70.0-x".to_string()],
+                keep: BTreeSet::from(["1.71.0-x".to_string()]),
+            }
+        );
+    }
+}
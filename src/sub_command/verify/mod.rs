@@ -8,9 +8,13 @@ use crate::reporter::event::VerifyResult;
 use crate::rust::Toolchain;
 use crate::sub_command::SubCommand;
 use cargo_msrv_context::VerifyContext;
-use cargo_msrv_context::context::verify::{RustVersion, RustVersionSource};
+use cargo_msrv_context::context::verify::{RustVersion, RustVersionSource, VerifyRustVersion};
 use cargo_msrv_types::BareVersion;
 
+mod workspace;
+
+pub use workspace::VerifyWorkspace;
+
 /// Verifier which determines whether a given Rust version is deemed compatible or not.
 pub struct Verify<'index, C: IsCompatible> {
     release_index: &'index ReleaseIndex,
@@ -34,19 +38,30 @@ impl<C: IsCompatible> SubCommand for Verify<'_, C> {
     type Output = ();
 
     /// Run the verifier against a Rust version which is obtained from the config.
+    ///
+    /// When verifying workspace packages, the runner is shared by all packages; use
+    /// [`VerifyWorkspace`] to check each package in isolation instead.
     fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
-        // todo!
-        let rust_version = ctx.rust_version.clone();
-
-        verify_msrv(
-            reporter,
-            ctx,
-            self.release_index,
-            rust_version,
-            &self.runner,
-        )?;
-
-        Ok(())
+        match &ctx.rust_version {
+            VerifyRustVersion::Crate(rust_version) => verify_msrv(
+                reporter,
+                ctx,
+                self.release_index,
+                rust_version.clone(),
+                &self.runner,
+            ),
+            VerifyRustVersion::Workspace(packages) => {
+                workspace::verify_workspace_msrv(
+                    reporter,
+                    ctx,
+                    self.release_index,
+                    packages,
+                    |_package, toolchain| self.runner.is_compatible(toolchain),
+                )?;
+
+                Ok(())
+            }
+        }
     }
 }
 
@@ -102,6 +117,17 @@ pub enum Error {
         "Crate source was found to be incompatible with Rust version '{}' specified {}", .0.rust_version, .0.source
     )]
     VerifyFailed(VerifyFailed),
+
+    #[error(
+        "Workspace packages were found to be incompatible with their Rust version: {}",
+        .0.iter().map(|failed| format!("'{}' (Rust {})", failed.package, failed.failed.rust_version)).collect::<Vec<_>>().join(", ")
+    )]
+    WorkspaceVerifyFailed(Vec<PackageVerifyFailed>),
+
+    #[error(
+        "None of the selected workspace packages has a Rust version; set the 'package.rust-version' key in their Cargo manifest, or give one with '--rust-version'"
+    )]
+    NoPackageRustVersion,
 }
 
 /// Data structure which contains information about which version failed to verify, and where
@@ -125,3 +151,16 @@ impl From<RustVersion> for VerifyFailed {
         }
     }
 }
+
+/// Like [`VerifyFailed`], but for a single package of a workspace.
+#[derive(Debug)]
+pub struct PackageVerifyFailed {
+    package: String,
+    failed: VerifyFailed,
+}
+
+impl PackageVerifyFailed {
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+}
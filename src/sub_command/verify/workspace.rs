@@ -0,0 +1,375 @@
+use std::collections::BTreeMap;
+
+use rust_releases::{Release, ReleaseIndex};
+
+use super::{Error, PackageVerifyFailed, VerifyFailed};
+use crate::SubCommand;
+use crate::compatibility::IsCompatible;
+use crate::error::{CargoMSRVError, TResult};
+use crate::outcome::Compatibility;
+use crate::reporter::Reporter;
+use crate::reporter::event::{
+    PackageVerifyStatus, VerifyResult, VerifyStatus, VerifyWorkspaceSummary,
+};
+use crate::rust::Toolchain;
+use crate::semver;
+use cargo_msrv_context::VerifyContext;
+use cargo_msrv_context::context::verify::{PackageRustVersion, RustVersion, VerifyRustVersion};
+
+/// Verify the MSRV of each selected package of a Cargo workspace.
+///
+/// Each package is checked in isolation (i.e. using `cargo check --package <name>`), using a
+/// runner which is provided by the `runner_factory` for the package with the given name.
+pub struct VerifyWorkspace<'index, F> {
+    release_index: &'index ReleaseIndex,
+    runner_factory: F,
+}
+
+impl<'index, C, F> VerifyWorkspace<'index, F>
+where
+    C: IsCompatible,
+    F: Fn(&str) -> C,
+{
+    pub fn new(release_index: &'index ReleaseIndex, runner_factory: F) -> Self {
+        Self {
+            release_index,
+            runner_factory,
+        }
+    }
+}
+
+impl<C, F> SubCommand for VerifyWorkspace<'_, F>
+where
+    C: IsCompatible,
+    F: Fn(&str) -> C,
+{
+    type Context = VerifyContext;
+    type Output = ();
+
+    /// Fails if any of the selected packages is not compatible with its Rust version.
+    ///
+    /// If the context holds a single Rust version, each selected package is verified against it.
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let packages = match &ctx.rust_version {
+            VerifyRustVersion::Workspace(packages) => packages.clone(),
+            VerifyRustVersion::Crate(rust_version) => ctx
+                .environment
+                .workspace_packages
+                .selected_packages()
+                .iter()
+                .map(|package| PackageRustVersion {
+                    name: package.name.to_string(),
+                    rust_version: Some(rust_version.clone()),
+                })
+                .collect(),
+        };
+
+        verify_workspace_msrv(
+            reporter,
+            ctx,
+            self.release_index,
+            &packages,
+            |package, toolchain| (self.runner_factory)(&package.name).is_compatible(toolchain),
+        )
+    }
+}
+
+/// Verify each package against its Rust version.
+///
+/// Packages which share the same Rust version are verified one after another, so each toolchain
+/// only needs to be installed once. Packages without a Rust version are skipped, and reported as
+/// such in the summary, unless no package has a Rust version at all.
+pub(super) fn verify_workspace_msrv(
+    reporter: &impl Reporter,
+    ctx: &VerifyContext,
+    release_index: &ReleaseIndex,
+    packages: &[PackageRustVersion],
+    check: impl Fn(&PackageRustVersion, &Toolchain) -> TResult<Compatibility>,
+) -> TResult<()> {
+    let mut groups = BTreeMap::<semver::Version, Vec<(&PackageRustVersion, &RustVersion)>>::new();
+    let mut summary = Vec::with_capacity(packages.len());
+
+    for package in packages {
+        let Some(rust_version) = &package.rust_version else {
+            info!(
+                package = package.name,
+                "skipping package without rust-version"
+            );
+            summary.push(PackageVerifyStatus::unset(&package.name));
+            continue;
+        };
+
+        let version = rust_version
+            .version()
+            .try_to_semver(release_index.releases().iter().map(Release::version))?;
+
+        groups
+            .entry(version.clone())
+            .or_default()
+            .push((package, rust_version));
+    }
+
+    if groups.is_empty() {
+        return Err(CargoMSRVError::SubCommandVerify(
+            Error::NoPackageRustVersion,
+        ));
+    }
+
+    let mut failures = Vec::new();
+
+    for (version, group) in groups {
+        let toolchain = Toolchain::new(version, ctx.toolchain.target, ctx.toolchain.components);
+
+        for (package, rust_version) in group {
+            info!(package = package.name, version = %toolchain.version(), "verifying package MSRV");
+
+            let status = match check(package, &toolchain)? {
+                Compatibility::Compatible(_) => {
+                    reporter.report_event(
+                        VerifyResult::compatible(toolchain.clone()).with_package(&package.name),
+                    )?;
+
+                    VerifyStatus::Compatible
+                }
+                Compatibility::Incompatible(f) => {
+                    reporter.report_event(
+                        VerifyResult::incompatible(toolchain.clone(), Some(f.error_message))
+                            .with_package(&package.name),
+                    )?;
+
+                    failures.push(PackageVerifyFailed {
+                        package: package.name.clone(),
+                        failed: VerifyFailed::from(rust_version.clone()),
+                    });
+
+                    VerifyStatus::Incompatible
+                }
+            };
+
+            summary.push(PackageVerifyStatus::new(
+                &package.name,
+                Some(toolchain.version().clone()),
+                status,
+            ));
+        }
+    }
+
+    // report the packages in the order in which they were selected
+    summary.sort_by_key(|status| packages.iter().position(|p| p.name == status.name));
+    reporter.report_event(VerifyWorkspaceSummary::new(summary))?;
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(CargoMSRVError::SubCommandVerify(
+            Error::WorkspaceVerifyFailed(failures),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compatibility::TestRunner;
+    use crate::context::{
+        CheckCommandContext, EnvironmentContext, RustReleasesContext, TargetDirCleanup,
+        ToolchainContext, ToolchainSource, WorkspacePackages,
+    };
+    use crate::reporter::{Event, TestReporterWrapper};
+    use camino::Utf8PathBuf;
+    use cargo_msrv_context::context::verify::RustVersion;
+    use cargo_msrv_context::types::ReleaseSource;
+    use cargo_msrv_types::BareVersion;
+    use std::iter::FromIterator;
+
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 58, 1)),
+            Release::new_stable(semver::Version::new(1, 57, 0)),
+            Release::new_stable(semver::Version::new(1, 56, 1)),
+        ])
+    }
+
+    fn package(name: &str, version: BareVersion) -> PackageRustVersion {
+        PackageRustVersion {
+            name: name.to_string(),
+            rust_version: Some(RustVersion::from_arg(version)),
+        }
+    }
+
+    fn unset_package(name: &str) -> PackageRustVersion {
+        PackageRustVersion {
+            name: name.to_string(),
+            rust_version: None,
+        }
+    }
+
+    // Packages `a` and `c` share the same Rust version, `b` requires a newer one
+    fn create_test_context() -> VerifyContext {
+        VerifyContext {
+            rust_version: VerifyRustVersion::Workspace(vec![
+                package("a", BareVersion::TwoComponents(1, 56)),
+                package("b", BareVersion::ThreeComponents(1, 58, 1)),
+                package("c", BareVersion::ThreeComponents(1, 56, 1)),
+            ]),
+            ignore_lockfile: false,
+            resolve_msrv_lockfile: false,
+            isolated: false,
+            target_dir_cleanup: TargetDirCleanup::Keep,
+            cleanup_toolchains: false,
+            cache: false,
+            no_check_feedback: false,
+            rust_releases: RustReleasesContext {
+                minimum_rust_version: None,
+                maximum_rust_version: None,
+                consider_patch_releases: false,
+                release_source: ReleaseSource::RustChangelog,
+                offline: false,
+            },
+            toolchain: ToolchainContext {
+                target: "x",
+                components: &[],
+                source: ToolchainSource::Rustup,
+            },
+            check_cmd: CheckCommandContext {
+                cargo_features: None,
+                cargo_all_features: false,
+                cargo_no_default_features: false,
+                rustup_command: None,
+            },
+            environment: EnvironmentContext {
+                root_crate_path: Utf8PathBuf::new(),
+                workspace_packages: WorkspacePackages::default(),
+            },
+        }
+    }
+
+    fn toolchain(version: semver::Version) -> Toolchain {
+        Toolchain::new(version, "x", &[])
+    }
+
+    #[test]
+    fn packages_grouped_by_version() {
+        let ctx = create_test_context();
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let factory = |_package: &str| {
+            TestRunner::with_ok(
+                "x",
+                &[
+                    semver::Version::new(1, 56, 1),
+                    semver::Version::new(1, 58, 1),
+                ],
+            )
+        };
+
+        VerifyWorkspace::new(&index, factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+
+        let v56 = semver::Version::new(1, 56, 1);
+        let v58 = semver::Version::new(1, 58, 1);
+        let expected: Vec<Event> = vec![
+            VerifyResult::compatible(toolchain(v56.clone()))
+                .with_package("a")
+                .into(),
+            VerifyResult::compatible(toolchain(v56.clone()))
+                .with_package("c")
+                .into(),
+            VerifyResult::compatible(toolchain(v58.clone()))
+                .with_package("b")
+                .into(),
+            VerifyWorkspaceSummary::new(vec![
+                PackageVerifyStatus::new("a", Some(v56.clone()), VerifyStatus::Compatible),
+                PackageVerifyStatus::new("b", Some(v58), VerifyStatus::Compatible),
+                PackageVerifyStatus::new("c", Some(v56), VerifyStatus::Compatible),
+            ])
+            .into(),
+        ];
+
+        assert_eq!(reporter.wait_for_events(), expected);
+    }
+
+    #[test]
+    fn fails_if_any_package_fails() {
+        let ctx = create_test_context();
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let factory = |package: &str| match package {
+            "b" => TestRunner::with_ok("x", &[]),
+            _ => TestRunner::with_ok("x", &[semver::Version::new(1, 56, 1)]),
+        };
+
+        let result = VerifyWorkspace::new(&index, factory).run(&ctx, reporter.get());
+
+        let Err(CargoMSRVError::SubCommandVerify(Error::WorkspaceVerifyFailed(failures))) = result
+        else {
+            panic!("expected the workspace verification to fail");
+        };
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].package(), "b");
+
+        let events = reporter.wait_for_events();
+        assert_eq!(
+            events[events.len() - 2],
+            VerifyResult::incompatible(
+                toolchain(semver::Version::new(1, 58, 1)),
+                Some("f".to_string())
+            )
+            .with_package("b")
+            .into()
+        );
+    }
+
+    #[test]
+    fn skips_package_without_rust_version() {
+        let mut ctx = create_test_context();
+        ctx.rust_version = VerifyRustVersion::Workspace(vec![
+            package("a", BareVersion::TwoComponents(1, 56)),
+            unset_package("b"),
+        ]);
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let factory = |_package: &str| TestRunner::with_ok("x", &[semver::Version::new(1, 56, 1)]);
+
+        VerifyWorkspace::new(&index, factory)
+            .run(&ctx, reporter.get())
+            .unwrap();
+
+        let v56 = semver::Version::new(1, 56, 1);
+        let expected: Vec<Event> = vec![
+            VerifyResult::compatible(toolchain(v56.clone()))
+                .with_package("a")
+                .into(),
+            VerifyWorkspaceSummary::new(vec![
+                PackageVerifyStatus::new("a", Some(v56), VerifyStatus::Compatible),
+                PackageVerifyStatus::unset("b"),
+            ])
+            .into(),
+        ];
+
+        assert_eq!(reporter.wait_for_events(), expected);
+    }
+
+    #[test]
+    fn fails_if_no_package_has_rust_version() {
+        let mut ctx = create_test_context();
+        ctx.rust_version = VerifyRustVersion::Workspace(vec![unset_package("a")]);
+        let index = index();
+        let reporter = TestReporterWrapper::default();
+
+        let factory = |_package: &str| TestRunner::with_ok("x", &[]);
+        let result = VerifyWorkspace::new(&index, factory).run(&ctx, reporter.get());
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::SubCommandVerify(
+                Error::NoPackageRustVersion
+            ))
+        ));
+    }
+}
@@ -5,6 +5,8 @@ use std::path::{Path, PathBuf};
 pub mod reporter;
 pub mod runner;
 pub mod sub_cmd_find;
+pub mod sub_cmd_set;
+pub mod sub_cmd_show;
 pub mod sub_cmd_verify;
 
 pub struct Fixture {
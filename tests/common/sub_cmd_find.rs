@@ -1,6 +1,6 @@
 use crate::common::reporter::EventTestDevice;
 use cargo_msrv::cli::CargoCli;
-use cargo_msrv::compatibility::{RunCommandProvider, RustupToolchainCheck};
+use cargo_msrv::compatibility::{RunCommandProvider, ToolchainCheck};
 use cargo_msrv::error::CargoMSRVError;
 use cargo_msrv::reporter::{Message, SubcommandResult};
 use cargo_msrv::{Context, Find, SubCommand};
@@ -70,7 +70,7 @@ pub fn find_msrv_with_releases<
     let no_check_feedback = find_ctx.no_check_feedback;
     let env = &find_ctx.environment;
 
-    let runner = RustupToolchainCheck::new(
+    let runner = ToolchainCheck::new(
         device.reporter(),
         ignore_toolchain,
         no_check_feedback,
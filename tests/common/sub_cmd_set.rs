@@ -0,0 +1,25 @@
+use crate::common::reporter::EventTestDevice;
+use cargo_msrv::cli::CargoCli;
+use cargo_msrv::error::CargoMSRVError;
+use cargo_msrv::{Context, Set, SubCommand};
+use std::convert::TryFrom;
+use std::ffi::OsString;
+
+pub fn run_set<I, T>(with_args: I) -> Result<(), CargoMSRVError>
+where
+    T: Into<OsString> + Clone,
+    I: IntoIterator<Item = T>,
+{
+    let matches = CargoCli::parse_args(with_args);
+    let opts = matches.to_cargo_msrv_cli().to_opts();
+    let ctx = Context::try_from(opts)?;
+
+    let Context::Set(set_ctx) = ctx else {
+        panic!("expected subcommand 'cargo msrv set'");
+    };
+
+    let device = EventTestDevice::default();
+
+    // Without a release index, the given MSRV is not checked against the available Rust releases.
+    Set::new(None).run(&set_ctx, device.reporter())
+}
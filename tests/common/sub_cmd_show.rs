@@ -0,0 +1,36 @@
+use crate::common::reporter::EventTestDevice;
+use cargo_msrv::cli::CargoCli;
+use cargo_msrv::error::CargoMSRVError;
+use cargo_msrv::reporter::{Message, SubcommandResult, event::ShowResult};
+use cargo_msrv::{Context, Show, SubCommand};
+use std::convert::TryFrom;
+use std::ffi::OsString;
+
+pub fn run_show<I, T>(with_args: I) -> Result<ShowResult, CargoMSRVError>
+where
+    T: Into<OsString> + Clone,
+    I: IntoIterator<Item = T>,
+{
+    let matches = CargoCli::parse_args(with_args);
+    let opts = matches.to_cargo_msrv_cli().to_opts();
+    let ctx = Context::try_from(opts)?;
+
+    let Context::Show(show_ctx) = ctx else {
+        panic!("expected subcommand 'cargo msrv show'");
+    };
+
+    let device = EventTestDevice::default();
+
+    Show.run(&show_ctx, device.reporter())?;
+
+    let result = device
+        .wait_for_events()
+        .into_iter()
+        .find_map(|event| match event.message() {
+            Message::SubcommandResult(SubcommandResult::Show(result)) => Some(result.clone()),
+            _ => None,
+        })
+        .expect("expected 'cargo msrv show' to report a result");
+
+    Ok(result)
+}
@@ -1,6 +1,6 @@
 use crate::common::reporter::EventTestDevice;
 use cargo_msrv::cli::CargoCli;
-use cargo_msrv::compatibility::{RunCommandProvider, RustupToolchainCheck};
+use cargo_msrv::compatibility::{RunCommandProvider, ToolchainCheck};
 use cargo_msrv::error::CargoMSRVError;
 use cargo_msrv::{Context, SubCommand, Verify};
 use rust_releases::{Release, ReleaseIndex};
@@ -29,7 +29,7 @@ where
     let no_check_feedback = verify_ctx.no_check_feedback;
     let env = &verify_ctx.environment;
 
-    let runner = RustupToolchainCheck::new(
+    let runner = ToolchainCheck::new(
         device.reporter(),
         ignore_toolchain,
         no_check_feedback,
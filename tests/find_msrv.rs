@@ -409,3 +409,177 @@ fn cargo_no_default_features_flag() {
         ]
     );
 }
+
+mod rejected_combinations {
+    use crate::common::Fixture;
+    use cargo_msrv::Context;
+    use cargo_msrv::cli::CargoCli;
+    use cargo_msrv::context::error::Error;
+    use std::convert::TryFrom;
+
+    fn context(fixture: &Fixture, args: &[&str]) -> Result<Context, Error> {
+        let with_args = ["cargo", "msrv", "--path", fixture.to_str()]
+            .into_iter()
+            .chain(args.iter().copied());
+        let matches = CargoCli::parse_args(with_args);
+
+        Context::try_from(matches.to_cargo_msrv_cli().to_opts())
+    }
+
+    #[yare::parameterized(
+        workspace = { &["--workspace", "find", "--target", "x", "--target", "y"] },
+        packages = { &["--package", "a", "--package", "b", "find", "--target", "x", "--target", "y"] },
+    )]
+    fn multiple_targets_with_selected_packages(args: &[&str]) {
+        let fixture = Fixture::new("virtual-workspace");
+
+        assert!(matches!(
+            context(&fixture, args),
+            Err(Error::MultipleTargetsCombinedWith(_))
+        ));
+    }
+
+    #[yare::parameterized(
+        workspace_powerset = { &["--workspace", "find", "--feature-powerset"] },
+        workspace_each_feature = { &["--workspace", "find", "--each-feature"] },
+        packages_powerset = { &["--package", "a", "--package", "b", "find", "--feature-powerset"] },
+        packages_each_feature = { &["find", "--package", "a", "--package", "b", "--each-feature"] },
+    )]
+    fn feature_sets_with_multiple_packages(args: &[&str]) {
+        let fixture = Fixture::new("virtual-workspace");
+
+        assert!(matches!(
+            context(&fixture, args),
+            Err(Error::FeatureSetsRequireSinglePackage { selected: 2 })
+        ));
+    }
+
+    #[yare::parameterized(
+        powerset = { &["find", "--target", "x", "--target", "y", "--feature-powerset"] },
+        each_feature = { &["find", "--target", "x", "--target", "y", "--each-feature"] },
+    )]
+    fn multiple_targets_with_feature_sets(args: &[&str]) {
+        let fixture = Fixture::new("1.56.0-edition-2021");
+
+        assert!(matches!(
+            context(&fixture, args),
+            Err(Error::MultipleTargetsCombinedWith(_))
+        ));
+    }
+
+    #[yare::parameterized(
+        workspace = { &["--workspace", "find", "--", "cargo", "check"] },
+        package = { &["--package", "a", "find", "--", "cargo", "check"] },
+        verify = { &["--workspace", "verify", "--", "cargo", "check"] },
+    )]
+    fn custom_check_command_with_selected_packages(args: &[&str]) {
+        let fixture = Fixture::new("virtual-workspace");
+
+        assert!(matches!(
+            context(&fixture, args),
+            Err(Error::CustomCheckCommandWithPackageSelection)
+        ));
+    }
+
+    #[test]
+    fn feature_sets_with_single_package() {
+        let fixture = Fixture::new("virtual-workspace");
+
+        assert!(context(&fixture, &["--package", "a", "find", "--feature-powerset"]).is_ok());
+    }
+
+    #[yare::parameterized(
+        default = { None },
+        bisect = { Some("bisect") },
+        anchored = { Some("anchored") },
+    )]
+    fn jobs_without_concurrent_search(configured: Option<&str>) {
+        let fixture = Fixture::new("1.56.0-edition-2021");
+        if let Some(search_method) = configured {
+            std::fs::write(
+                fixture.tmp_path("cargo-msrv.toml"),
+                format!("search-method = '{}'", search_method),
+            )
+            .unwrap();
+        }
+
+        assert!(matches!(
+            context(&fixture, &["find", "--jobs", "2"]),
+            Err(Error::JobsRequireConcurrentSearch { .. })
+        ));
+    }
+
+    // The search method may be configured by the project, rather than given on the command line
+    #[yare::parameterized(
+        linear = { "linear" },
+        multi_bisect = { "multi-bisect" },
+    )]
+    fn jobs_with_configured_concurrent_search(search_method: &str) {
+        let fixture = Fixture::new("1.56.0-edition-2021");
+        std::fs::write(
+            fixture.tmp_path("cargo-msrv.toml"),
+            format!("search-method = '{}'", search_method),
+        )
+        .unwrap();
+
+        assert!(context(&fixture, &["find", "--jobs", "2"]).is_ok());
+    }
+}
+
+mod toolchain_dir {
+    use crate::common::Fixture;
+    use cargo_msrv::Context;
+    use cargo_msrv::cli::CargoCli;
+    use cargo_msrv::context::error::Error;
+    use std::convert::TryFrom;
+
+    fn context(fixture: &Fixture, toolchain_dir: &std::path::Path) -> Result<Context, Error> {
+        let matches = CargoCli::parse_args([
+            "cargo",
+            "msrv",
+            "--path",
+            fixture.to_str(),
+            "find",
+            "--toolchain-dir",
+            toolchain_dir.to_str().unwrap(),
+        ]);
+
+        Context::try_from(matches.to_cargo_msrv_cli().to_opts())
+    }
+
+    // The host triple is taken from the toolchains in the directory, rather than from rustup
+    #[cfg(unix)]
+    #[test]
+    fn host_triple_of_toolchain_in_dir() {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let fixture = Fixture::new("1.56.0-edition-2021");
+        let toolchains = assert_fs::TempDir::new().unwrap();
+        let bin = toolchains.path().join("1.70.0").join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(
+            bin.join("rustc"),
+            "#!/bin/sh\necho 'rustc 1.70.0'\necho 'host: x86_64-cargo-msrv-test'\n",
+        )
+        .unwrap();
+        fs::set_permissions(bin.join("rustc"), fs::Permissions::from_mode(0o755)).unwrap();
+
+        let Ok(Context::Find(ctx)) = context(&fixture, toolchains.path()) else {
+            panic!("expected find context");
+        };
+
+        assert_eq!(ctx.toolchain.target, "x86_64-cargo-msrv-test");
+    }
+
+    #[test]
+    fn no_toolchain_in_dir() {
+        let fixture = Fixture::new("1.56.0-edition-2021");
+        let toolchains = assert_fs::TempDir::new().unwrap();
+
+        assert!(matches!(
+            context(&fixture, toolchains.path()),
+            Err(Error::NoToolchainInDirectory(_))
+        ));
+    }
+}
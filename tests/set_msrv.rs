@@ -0,0 +1,187 @@
+use crate::common::{Fixture, sub_cmd_set::run_set};
+use std::path::Path;
+
+mod common;
+
+fn read_manifest(path: impl AsRef<Path>) -> toml_edit::DocumentMut {
+    let contents = std::fs::read_to_string(path.as_ref().join("Cargo.toml")).unwrap();
+    contents.parse().unwrap()
+}
+
+#[test]
+fn set_inherited_msrv_from_workspace_root() {
+    let fixture = Fixture::new("workspace-inheritance");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        fixture.to_str(),
+        "--no-user-output",
+        "set",
+        "1.70",
+    ];
+
+    run_set(with_args).unwrap();
+
+    let workspace = read_manifest(fixture.tmp_path(""));
+    assert_eq!(
+        workspace["workspace"]["package"]["rust-version"].as_str(),
+        Some("1.70")
+    );
+
+    // The member still inherits its MSRV from the workspace
+    let b = read_manifest(fixture.tmp_path("b"));
+    assert_eq!(
+        b["package"]["rust-version"]["workspace"].as_bool(),
+        Some(true)
+    );
+}
+
+#[test]
+fn set_inherited_msrv_from_member() {
+    let fixture = Fixture::new("workspace-inheritance");
+    let package = fixture.tmp_path("b");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        package.to_str().unwrap(),
+        "--no-user-output",
+        "set",
+        "1.70",
+    ];
+
+    run_set(with_args).unwrap();
+
+    let workspace = read_manifest(fixture.tmp_path(""));
+    assert_eq!(
+        workspace["workspace"]["package"]["rust-version"].as_str(),
+        Some("1.70")
+    );
+
+    let b = read_manifest(&package);
+    assert_eq!(
+        b["package"]["rust-version"]["workspace"].as_bool(),
+        Some(true)
+    );
+}
+
+#[test]
+fn set_msrv_of_member_with_own_msrv() {
+    let fixture = Fixture::new("workspace-inheritance");
+    let package = fixture.tmp_path("a");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        package.to_str().unwrap(),
+        "--no-user-output",
+        "set",
+        "1.70",
+    ];
+
+    run_set(with_args).unwrap();
+
+    let a = read_manifest(&package);
+    assert_eq!(a["package"]["rust-version"].as_str(), Some("1.70"));
+
+    // The workspace MSRV is left untouched
+    let workspace = read_manifest(fixture.tmp_path(""));
+    assert_eq!(
+        workspace["workspace"]["package"]["rust-version"].as_str(),
+        Some("1.66.0")
+    );
+}
+
+#[test]
+fn set_selected_package_only() {
+    let fixture = Fixture::new("workspace-inheritance");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        fixture.to_str(),
+        "--no-user-output",
+        "set",
+        "--package",
+        "b",
+        "1.70",
+    ];
+
+    run_set(with_args).unwrap();
+
+    // The selected member no longer inherits its MSRV
+    let b = read_manifest(fixture.tmp_path("b"));
+    assert_eq!(b["package"]["rust-version"].as_str(), Some("1.70"));
+
+    let workspace = read_manifest(fixture.tmp_path(""));
+    assert_eq!(
+        workspace["workspace"]["package"]["rust-version"].as_str(),
+        Some("1.66.0")
+    );
+
+    let a = read_manifest(fixture.tmp_path("a"));
+    assert_eq!(a["package"]["rust-version"].as_str(), Some("1.64.0"));
+}
+
+#[test]
+fn set_virtual_workspace() {
+    let fixture = Fixture::new("virtual-workspace");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        fixture.to_str(),
+        "--no-user-output",
+        "set",
+        "1.70",
+    ];
+
+    run_set(with_args).unwrap();
+
+    let workspace = read_manifest(fixture.tmp_path(""));
+    assert_eq!(
+        workspace["workspace"]["package"]["rust-version"].as_str(),
+        Some("1.70")
+    );
+    assert_eq!(
+        workspace["workspace"]["members"]
+            .as_array()
+            .map(|members| members.len()),
+        Some(2)
+    );
+}
+
+#[test]
+fn set_virtual_workspace_selected_packages() {
+    let fixture = Fixture::new("virtual-workspace");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        fixture.to_str(),
+        "--no-user-output",
+        "set",
+        "-p",
+        "a",
+        "-p",
+        "b",
+        "1.70",
+    ];
+
+    run_set(with_args).unwrap();
+
+    for member in ["a", "b"] {
+        let manifest = read_manifest(fixture.tmp_path(member));
+        assert_eq!(manifest["package"]["rust-version"].as_str(), Some("1.70"));
+    }
+
+    let workspace = read_manifest(fixture.tmp_path(""));
+    assert!(workspace["workspace"].get("package").is_none());
+}
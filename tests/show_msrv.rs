@@ -0,0 +1,127 @@
+use crate::common::{Fixture, sub_cmd_show::run_show};
+use cargo_msrv::manifest::MsrvOrigin;
+use cargo_msrv::reporter::event::ShowPackage;
+use cargo_msrv_types::BareVersion;
+
+mod common;
+
+fn origins(packages: &[ShowPackage]) -> Vec<(&str, Option<&BareVersion>, Option<MsrvOrigin>)> {
+    packages
+        .iter()
+        .map(|package| {
+            (
+                package.name.as_str(),
+                package.version.as_ref(),
+                package.origin,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn show_workspace_lists_msrv_origin_of_each_member() {
+    let fixture = Fixture::new("workspace-inheritance");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        fixture.to_str(),
+        "--no-user-output",
+        "--workspace",
+        "show",
+    ];
+
+    let result = run_show(with_args).unwrap();
+    let packages = result.packages().unwrap();
+
+    assert_eq!(
+        origins(packages),
+        vec![
+            (
+                "a",
+                Some(&BareVersion::ThreeComponents(1, 64, 0)),
+                Some(MsrvOrigin::PackageRustVersion)
+            ),
+            (
+                "b",
+                Some(&BareVersion::ThreeComponents(1, 66, 0)),
+                Some(MsrvOrigin::WorkspacePackageRustVersion)
+            ),
+            (
+                "c",
+                Some(&BareVersion::TwoComponents(1, 64)),
+                Some(MsrvOrigin::PackageMetadataMsrv)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn show_selected_package() {
+    let fixture = Fixture::new("workspace-inheritance");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        fixture.to_str(),
+        "--no-user-output",
+        "--package",
+        "b",
+        "show",
+    ];
+
+    let result = run_show(with_args).unwrap();
+    let packages = result.packages().unwrap();
+
+    assert_eq!(
+        origins(packages),
+        vec![(
+            "b",
+            Some(&BareVersion::ThreeComponents(1, 66, 0)),
+            Some(MsrvOrigin::WorkspacePackageRustVersion)
+        )]
+    );
+}
+
+#[test]
+fn show_virtual_workspace_without_flags() {
+    let fixture = Fixture::new("workspace-inheritance");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        fixture.to_str(),
+        "--no-user-output",
+        "show",
+    ];
+
+    let result = run_show(with_args).unwrap();
+
+    assert_eq!(result.packages().unwrap().len(), 3);
+}
+
+#[test]
+fn show_package() {
+    let fixture = Fixture::new("workspace-inheritance");
+    let package = fixture.tmp_path("a");
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "--path",
+        package.to_str().unwrap(),
+        "--no-user-output",
+        "show",
+    ];
+
+    let result = run_show(with_args).unwrap();
+
+    assert_eq!(
+        result.version(),
+        Some(&BareVersion::ThreeComponents(1, 64, 0))
+    );
+    assert!(result.packages().is_none());
+}
@@ -264,3 +264,111 @@ fn manifest_path() {
 
     assert!(result.is_ok());
 }
+
+mod workspace {
+    use crate::common::Fixture;
+    use cargo_msrv::Context;
+    use cargo_msrv::cli::CargoCli;
+    use cargo_msrv::context::verify::VerifyRustVersion;
+    use cargo_msrv_types::BareVersion;
+    use std::convert::TryFrom;
+
+    fn resolve_rust_versions(with_args: Vec<&str>) -> Vec<(String, Option<BareVersion>)> {
+        let matches = CargoCli::parse_args(with_args);
+        let ctx = Context::try_from(matches.to_cargo_msrv_cli().to_opts()).unwrap();
+        let verify_ctx = ctx.to_verify_context().unwrap();
+
+        let VerifyRustVersion::Workspace(packages) = verify_ctx.rust_version else {
+            panic!("expected the Rust version of each workspace package");
+        };
+
+        packages
+            .into_iter()
+            .map(|package| {
+                let version = package.rust_version.map(|version| version.into_version());
+                (package.name, version)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolves_msrv_per_package() {
+        let fixture = Fixture::new("workspace-inheritance");
+
+        let with_args = vec![
+            "cargo",
+            "msrv",
+            "--path",
+            fixture.to_str(),
+            "--workspace",
+            "verify",
+        ];
+
+        assert_eq!(
+            resolve_rust_versions(with_args),
+            vec![
+                (
+                    "a".to_string(),
+                    Some(BareVersion::ThreeComponents(1, 64, 0))
+                ),
+                (
+                    "b".to_string(),
+                    Some(BareVersion::ThreeComponents(1, 66, 0))
+                ),
+                ("c".to_string(), Some(BareVersion::TwoComponents(1, 64))),
+            ]
+        );
+    }
+
+    #[test]
+    fn rust_version_argument_applies_to_each_package() {
+        let fixture = Fixture::new("workspace-inheritance");
+
+        let with_args = vec![
+            "cargo",
+            "msrv",
+            "--path",
+            fixture.to_str(),
+            "--package",
+            "a",
+            "--package",
+            "b",
+            "verify",
+            "--rust-version",
+            "1.70",
+        ];
+
+        assert_eq!(
+            resolve_rust_versions(with_args),
+            vec![
+                ("a".to_string(), Some(BareVersion::TwoComponents(1, 70))),
+                ("b".to_string(), Some(BareVersion::TwoComponents(1, 70))),
+            ]
+        );
+    }
+
+    #[test]
+    fn package_without_rust_version_is_unset() {
+        let fixture = Fixture::new("workspace-unset-member");
+
+        let with_args = vec![
+            "cargo",
+            "msrv",
+            "--path",
+            fixture.to_str(),
+            "--workspace",
+            "verify",
+        ];
+
+        assert_eq!(
+            resolve_rust_versions(with_args),
+            vec![
+                (
+                    "a".to_string(),
+                    Some(BareVersion::ThreeComponents(1, 56, 0))
+                ),
+                ("b".to_string(), None),
+            ]
+        );
+    }
+}